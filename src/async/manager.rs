@@ -0,0 +1,62 @@
+use flume::RecvTimeoutError;
+
+use crate::{Role, TickCommand, TickInfo, TickManager, TickManagerHandle, TickMember};
+
+impl TickManager {
+    /// shuts the Tick Manager down and resolves once its thread returned, regardless of the
+    /// DropPolicy
+    pub async fn shutdown_async(mut self) {
+        if let Some((_thread, exited)) = self.request_shutdown() {
+            // only ever disconnects
+            let _ = exited.recv_async().await;
+        }
+    }
+}
+
+impl TickManagerHandle {
+    /// resolves once the next main frame starts, without registering a member.
+    /// resolves to None if the Tick Manager has shut down
+    pub async fn next_frame(&self) -> Option<TickInfo> {
+        let (sender, receiver) = flume::bounded(1);
+        self.send(TickCommand::Observe(sender)).ok()?;
+        receiver.recv_async().await.ok()
+    }
+
+    /// resolves once the predicate is true for a main frame, see TickManagerHandle::wait_until.
+    /// resolves to None if the Tick Manager has shut down
+    pub async fn frame_where(
+        &self,
+        predicate: impl FnMut(&TickInfo) -> bool + Send + 'static,
+    ) -> Option<TickInfo> {
+        self.frame_condition(predicate)?.recv_async().await.ok()
+    }
+}
+
+impl<R: Role> TickMember<R> {
+    /// like TickMember::wait_for_tick, but awaits the next tick instead of blocking the thread.
+    /// members with WakeStrategy::Park get their ticks over the channel while they are awaited
+    pub async fn wait_for_tick_async(&self) -> Option<TickInfo> {
+        if !self.is_registered() {
+            return None;
+        }
+        let receiver = self.report_finished_unparked();
+        loop {
+            let reply = receiver
+                .recv_async()
+                .await
+                .map_err(|_| RecvTimeoutError::Disconnected);
+            if self.on_wait_reply(reply) {
+                return self.received_tick();
+            }
+        }
+    }
+}
+
+#[cfg(feature = "watch")]
+impl crate::TickWatch {
+    /// resolves once a frame newer than the one last seen started, see TickWatch::changed
+    pub async fn changed_async(&mut self) -> Result<TickInfo, crate::TickError> {
+        let reached = self.clock.wait_for_frame_async(self.seen + 1).await;
+        self.seen_after(reached)
+    }
+}
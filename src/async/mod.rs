@@ -0,0 +1,67 @@
+//! awaitable frames, nothing in here blocks the calling thread.
+//! the frames of a TickClock come from a FrameDriver polled by the caller's runtime, or with the
+//! sync feature from the Tick Manager thread
+
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::{FrameWaiter, TickClock};
+
+#[cfg(feature = "sync")]
+mod manager;
+
+impl TickClock {
+    /// resolves once main frame `frame` started to the latest frame number, see
+    /// TickClock::wait_for_frame. the task's waker is kept by the clock until then, or until
+    /// the future is dropped
+    pub async fn wait_for_frame_async(&self, frame: u64) -> u64 {
+        FrameWait {
+            clock: self,
+            frame,
+            id: None,
+        }
+        .await
+    }
+}
+
+/// registers its waiter with the clock once and only swaps the waker on later polls
+struct FrameWait<'a> {
+    clock: &'a TickClock,
+    frame: u64,
+    id: Option<u64>,
+}
+
+impl Future for FrameWait<'_> {
+    type Output = u64;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<u64> {
+        let wait = self.get_mut();
+        let reached = wait.clock.tick_number();
+        if reached >= wait.frame || wait.clock.is_closed() {
+            return Poll::Ready(reached);
+        }
+        let waiter = || FrameWaiter::Task(cx.waker().clone());
+        // a waiter that is gone was woken by a frame, registered again below
+        if let Some(id) = wait.id
+            && wait.clock.update_waiter(id, waiter())
+        {
+            return Poll::Pending;
+        }
+        let id = *wait.id.get_or_insert_with(|| wait.clock.waiter_id());
+        match wait.clock.add_waiter(wait.frame, id, waiter()) {
+            Some(reached) => Poll::Ready(reached),
+            None => Poll::Pending,
+        }
+    }
+}
+
+impl Drop for FrameWait<'_> {
+    fn drop(&mut self) {
+        if let Some(id) = self.id {
+            self.clock.remove_waiter(id);
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use std::{
+    sync::{
+        Mutex,
+        atomic::{self, AtomicBool, AtomicU64, Ordering},
+    },
+    task::Waker,
+    thread::Thread,
+    time::Duration,
+};
+
+use crate::TickInfo;
+
+/// lock-free view of the Tick Manager's frame counter and simulated time,
+/// updated by the manager thread at the start of every main frame
+#[derive(Debug)]
+pub struct TickClock {
+    tick_number: AtomicU64,
+    sim_time_nanos: AtomicU64,
+    wall_time_nanos: AtomicU64,
+    delta_nanos: AtomicU64,
+    raw_delta_nanos: AtomicU64,
+    epoch: AtomicU64,
+    /// threads and tasks waiting for a frame number with their waiter id, see wait_for_frame
+    waiters: Mutex<Vec<(u64, u64, FrameWaiter)>>,
+    /// the lowest frame in waiters, u64::MAX while nobody waits so publishing needs no lock
+    next_wanted: AtomicU64,
+    next_waiter_id: AtomicU64,
+    /// set once no more frames come, see close
+    closed: AtomicBool,
+    #[cfg(feature = "watch")]
+    latest: Mutex<Option<TickInfo>>,
+}
+
+/// woken once the frame it waits for started, only the facades wait
+#[derive(Debug)]
+pub(crate) enum FrameWaiter {
+    #[cfg_attr(not(feature = "sync"), allow(dead_code))]
+    Thread(Thread),
+    #[cfg_attr(not(feature = "async"), allow(dead_code))]
+    Task(Waker),
+}
+
+impl FrameWaiter {
+    fn wake(self) {
+        match self {
+            FrameWaiter::Thread(thread) => thread.unpark(),
+            FrameWaiter::Task(waker) => waker.wake(),
+        }
+    }
+}
+
+impl Default for TickClock {
+    fn default() -> Self {
+        Self {
+            tick_number: AtomicU64::new(0),
+            sim_time_nanos: AtomicU64::new(0),
+            wall_time_nanos: AtomicU64::new(0),
+            delta_nanos: AtomicU64::new(0),
+            raw_delta_nanos: AtomicU64::new(0),
+            epoch: AtomicU64::new(0),
+            waiters: Mutex::new(Vec::new()),
+            next_wanted: AtomicU64::new(u64::MAX),
+            next_waiter_id: AtomicU64::new(0),
+            closed: AtomicBool::new(false),
+            #[cfg(feature = "watch")]
+            latest: Mutex::new(None),
+        }
+    }
+}
+
+impl TickClock {
+    /// number of the latest main frame, 0 before the first one
+    pub fn tick_number(&self) -> u64 {
+        self.tick_number.load(Ordering::Acquire)
+    }
+
+    /// simulated time accumulated up to the latest main frame
+    pub fn sim_time(&self) -> Duration {
+        Duration::from_nanos(self.sim_time_nanos.load(Ordering::Acquire))
+    }
+
+    /// TickInfo::delta of the latest main frame, the simulated time it advanced by
+    pub fn delta(&self) -> Duration {
+        Duration::from_nanos(self.delta_nanos.load(Ordering::Acquire))
+    }
+
+    /// TickInfo::raw_delta of the latest main frame, the real time since the one before
+    pub fn raw_delta(&self) -> Duration {
+        Duration::from_nanos(self.raw_delta_nanos.load(Ordering::Acquire))
+    }
+
+    /// real time elapsed since the Tick Manager started, up to the latest main frame
+    pub fn wall_time(&self) -> Duration {
+        Duration::from_nanos(self.wall_time_nanos.load(Ordering::Acquire))
+    }
+
+    /// the latest main frame that started while no member was in the middle of a tick.
+    /// anything a member unlinked before this frame started can't be reached by a tick anymore,
+    /// see TickManagerHandle::defer_until_next_frame
+    pub fn epoch(&self) -> u64 {
+        self.epoch.load(Ordering::Acquire)
+    }
+
+    /// stores the epoch, called by whatever drives the frames
+    pub fn publish_epoch(&self, epoch: u64) {
+        self.epoch.store(epoch, Ordering::Release);
+    }
+
+    /// the latest main frame as published at its start, None before the first one
+    #[cfg(feature = "watch")]
+    pub fn latest(&self) -> Option<TickInfo> {
+        *self.latest.lock().unwrap()
+    }
+
+    /// stores the frame, called by whatever drives the frames
+    pub fn publish(&self, info: &TickInfo) {
+        #[cfg(feature = "watch")]
+        {
+            *self.latest.lock().unwrap() = Some(*info);
+        }
+        self.sim_time_nanos
+            .store(info.sim_time.as_nanos() as u64, Ordering::Release);
+        self.wall_time_nanos
+            .store(info.wall_time.as_nanos() as u64, Ordering::Release);
+        self.delta_nanos
+            .store(info.delta.as_nanos() as u64, Ordering::Release);
+        self.raw_delta_nanos
+            .store(info.raw_delta.as_nanos() as u64, Ordering::Release);
+        self.tick_number.store(info.tick_number, Ordering::Release);
+        // pairs with the fence in add_waiter, either side sees the other's store
+        atomic::fence(Ordering::SeqCst);
+        if self.next_wanted.load(Ordering::Relaxed) <= info.tick_number {
+            self.wake_waiters(info.tick_number);
+        }
+    }
+
+    /// marks that no more frames come and wakes every waiter, called by whatever drives the
+    /// frames once it stopped. waiting for a frame returns right away after
+    pub fn close(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+        // pairs with the fence in add_waiter, like publish
+        atomic::fence(Ordering::SeqCst);
+        let waiters = std::mem::take(&mut *self.waiters.lock().unwrap());
+        self.next_wanted.store(u64::MAX, Ordering::Relaxed);
+        for (_frame, _id, waiter) in waiters {
+            waiter.wake();
+        }
+    }
+
+    /// whether the frames stopped for good, see close
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Acquire)
+    }
+
+    /// how many threads and tasks wait for a frame
+    pub fn waiting(&self) -> usize {
+        self.waiters.lock().unwrap().len()
+    }
+
+    /// a new id to register a waiter under
+    #[cfg_attr(not(feature = "sync"), allow(dead_code))]
+    pub(crate) fn waiter_id(&self) -> u64 {
+        self.next_waiter_id.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// registers the waiter for `frame` under `id` unless it already started or the clock was
+    /// closed. returns the latest frame number then, the waiter may stay registered until the
+    /// next frame or remove_waiter
+    #[cfg_attr(not(feature = "sync"), allow(dead_code))]
+    pub(crate) fn add_waiter(&self, frame: u64, id: u64, waiter: FrameWaiter) -> Option<u64> {
+        let reached = self.tick_number();
+        if reached >= frame || self.is_closed() {
+            return Some(reached);
+        }
+        {
+            let mut waiters = self.waiters.lock().unwrap();
+            waiters.push((frame, id, waiter));
+            self.next_wanted.fetch_min(frame, Ordering::Relaxed);
+        }
+        atomic::fence(Ordering::SeqCst);
+        // the frame may have started, or the clock closed, before the waiter was visible
+        let reached = self.tick_number();
+        (reached >= frame || self.is_closed()).then_some(reached)
+    }
+
+    /// replaces the waker of a registered waiter, false if it isn't registered anymore
+    #[cfg_attr(not(feature = "async"), allow(dead_code))]
+    pub(crate) fn update_waiter(&self, id: u64, waiter: FrameWaiter) -> bool {
+        let mut waiters = self.waiters.lock().unwrap();
+        match waiters
+            .iter_mut()
+            .find(|(_frame, other, _waiter)| *other == id)
+        {
+            Some(entry) => {
+                entry.2 = waiter;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// unregisters a waiter that gave up, it is gone already if it was woken
+    #[cfg_attr(not(feature = "sync"), allow(dead_code))]
+    pub(crate) fn remove_waiter(&self, id: u64) {
+        self.waiters
+            .lock()
+            .unwrap()
+            .retain(|(_frame, other, _waiter)| *other != id);
+    }
+
+    fn wake_waiters(&self, tick_number: u64) {
+        let mut waiters = self.waiters.lock().unwrap();
+        let mut next_wanted = u64::MAX;
+        let mut i = 0;
+        while i < waiters.len() {
+            if waiters[i].0 <= tick_number {
+                waiters.swap_remove(i).2.wake();
+            } else {
+                next_wanted = next_wanted.min(waiters[i].0);
+                i += 1;
+            }
+        }
+        self.next_wanted.store(next_wanted, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,99 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use crate::{FramePacer, Speed, TickClock, TickInfo};
+
+/// starts main frames on a TickClock from the caller's own loop instead of the Tick Manager
+/// thread, for async runtimes that sleep with their own timers.
+/// sleep until next_due, then call frame; anyone holding the clock sees the frames
+#[derive(Debug)]
+pub struct FrameDriver {
+    clock: Arc<TickClock>,
+    speed: Speed,
+    pacer: FramePacer,
+    started_at: Instant,
+    last_frame: Instant,
+    tick_number: u64,
+    sim_time: Duration,
+}
+
+impl FrameDriver {
+    /// a driver whose first frame is due one period from now
+    pub fn new(speed: Speed) -> Self {
+        let now = Instant::now();
+        Self {
+            clock: Arc::new(TickClock::default()),
+            pacer: FramePacer::new(&speed, now),
+            speed,
+            started_at: now,
+            last_frame: now,
+            tick_number: 0,
+            sim_time: Duration::ZERO,
+        }
+    }
+
+    /// the clock the frames are published on
+    pub fn clock(&self) -> &Arc<TickClock> {
+        &self.clock
+    }
+
+    pub fn speed(&self) -> &Speed {
+        &self.speed
+    }
+
+    /// paces the following frames at the new speed, starting one period after the latest frame
+    pub fn set_speed(&mut self, speed: Speed) {
+        self.pacer = FramePacer::new(&speed, self.last_frame);
+        self.speed = speed;
+    }
+
+    /// when the next frame is due to start, None with Speed::Manual where every call to frame
+    /// starts one
+    pub fn next_due(&self) -> Option<Instant> {
+        (!self.speed.is_manual()).then(|| self.pacer.next_due())
+    }
+
+    /// starts the next main frame and publishes it on the clock, if it is due at `now`.
+    /// None if it isn't due yet, or the clock was closed
+    pub fn frame(&mut self, now: Instant) -> Option<TickInfo> {
+        if self.clock.is_closed() {
+            return None;
+        }
+        let (delta, scheduled_at) = match self.speed {
+            Speed::Manual(step) => (step, now),
+            _ if !self.pacer.is_due(now) => return None,
+            _ => (
+                now.saturating_duration_since(self.last_frame),
+                self.pacer.advance(&self.speed, now),
+            ),
+        };
+        self.tick_number += 1;
+        self.sim_time += delta;
+        let info = TickInfo {
+            tick_number: self.tick_number,
+            delta,
+            raw_delta: now.saturating_duration_since(self.last_frame),
+            scheduled_at,
+            sim_time: self.sim_time,
+            wall_time: now.saturating_duration_since(self.started_at),
+            headroom: self.speed.get_duration(),
+            seed: None,
+        };
+        self.last_frame = now;
+        self.clock.publish(&info);
+        Some(info)
+    }
+
+    /// stops the frames, whoever waits on the clock wakes up, see TickClock::close
+    pub fn close(&self) {
+        self.clock.close();
+    }
+}
+
+impl Drop for FrameDriver {
+    fn drop(&mut self) {
+        self.clock.close();
+    }
+}
@@ -0,0 +1,43 @@
+use std::time::{Duration, Instant};
+
+use crate::Speed;
+
+/// frame times as exact integer offsets from an anchor frame instead of a float period added
+/// up every frame, so every platform computes the same deltas and simulated time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedTimeline {
+    anchor_at: Instant,
+    anchor_frame: u64,
+    anchor_sim_time: Duration,
+}
+
+impl FixedTimeline {
+    /// a timeline on which `frame` started at `at` with the given simulated time
+    pub fn new(at: Instant, frame: u64, sim_time: Duration) -> Self {
+        Self {
+            anchor_at: at,
+            anchor_frame: frame,
+            anchor_sim_time: sim_time,
+        }
+    }
+
+    /// when the frame is due to start
+    pub fn due_at(&self, speed: &Speed, frame: u64) -> Instant {
+        self.anchor_at + self.since_anchor(speed, frame)
+    }
+
+    /// simulated time at the start of the frame
+    pub fn sim_time(&self, speed: &Speed, frame: u64) -> Duration {
+        self.anchor_sim_time + self.since_anchor(speed, frame)
+    }
+
+    /// the exact time between the previous frame and this one
+    pub fn delta(&self, speed: &Speed, frame: u64) -> Duration {
+        self.sim_time(speed, frame)
+            - self.sim_time(speed, frame.saturating_sub(1).max(self.anchor_frame))
+    }
+
+    fn since_anchor(&self, speed: &Speed, frame: u64) -> Duration {
+        speed.duration_of(frame.saturating_sub(self.anchor_frame))
+    }
+}
@@ -0,0 +1,24 @@
+//! scheduling logic without threads or futures, shared by the sync and async facades
+
+pub mod clock;
+pub mod driver;
+pub mod fixed_time;
+pub mod negotiate;
+pub mod pacer;
+pub mod schedule;
+pub mod scheduler;
+pub mod speed_log;
+pub mod time;
+pub mod timer_wheel;
+pub mod types;
+pub use clock::*;
+pub use driver::*;
+pub use fixed_time::*;
+pub use negotiate::*;
+pub use pacer::*;
+pub use schedule::*;
+pub use scheduler::*;
+pub use speed_log::*;
+pub use time::*;
+pub use timer_wheel::*;
+pub use types::*;
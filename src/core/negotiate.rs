@@ -0,0 +1,59 @@
+use std::time::Duration;
+
+use crate::{Speed, SpeedFactor};
+
+/// the main speed picked for a set of member rates, see negotiate_speed
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeedPlan {
+    pub speed: Speed,
+    /// the speed factor for each requirement, in the order they were given
+    pub factors: Vec<SpeedFactor>,
+}
+
+/// picks the slowest main speed every requested rate can be reached from with an integer
+/// speed factor, e.g. Fps(60) and Fps(50) result in Fps(300) with the factors 5 and 6.
+/// returns None without requirements or if one of them is Fps(0) or Manual
+pub fn negotiate_speed(requirements: &[Speed]) -> Option<SpeedPlan> {
+    // periods in seconds as reduced fractions
+    let periods = requirements
+        .iter()
+        .map(period)
+        .collect::<Option<Vec<(u128, u128)>>>()?;
+    let (num, den) = periods
+        .iter()
+        .copied()
+        .reduce(|(n1, d1), (n2, d2)| (gcd(n1, n2), lcm(d1, d2)))?;
+
+    let factors = periods
+        .iter()
+        .map(|&(n, d)| (n * den / (d * num)) as SpeedFactor)
+        .collect();
+    let speed = match usize::try_from(den) {
+        Ok(fps) if num == 1 => Speed::Fps(fps),
+        _ => Speed::Interval(Duration::from_nanos((num * 1_000_000_000 / den) as u64)),
+    };
+    Some(SpeedPlan { speed, factors })
+}
+
+fn period(speed: &Speed) -> Option<(u128, u128)> {
+    let (num, den) = match speed {
+        Speed::Fps(0) => return None,
+        Speed::Fps(fps) => (1, *fps as u128),
+        Speed::Interval(interval) if interval.is_zero() => return None,
+        Speed::Interval(interval) => (interval.as_nanos(), 1_000_000_000),
+        Speed::Manual(_) => return None,
+    };
+    let divisor = gcd(num, den);
+    Some((num / divisor, den / divisor))
+}
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+fn lcm(a: u128, b: u128) -> u128 {
+    a / gcd(a, b) * b
+}
@@ -0,0 +1,60 @@
+use std::time::{Duration, Instant};
+
+use crate::Speed;
+
+/// paces main frames with the period kept as the exact rational 1s / fps.
+/// the nanoseconds lost by rounding the period down are carried over and paid back one at a
+/// time, so exactly fps frames are due in every second instead of drifting with a float period
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FramePacer {
+    next_due: Instant,
+    /// rounding error accumulated so far, in 1 / denominator nanoseconds
+    carry: u64,
+}
+
+impl FramePacer {
+    /// a pacer whose first frame is due one period after `last_frame`
+    pub fn new(speed: &Speed, last_frame: Instant) -> Self {
+        let mut pacer = Self {
+            next_due: last_frame,
+            carry: 0,
+        };
+        let period = pacer.step(speed);
+        pacer.next_due += period;
+        pacer
+    }
+
+    /// when the next frame is due to start
+    pub fn next_due(&self) -> Instant {
+        self.next_due
+    }
+
+    pub fn is_due(&self, now: Instant) -> bool {
+        self.next_due <= now
+    }
+
+    /// moves on to the frame after the one starting at `now` and returns when the started frame
+    /// was due. once more than a frame behind, pacing starts over from now instead of catching up
+    pub fn advance(&mut self, speed: &Speed, now: Instant) -> Instant {
+        let scheduled_at = self.next_due;
+        let period = self.step(speed);
+        if now >= scheduled_at + period {
+            *self = Self::new(speed, now);
+        } else {
+            self.next_due += period;
+        }
+        scheduled_at
+    }
+
+    /// the next period, one nanosecond longer whenever the carried rounding error adds up to one
+    fn step(&mut self, speed: &Speed) -> Duration {
+        let (whole, remainder, denominator) = speed.period_nanos();
+        self.carry += remainder;
+        let mut nanos = whole;
+        if self.carry >= denominator {
+            self.carry -= denominator;
+            nanos += 1;
+        }
+        Duration::from_nanos(nanos)
+    }
+}
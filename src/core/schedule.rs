@@ -0,0 +1,208 @@
+use std::{ops::Range, time::Duration};
+
+use crate::{MemberID, Speed, SpeedFactor};
+
+/// whether a member with the given speed factor and phase offset is due on the main frame.
+/// this is the rule the Tick Manager dispatches by
+pub fn is_due(tick_number: u64, speed_factor: SpeedFactor, offset: u64) -> bool {
+    let factor = speed_factor.max(1) as u64;
+    tick_number % factor == offset % factor
+}
+
+/// the first main frame after `tick_number` the member is due on
+pub fn next_due(tick_number: u64, speed_factor: SpeedFactor, offset: u64) -> u64 {
+    let factor = speed_factor.max(1) as u64;
+    let next = tick_number + 1;
+    next + (offset % factor + factor - next % factor) % factor
+}
+
+/// the speed factor and offset of a registered member
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScheduledMember {
+    pub id: MemberID,
+    pub speed_factor: SpeedFactor,
+    pub offset: u64,
+}
+
+/// least common multiple of the speed factors, after that many frames the due sets repeat.
+/// None if it doesn't fit a u64
+pub fn cycle_length(speed_factors: impl IntoIterator<Item = SpeedFactor>) -> Option<u64> {
+    speed_factors.into_iter().try_fold(1u64, |length, factor| {
+        let factor = factor.max(1) as u64;
+        (length / gcd(length, factor)).checked_mul(factor)
+    })
+}
+
+/// the longest cycle Cycle::worst_frames scans, a few coprime factors make far longer ones
+pub const MAX_SCANNED_FRAMES: u64 = 1 << 20;
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// the offset for a new member with the given speed factor that makes it coincide with the
+/// fewest of the given members, weighted by how often they coincide. ties pick the lowest offset
+pub fn balanced_offset(
+    speed_factor: SpeedFactor,
+    members: impl IntoIterator<Item = (SpeedFactor, u64)> + Clone,
+) -> u64 {
+    let factor = speed_factor.max(1) as u64;
+    let overlap = |offset: u64| -> f64 {
+        members
+            .clone()
+            .into_iter()
+            .map(|(other_factor, other_offset)| {
+                let other_factor = other_factor.max(1) as u64;
+                let common = gcd(factor, other_factor);
+                // both are due on the same frames whenever the offsets agree modulo the gcd
+                if offset % common == other_offset % common {
+                    common as f64 / other_factor as f64
+                } else {
+                    0.0
+                }
+            })
+            .sum()
+    };
+    (0..factor)
+        .map(|offset| (offset, overlap(offset)))
+        .fold((0, f64::INFINITY), |best, (offset, cost)| {
+            if cost < best.1 { (offset, cost) } else { best }
+        })
+        .0
+}
+
+/// one full cycle of the schedule. the frames of a cycle are numbered 0..length,
+/// frame k stands for every tick number that leaves the remainder k when divided by length
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle {
+    /// u64::MAX if the cycle is longer than that
+    pub length: u64,
+    pub members: Vec<ScheduledMember>,
+}
+
+impl Cycle {
+    pub fn new(members: Vec<ScheduledMember>) -> Self {
+        Self {
+            length: cycle_length(members.iter().map(|m| m.speed_factor)).unwrap_or(u64::MAX),
+            members,
+        }
+    }
+
+    /// the members due on frame k of the cycle
+    pub fn due(&self, frame: u64) -> Vec<MemberID> {
+        self.members
+            .iter()
+            .filter(|m| is_due(frame, m.speed_factor, m.offset))
+            .map(|m| m.id)
+            .collect()
+    }
+
+    /// the due set of every frame in the cycle, computed lazily since cycles of coprime
+    /// factors get long
+    pub fn frames(&self) -> impl Iterator<Item = Vec<MemberID>> + '_ {
+        (0..self.length).map(|frame| self.due(frame))
+    }
+
+    /// the frames of the cycle with the most members due, and that amount.
+    /// None for cycles longer than MAX_SCANNED_FRAMES
+    pub fn worst_frames(&self) -> Option<(Vec<u64>, usize)> {
+        if self.length > MAX_SCANNED_FRAMES {
+            return None;
+        }
+        let mut worst = (Vec::new(), 0);
+        for (frame, due) in self.frames().enumerate() {
+            if due.len() > worst.1 {
+                worst = (vec![frame as u64], due.len());
+            } else if due.len() == worst.1 {
+                worst.0.push(frame as u64);
+            }
+        }
+        Some(worst)
+    }
+}
+
+/// a member as seen by ScheduleSim
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SimMember {
+    pub speed_factor: SpeedFactor,
+    pub offset: u64,
+}
+
+/// a main frame computed by ScheduleSim
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimFrame {
+    pub tick_number: u64,
+    /// time since the Tick Manager started, if every frame starts on time
+    pub at: Duration,
+    /// the members due on this frame, in the order they were added
+    pub due: Vec<MemberID>,
+}
+
+/// computes which members fire on which frames without spawning a Tick Manager.
+/// members get the ids a fresh Tick Manager would assign, in the order they were added
+#[derive(Debug, Clone)]
+pub struct ScheduleSim {
+    speed: Speed,
+    members: Vec<SimMember>,
+}
+
+impl ScheduleSim {
+    pub fn new(speed: Speed) -> Self {
+        Self {
+            speed,
+            members: Vec::new(),
+        }
+    }
+
+    /// adds a member, its id is the amount of members added before it
+    pub fn member(mut self, speed_factor: SpeedFactor, offset: u64) -> Self {
+        self.members.push(SimMember {
+            speed_factor,
+            offset,
+        });
+        self
+    }
+
+    pub fn members(&self) -> &[SimMember] {
+        &self.members
+    }
+
+    /// the members due on a single main frame
+    pub fn due(&self, tick_number: u64) -> Vec<MemberID> {
+        self.members
+            .iter()
+            .enumerate()
+            .filter(|(_id, m)| is_due(tick_number, m.speed_factor, m.offset))
+            .map(|(id, _m)| id)
+            .collect()
+    }
+
+    /// one full cycle of the simulated members
+    pub fn cycle(&self) -> Cycle {
+        Cycle::new(
+            self.members
+                .iter()
+                .enumerate()
+                .map(|(id, m)| ScheduledMember {
+                    id,
+                    speed_factor: m.speed_factor,
+                    offset: m.offset,
+                })
+                .collect(),
+        )
+    }
+
+    /// every main frame in the range of tick numbers, frames start at 1
+    pub fn frames(&self, tick_numbers: Range<u64>) -> Vec<SimFrame> {
+        tick_numbers
+            .map(|tick_number| SimFrame {
+                tick_number,
+                at: self.speed.duration_of(tick_number),
+                due: self.due(tick_number),
+            })
+            .collect()
+    }
+}
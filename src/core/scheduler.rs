@@ -0,0 +1,176 @@
+use std::{
+    collections::BTreeMap,
+    io,
+    ops::ControlFlow,
+    time::{Duration, Instant},
+};
+
+use crate::{FramePacer, MemberID, ScheduledMember, Speed, SpeedFactor, is_due};
+
+/// the frame pacing and dispatch rule of the Tick Manager without its thread, for event loops
+/// that drive the frames themselves, e.g. FFI hosts, async reactors or GUI frameworks.
+/// call poll whenever the loop wakes up and sleep until the returned deadline
+#[derive(Debug, Clone)]
+pub struct TickScheduler {
+    speed: Speed,
+    pacer: FramePacer,
+    tick_number: u64,
+    members: BTreeMap<MemberID, ScheduledMember>,
+    next_member_id: MemberID,
+}
+
+/// a reactor a TickScheduler shares its thread with, see TickScheduler::run_on.
+/// mio's Poll and polling's Poller fit by forwarding to their poll and wait
+pub trait Reactor {
+    /// the buffer the reactor fills, e.g. mio::Events or polling::Events
+    type Events;
+
+    /// blocks until events arrived or the timeout passed, without a timeout if None
+    fn wait(&mut self, events: &mut Self::Events, timeout: Option<Duration>) -> io::Result<()>;
+}
+
+/// what a single TickScheduler::poll did
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PollResult {
+    /// the main frame that started, None if none was due yet
+    pub frame: Option<u64>,
+    /// the members due in the started frame, by id
+    pub due_members: Vec<MemberID>,
+    /// when the next frame is due, None while the speed is Manual
+    pub next_deadline: Option<Instant>,
+}
+
+impl TickScheduler {
+    /// a scheduler whose first frame is due one period after `now`
+    pub fn new(speed: Speed, now: Instant) -> Self {
+        Self {
+            pacer: FramePacer::new(&speed, now),
+            speed,
+            tick_number: 0,
+            members: BTreeMap::new(),
+            next_member_id: 0,
+        }
+    }
+
+    /// the latest frame that started, 0 before the first one
+    pub fn tick_number(&self) -> u64 {
+        self.tick_number
+    }
+
+    pub fn speed(&self) -> &Speed {
+        &self.speed
+    }
+
+    /// changes the speed, the pacing starts over from `now`
+    pub fn set_speed(&mut self, speed: Speed, now: Instant) {
+        self.pacer = FramePacer::new(&speed, now);
+        self.speed = speed;
+    }
+
+    /// adds a member that is due whenever is_due says so, returns its id
+    pub fn add_member(&mut self, speed_factor: SpeedFactor, offset: u64) -> MemberID {
+        let id = self.next_member_id;
+        self.next_member_id += 1;
+        self.members.insert(
+            id,
+            ScheduledMember {
+                id,
+                speed_factor: speed_factor.max(1),
+                offset,
+            },
+        );
+        id
+    }
+
+    /// false if there is no such member
+    pub fn remove_member(&mut self, id: MemberID) -> bool {
+        self.members.remove(&id).is_some()
+    }
+
+    /// false if there is no such member
+    pub fn set_speed_factor(&mut self, id: MemberID, speed_factor: SpeedFactor) -> bool {
+        match self.members.get_mut(&id) {
+            Some(member) => {
+                member.speed_factor = speed_factor.max(1);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// how long a reactor may block in its wait, e.g. the timeout of mio's Poll::poll or
+    /// polling's Poller::wait, to wake up in time for the next frame.
+    /// None while the speed is Manual, the reactor then only wakes up for its own events
+    pub fn timeout(&self, now: Instant) -> Option<Duration> {
+        (!self.speed.is_manual()).then(|| self.pacer.next_due().saturating_duration_since(now))
+    }
+
+    /// for single-threaded reactor loops that interleave socket events and frames: call it
+    /// every time the wait returned, for events or the timeout. hands a started frame and its due
+    /// members to `on_frame` and returns the timeout for the next wait
+    pub fn dispatch(
+        &mut self,
+        now: Instant,
+        on_frame: impl FnOnce(u64, &[MemberID]),
+    ) -> Option<Duration> {
+        let polled = self.poll(now);
+        if let Some(frame) = polled.frame {
+            on_frame(frame, &polled.due_members);
+        }
+        polled
+            .next_deadline
+            .map(|deadline| deadline.saturating_duration_since(now))
+    }
+
+    /// runs a single-threaded reactor loop until `on_events` breaks or the reactor fails.
+    /// every wait times out in time for the next frame, the events go to `on_events` and every
+    /// started frame with its due members to `on_frame`. interrupted waits are retried
+    pub fn run_on<R: Reactor>(
+        &mut self,
+        reactor: &mut R,
+        events: &mut R::Events,
+        mut on_events: impl FnMut(&mut R::Events) -> ControlFlow<()>,
+        mut on_frame: impl FnMut(u64, &[MemberID]),
+    ) -> io::Result<()> {
+        let mut timeout = self.timeout(Instant::now());
+        loop {
+            match reactor.wait(events, timeout) {
+                Ok(()) => {
+                    if on_events(events).is_break() {
+                        return Ok(());
+                    }
+                }
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => return Err(error),
+            }
+            timeout = self.dispatch(Instant::now(), &mut on_frame);
+        }
+    }
+
+    /// starts the next frame if it is due at `now`. with a Manual speed every poll starts one
+    pub fn poll(&mut self, now: Instant) -> PollResult {
+        let manual = self.speed.is_manual();
+        if !manual && !self.pacer.is_due(now) {
+            return PollResult {
+                frame: None,
+                due_members: Vec::new(),
+                next_deadline: Some(self.pacer.next_due()),
+            };
+        }
+        if !manual {
+            self.pacer.advance(&self.speed, now);
+        }
+        self.tick_number += 1;
+        let due_members = self
+            .members
+            .values()
+            .filter(|m| is_due(self.tick_number, m.speed_factor, m.offset))
+            .map(|m| m.id)
+            .collect();
+        PollResult {
+            frame: Some(self.tick_number),
+            due_members,
+            next_deadline: (!manual).then(|| self.pacer.next_due()),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use std::time::Duration;
+
+use crate::Speed;
+
+/// a speed the main frames ran at from `from_frame` on
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeedChange {
+    /// the first main frame started at the speed
+    pub from_frame: u64,
+    pub speed: Speed,
+    /// the simulated time at the start of the frame before `from_frame`,
+    /// the frames at the speed are counted from there like on a FixedTimeline
+    pub sim_time_before: Duration,
+}
+
+/// every speed the main frames ran at, oldest first, to reconstruct the simulated time of past
+/// frames. the simulated times are the nominal ones, which are exact with fixed point time
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SpeedLog {
+    changes: Vec<SpeedChange>,
+}
+
+impl SpeedLog {
+    /// a log whose frames start at `speed` from frame 1 on
+    pub fn new(speed: Speed) -> Self {
+        Self {
+            changes: vec![SpeedChange {
+                from_frame: 1,
+                speed,
+                sim_time_before: Duration::ZERO,
+            }],
+        }
+    }
+
+    /// records that the frames after `last_frame` run at `speed`, `last_frame` started at `sim_time`.
+    /// a change replaces the ones that would have taken effect at the same frame or later
+    pub fn record(&mut self, last_frame: u64, sim_time: Duration, speed: Speed) {
+        let from_frame = last_frame + 1;
+        self.changes.retain(|change| change.from_frame < from_frame);
+        if self.changes.last().is_some_and(|last| last.speed == speed) {
+            return;
+        }
+        self.changes.push(SpeedChange {
+            from_frame,
+            speed,
+            sim_time_before: sim_time,
+        });
+    }
+
+    pub fn changes(&self) -> &[SpeedChange] {
+        &self.changes
+    }
+
+    /// the change in effect during the frame, None for frame 0 or an empty log
+    pub fn change_at(&self, frame: u64) -> Option<&SpeedChange> {
+        self.changes
+            .iter()
+            .rev()
+            .find(|change| change.from_frame <= frame)
+    }
+
+    /// the speed the frame started at
+    pub fn speed_at(&self, frame: u64) -> Option<&Speed> {
+        self.change_at(frame).map(|change| &change.speed)
+    }
+
+    /// the nominal simulated time at the start of the frame
+    pub fn sim_time_at(&self, frame: u64) -> Option<Duration> {
+        self.change_at(frame).map(|change| {
+            let frames = frame - change.from_frame + 1;
+            change.sim_time_before + change.speed.duration_of(frames)
+        })
+    }
+}
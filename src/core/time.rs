@@ -0,0 +1,177 @@
+use std::{
+    fmt,
+    sync::{Arc, Condvar, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// how often a thread waiting on a MockClock checks whether it was advanced
+const MOCK_POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// where the Tick Manager takes the time from, see TickManager::new_with_clock
+pub trait Clock: Send + Sync + fmt::Debug {
+    fn now(&self) -> Instant;
+
+    /// blocks the calling thread until now reached the deadline
+    fn sleep_until(&self, deadline: Instant);
+
+    /// when a thread waiting for `deadline` of this clock should check the clock again,
+    /// in real time. the deadline itself for clocks that follow the real time
+    fn wake_at(&self, deadline: Instant) -> Instant {
+        deadline
+    }
+}
+
+/// the real monotonic time, the default. std's Instant, which is QueryPerformanceCounter on
+/// Windows, mach_absolute_time on macOS and CLOCK_MONOTONIC on Linux.
+/// CLOCK_MONOTONIC is slewed by NTP, see RawMonotonicClock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MonotonicClock;
+
+impl Clock for MonotonicClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep_until(&self, deadline: Instant) {
+        thread::sleep(deadline.saturating_duration_since(Instant::now()));
+    }
+}
+
+/// a clock that only moves when it is advanced, to test timing without waiting for it.
+/// clones share the same time
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    now: Arc<(Mutex<Instant>, Condvar)>,
+}
+
+impl MockClock {
+    /// a clock standing at the real time it was created
+    pub fn new() -> Self {
+        Self::starting_at(Instant::now())
+    }
+
+    pub fn starting_at(now: Instant) -> Self {
+        Self {
+            now: Arc::new((Mutex::new(now), Condvar::new())),
+        }
+    }
+
+    pub fn advance(&self, by: Duration) {
+        let (now, advanced) = &*self.now;
+        *now.lock().unwrap() += by;
+        advanced.notify_all();
+    }
+
+    /// moves the clock to `to`, never backwards
+    pub fn set(&self, to: Instant) {
+        let (now, advanced) = &*self.now;
+        let mut now = now.lock().unwrap();
+        *now = (*now).max(to);
+        advanced.notify_all();
+    }
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        *self.now.0.lock().unwrap()
+    }
+
+    fn sleep_until(&self, deadline: Instant) {
+        let (now, advanced) = &*self.now;
+        let now = now.lock().unwrap();
+        let _now = advanced.wait_while(now, |now| *now < deadline).unwrap();
+    }
+
+    fn wake_at(&self, deadline: Instant) -> Instant {
+        if deadline <= self.now() {
+            Instant::now()
+        } else {
+            Instant::now() + MOCK_POLL_INTERVAL
+        }
+    }
+}
+
+/// CLOCK_MONOTONIC_RAW on Linux, which NTP never slews, for deployments where the adjustments
+/// of CLOCK_MONOTONIC would show up in the pacing. its Instants are anchored at the real
+/// Instant the clock was created at
+#[cfg(all(
+    feature = "monotonic-raw",
+    any(target_os = "linux", target_os = "android")
+))]
+#[derive(Debug, Clone, Copy)]
+pub struct RawMonotonicClock {
+    anchor: Instant,
+    anchor_raw: Duration,
+}
+
+#[cfg(all(
+    feature = "monotonic-raw",
+    any(target_os = "linux", target_os = "android")
+))]
+impl RawMonotonicClock {
+    pub fn new() -> Self {
+        Self {
+            anchor: Instant::now(),
+            anchor_raw: raw_now(),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "monotonic-raw",
+    any(target_os = "linux", target_os = "android")
+))]
+impl Default for RawMonotonicClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(all(
+    feature = "monotonic-raw",
+    any(target_os = "linux", target_os = "android")
+))]
+impl Clock for RawMonotonicClock {
+    fn now(&self) -> Instant {
+        self.anchor + raw_now().saturating_sub(self.anchor_raw)
+    }
+
+    fn sleep_until(&self, deadline: Instant) {
+        // the sleep itself runs on CLOCK_MONOTONIC, so it is checked against the raw time
+        loop {
+            let left = deadline.saturating_duration_since(self.now());
+            if left.is_zero() {
+                return;
+            }
+            thread::sleep(left);
+        }
+    }
+
+    fn wake_at(&self, deadline: Instant) -> Instant {
+        Instant::now() + deadline.saturating_duration_since(self.now())
+    }
+}
+
+#[cfg(all(
+    feature = "monotonic-raw",
+    any(target_os = "linux", target_os = "android")
+))]
+fn raw_now() -> Duration {
+    let mut now = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    // SAFETY: clock_gettime only writes the timespec, which lives until the call returned.
+    // CLOCK_MONOTONIC_RAW exists on every supported kernel, so it can't fail
+    unsafe {
+        libc::clock_gettime(libc::CLOCK_MONOTONIC_RAW, &mut now);
+    }
+    Duration::new(now.tv_sec as u64, now.tv_nsec as u32)
+}
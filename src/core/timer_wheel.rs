@@ -0,0 +1,114 @@
+/// bits of the frame number covered by one level of the wheel
+const SLOT_BITS: u32 = 6;
+const SLOTS: usize = 1 << SLOT_BITS;
+const LEVELS: usize = 4;
+
+/// hierarchical timer wheel keyed by main frame numbers.
+/// inserting and advancing by one frame cost O(1) no matter how many items are scheduled,
+/// items further out than 64^4 frames wait in an overflow list
+#[derive(Debug)]
+pub struct TimerWheel<T> {
+    now: u64,
+    /// level l holds the items whose due frame shares every bit above 6 * (l + 1) with now
+    levels: Vec<Vec<Vec<(u64, T)>>>,
+    overflow: Vec<(u64, T)>,
+    /// items inserted for a frame that already passed, handed out by the next advance
+    expired: Vec<(u64, T)>,
+    len: usize,
+}
+
+impl<T> TimerWheel<T> {
+    /// creates an empty wheel standing on the given frame
+    pub fn new(now: u64) -> Self {
+        Self {
+            now,
+            levels: (0..LEVELS)
+                .map(|_| (0..SLOTS).map(|_| Vec::new()).collect())
+                .collect(),
+            overflow: Vec::new(),
+            expired: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// the last frame the wheel advanced to
+    pub fn now(&self) -> u64 {
+        self.now
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// schedules the item for the main frame, frames that already passed fire on the next advance
+    pub fn insert(&mut self, due: u64, item: T) {
+        self.len += 1;
+        self.place(due, item);
+    }
+
+    /// moves the wheel to the frame and returns every item due up to it, in due order
+    pub fn advance(&mut self, to: u64) -> Vec<(u64, T)> {
+        let mut fired = std::mem::take(&mut self.expired);
+        while self.now < to {
+            self.now += 1;
+            self.cascade();
+            fired.append(&mut self.expired);
+            let slot = (self.now as usize) & (SLOTS - 1);
+            fired.append(&mut self.levels[0][slot]);
+        }
+        self.len -= fired.len();
+        fired
+    }
+
+    /// removes every item regardless of its due frame
+    pub fn drain(&mut self) -> Vec<(u64, T)> {
+        let mut items = std::mem::take(&mut self.expired);
+        for level in &mut self.levels {
+            for slot in level {
+                items.append(slot);
+            }
+        }
+        items.append(&mut self.overflow);
+        items.sort_by_key(|(due, _item)| *due);
+        self.len = 0;
+        items
+    }
+
+    fn place(&mut self, due: u64, item: T) {
+        if due <= self.now {
+            self.expired.push((due, item));
+            return;
+        }
+        for level in 0..LEVELS {
+            let shift = SLOT_BITS * (level as u32 + 1);
+            if due >> shift == self.now >> shift {
+                let slot = ((due >> (SLOT_BITS * level as u32)) as usize) & (SLOTS - 1);
+                self.levels[level][slot].push((due, item));
+                return;
+            }
+        }
+        self.overflow.push((due, item));
+    }
+
+    /// moves the items of the higher levels down once now reaches their slot
+    fn cascade(&mut self) {
+        if self.now.trailing_zeros() >= SLOT_BITS * LEVELS as u32 {
+            for (due, item) in std::mem::take(&mut self.overflow) {
+                self.place(due, item);
+            }
+        }
+        for level in (1..LEVELS).rev() {
+            let shift = SLOT_BITS * level as u32;
+            if self.now.trailing_zeros() >= shift {
+                let slot = ((self.now >> shift) as usize) & (SLOTS - 1);
+                for (due, item) in std::mem::take(&mut self.levels[level][slot]) {
+                    self.place(due, item);
+                }
+            }
+        }
+    }
+}
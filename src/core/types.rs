@@ -0,0 +1,131 @@
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Speed {
+    Fps(usize),
+    Interval(Duration),
+    /// frames only start when asked for with TickManagerHandle::tick_now,
+    /// each one advances the simulated time by the step
+    Manual(Duration),
+}
+
+impl Speed {
+    /// whether we are allowed to start a new main frame
+    pub fn new_frame(&self, last_frame: Instant) -> bool {
+        !self.is_manual() && last_frame + self.get_duration() <= Instant::now()
+    }
+
+    pub fn is_manual(&self) -> bool {
+        matches!(self, Speed::Manual(_))
+    }
+
+    /// the period of one main frame, rounded down to whole nanoseconds
+    pub fn get_duration(&self) -> Duration {
+        self.duration_of(1)
+    }
+
+    /// the period as the rational `whole + remainder / denominator` nanoseconds
+    pub fn period_nanos(&self) -> (u64, u64, u64) {
+        match self {
+            Speed::Fps(fps) => {
+                let fps = (*fps).max(1) as u64;
+                (1_000_000_000 / fps, 1_000_000_000 % fps, fps)
+            }
+            Speed::Interval(dur) | Speed::Manual(dur) => (dur.as_nanos() as u64, 0, 1),
+        }
+    }
+
+    /// the time `frames` main frames take, computed in integer nanoseconds without rounding
+    /// each period, so 60 frames at Fps(60) are exactly one second. saturates at Duration::MAX
+    pub fn duration_of(&self, frames: u64) -> Duration {
+        let nanos = match self {
+            Speed::Fps(fps) => Some(frames as u128 * 1_000_000_000 / (*fps).max(1) as u128),
+            Speed::Interval(dur) | Speed::Manual(dur) => {
+                (frames as u128).checked_mul(dur.as_nanos())
+            }
+        };
+        let Some(nanos) = nanos else {
+            return Duration::MAX;
+        };
+        match u64::try_from(nanos / 1_000_000_000) {
+            Ok(secs) => Duration::new(secs, (nanos % 1_000_000_000) as u32),
+            Err(_) => Duration::MAX,
+        }
+    }
+}
+
+/// information about a single main frame
+#[derive(Debug, Clone, Copy)]
+pub struct TickInfo {
+    /// number of the main frame, starting at 1
+    pub tick_number: u64,
+    /// simulated time since the previous main frame, what sim_time advanced by.
+    /// the nominal period with fixed point time, pauses never count
+    pub delta: Duration,
+    /// real time since the previous main frame started, or since the Tick Manager resumed
+    pub raw_delta: Duration,
+    /// the instant this frame was due to start
+    pub scheduled_at: Instant,
+    /// simulated time accumulated over all main frames up to this one, frozen while paused
+    pub sim_time: Duration,
+    /// real time elapsed since the Tick Manager started, including pauses
+    pub wall_time: Duration,
+    /// estimated time left until the next main frame is due, measured when this info was delivered
+    pub headroom: Duration,
+    /// deterministic seed of this frame, if the Tick Manager was built with a seed
+    pub seed: Option<u64>,
+}
+
+impl TickInfo {
+    /// the info with the headroom measured from now until `next_frame_at`
+    pub fn delivered(mut self, next_frame_at: Instant) -> Self {
+        self.headroom = next_frame_at.saturating_duration_since(Instant::now());
+        self
+    }
+}
+
+/// the seed of a single main frame, the same for every run with the same manager seed.
+/// neighbouring frames get unrelated seeds (splitmix64)
+pub fn frame_seed(seed: u64, tick_number: u64) -> u64 {
+    let mut z = seed.wrapping_add(tick_number.wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+pub type HookID = usize;
+/// unique per Tick Manager within the process
+pub type ManagerID = u64;
+pub type MemberID = usize;
+pub type SpeedFactor = usize;
+
+#[derive(Clone, Debug)]
+pub enum MemberState {
+    Finished,
+    Running,
+    Hidden,
+}
+
+/// how a member takes part in main frames
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MemberKind {
+    /// frames wait until the member reported Finished
+    #[default]
+    Participant,
+    /// frames don't wait for the member, it skips the frames that are due while it's still Running
+    FrameDropping,
+    /// frames don't wait for the member and its state is ignored, it is sent every due tick.
+    /// ticks are dropped while its channel is full
+    Observer,
+}
+
+/// which clock a scheduled item is measured in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ClockDomain {
+    /// counted in main frames, converted at the speed the item is scheduled at.
+    /// freezes while no frames start, e.g. while quiesced, and stretches when the manager slows down
+    #[default]
+    Sim,
+    /// counted in wall time, keeps firing while no frames start
+    Wall,
+}
@@ -1,10 +1,15 @@
-pub mod tickmanager;
-pub use tickmanager::*;
+pub mod core;
+pub use crate::core::*;
 
-pub mod tick_hook;
-pub use tick_hook::*;
+#[cfg(feature = "sync")]
+pub mod sync;
+#[cfg(feature = "sync")]
+pub use sync::*;
 
-#[cfg(test)]
+#[cfg(feature = "async")]
+pub mod r#async;
+
+#[cfg(all(test, feature = "sync"))]
 mod tests {
     use std::sync::{
         Arc,
@@ -168,4 +173,2880 @@ mod tests {
             dt
         );
     }
+
+    #[test]
+    fn sleep_until_next_frame_without_members() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(120));
+
+        let first = handle.sleep_until_next_frame().unwrap();
+        let second = handle.sleep_until_next_frame().unwrap();
+        assert!(second.tick_number > first.tick_number);
+    }
+
+    #[test]
+    fn rate_limiter_refills_every_frame() {
+        let (manager, handle) = TickManager::new(Speed::Interval(Duration::from_millis(50)));
+        let limiter = RateLimiter::new(&handle, 2, 1).unwrap();
+
+        assert!(limiter.try_acquire());
+        assert!(limiter.try_acquire());
+        assert!(!limiter.try_acquire());
+
+        assert!(limiter.acquire());
+        assert_eq!(limiter.available(), 1);
+
+        handle.shutdown_with_timeout(Duration::from_secs(1));
+        drop(manager);
+        assert!(RateLimiter::new(&handle, 2, 1).is_err());
+    }
+
+    #[test]
+    fn throttle_passes_every_nth_tick() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(240));
+        let member = TickMember::new(handle.clone(), 1);
+
+        let passed: Vec<u64> = member.ticks().throttle(3).take(2).collect();
+        assert_eq!(passed, vec![3, 6]);
+    }
+
+    #[test]
+    fn sample_latest_pairs_ticks_with_newest_value() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(240));
+        let member = TickMember::new(handle.clone(), 1);
+        let (sender, receiver) = flume::unbounded();
+
+        let mut sampled = member.ticks().sample_latest(receiver);
+        assert_eq!(sampled.next(), Some((1, None)));
+        sender.send(1).unwrap();
+        sender.send(2).unwrap();
+        assert_eq!(sampled.next(), Some((2, Some(2))));
+        assert_eq!(sampled.next(), Some((3, Some(2))));
+    }
+
+    #[test]
+    fn debounce_until_idle_waits_for_quiet_stream() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(240));
+        let member = TickMember::new(handle.clone(), 1);
+        // keeps the barrier closed once it stops waiting
+        let blocker = Arc::new(TickMember::new(handle.clone(), 1));
+
+        let j = {
+            let blocker = blocker.clone();
+            std::thread::spawn(move || {
+                for _ in 0..3 {
+                    blocker.wait_for_tick();
+                }
+            })
+        };
+
+        let mut debounced = member
+            .ticks()
+            .debounce_until_idle(Duration::from_millis(100));
+        let last = debounced.next().unwrap();
+        j.join().unwrap();
+        assert!(last >= 2, "expected the trailing tick, got {}", last);
+    }
+
+    #[test]
+    fn stopwatch_follows_frames() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(200));
+        let stopwatch = TickStopwatch::new(&handle);
+
+        std::thread::sleep(Duration::from_millis(60));
+        let elapsed = stopwatch.elapsed();
+        assert!(elapsed >= Duration::from_millis(30), "{:?}", elapsed);
+
+        stopwatch.stop();
+        let stopped = stopwatch.elapsed();
+        std::thread::sleep(Duration::from_millis(30));
+        assert_eq!(stopwatch.elapsed(), stopped);
+    }
+
+    #[test]
+    fn countdown_finishes() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(200));
+        let countdown = Countdown::new(&handle, Duration::from_millis(40));
+
+        assert!(!countdown.is_finished());
+        assert!(countdown.wait());
+        assert!(countdown.is_finished());
+        assert_eq!(countdown.remaining(), Duration::ZERO);
+    }
+
+    #[test]
+    fn tween_reaches_target() {
+        let (manager, handle) = TickManager::new(Speed::Fps(200));
+        let tween =
+            Tween::new(&handle, 0.0, 10.0, TweenDuration::Ticks(5), Easing::Linear).unwrap();
+
+        let mut last = tween.value();
+        assert_eq!(last, 0.0);
+        while !tween.is_finished() {
+            handle.sleep_until_next_frame().unwrap();
+            let value = tween.value();
+            assert!(value >= last);
+            last = value;
+        }
+        assert_eq!(tween.value(), 10.0);
+
+        handle.shutdown_with_timeout(Duration::from_secs(1));
+        drop(manager);
+        assert!(Tween::new(&handle, 0.0, 1.0, TweenDuration::Ticks(1), Easing::Linear).is_err());
+    }
+
+    #[test]
+    fn retry_backs_off_until_exhausted() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(500));
+        let (sender, receiver) = flume::unbounded();
+        let policy = RetryPolicy {
+            initial_delay: Duration::from_millis(5),
+            multiplier: 2.0,
+            max_delay: Duration::from_secs(1),
+            max_attempts: Some(3),
+            jitter: 0.0,
+            domain: ClockDomain::Sim,
+        };
+        let retry = handle.retry(policy, sender);
+
+        let attempts: Vec<RetryAttempt> = receiver.iter().collect();
+        let numbers: Vec<u32> = attempts.iter().map(|a| a.attempt).collect();
+        assert_eq!(numbers, vec![1, 2, 3]);
+        assert_eq!(attempts[2].delay, Duration::from_millis(20));
+        assert!(!retry.is_pending());
+        assert_eq!(retry.attempts(), 3);
+    }
+
+    #[test]
+    fn retry_delay_stays_at_the_maximum_for_late_attempts() {
+        let policy = RetryPolicy {
+            max_attempts: None,
+            ..Default::default()
+        };
+        assert_eq!(policy.base_delay(1), policy.initial_delay);
+        for attempt in [70, 1100, u32::MAX] {
+            assert_eq!(policy.base_delay(attempt), policy.max_delay);
+        }
+    }
+
+    #[test]
+    fn retry_stops_when_cancelled() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(500));
+        let (sender, receiver) = flume::unbounded();
+        let policy = RetryPolicy {
+            initial_delay: Duration::from_millis(5),
+            max_attempts: None,
+            ..Default::default()
+        };
+        let retry = handle.retry(policy, sender);
+
+        assert_eq!(receiver.recv().unwrap().attempt, 1);
+        retry.cancel();
+        assert!(!retry.is_pending());
+        // the manager drops the schedule, closing the channel
+        while receiver.recv().is_ok() {}
+        assert!(retry.attempts() <= 2);
+    }
+
+    #[test]
+    fn circuit_breaker_demotes_and_restores() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(500));
+        let events = handle.subscribe_events().unwrap();
+        handle
+            .set_circuit_breaker(Some(CircuitBreakerPolicy {
+                max_misses: 2,
+                window: 4,
+                demote_factor: 4,
+                restore_after: 3,
+            }))
+            .unwrap();
+
+        // never waits, so it misses every deadline
+        let slow = Arc::new(TickMember::new(handle.clone(), 1));
+        let event = events.recv_timeout(Duration::from_secs(1)).unwrap().event;
+        assert_eq!(
+            event,
+            TickEvent::MemberDemoted {
+                id: slow.id,
+                factor: 4
+            }
+        );
+
+        let j = {
+            let slow = slow.clone();
+            std::thread::spawn(move || {
+                for _ in 0..6 {
+                    slow.wait_for_tick();
+                }
+            })
+        };
+        let event = events.recv_timeout(Duration::from_secs(1)).unwrap().event;
+        assert_eq!(
+            event,
+            TickEvent::MemberRestored {
+                id: slow.id,
+                factor: 1
+            }
+        );
+        j.join().unwrap();
+    }
+
+    #[test]
+    fn starved_member_gets_boosted() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(500));
+        let events = handle.subscribe_events().unwrap();
+        handle
+            .set_starvation_policy(Some(StarvationPolicy {
+                threshold: 0.5,
+                window: 4,
+                boost_for: 2,
+            }))
+            .unwrap();
+
+        // the fast member is starved by a member that never finishes
+        let fast = TickMember::new(handle.clone(), 1);
+        let _blocker = TickMember::new(handle.clone(), 1);
+        fast.set_state(MemberState::Finished);
+
+        // the blocker is starved as well, so look for the fast member specifically
+        loop {
+            let event = events.recv_timeout(Duration::from_secs(1)).unwrap().event;
+            if event == (TickEvent::MemberBoosted { id: fast.id }) {
+                break;
+            }
+        }
+    }
+
+    #[test]
+    fn exclusive_frame_skips_other_members() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(500));
+        let member = TickMember::new(handle.clone(), 1);
+        // never finishes, so ordinary frames are never dispatched
+        let _blocker = TickMember::new(handle.clone(), 1);
+
+        let frame = handle.clock().tick_number() + 5;
+        assert!(member.reserve_exclusive(frame));
+        assert!(!member.reserve_exclusive(frame), "frame is already taken");
+        assert!(!member.reserve_exclusive(0), "frame already passed");
+
+        let mut ticks = member.ticks();
+        assert_eq!(
+            ticks.next_timeout(Some(Duration::from_secs(1))),
+            StreamItem::Item(1)
+        );
+    }
+
+    #[test]
+    fn quiesce_holds_members_between_ticks() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(500));
+        let member = Arc::new(TickMember::new(handle.clone(), 1));
+        let count = Arc::new(AtomicUsize::new(0));
+        let running = Arc::new(std::sync::atomic::AtomicBool::new(true));
+
+        let j = {
+            let member = member.clone();
+            let count = count.clone();
+            let running = running.clone();
+            std::thread::spawn(move || {
+                while running.load(Ordering::SeqCst) {
+                    member.wait_for_tick();
+                    std::thread::sleep(Duration::from_millis(2));
+                    count.fetch_add(1, Ordering::SeqCst);
+                }
+            })
+        };
+
+        std::thread::sleep(Duration::from_millis(20));
+        let guard = handle.quiesce().unwrap();
+        let held = count.load(Ordering::SeqCst);
+        std::thread::sleep(Duration::from_millis(30));
+        assert_eq!(count.load(Ordering::SeqCst), held);
+
+        drop(guard);
+        running.store(false, Ordering::SeqCst);
+        j.join().unwrap();
+        assert!(count.load(Ordering::SeqCst) > held);
+    }
+
+    #[test]
+    fn unregister_group_removes_all_members() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(500));
+        let events = handle.subscribe_events().unwrap();
+        let ai1 = Arc::new(TickMember::new_in_group(handle.clone(), 1, "ai"));
+        let ai2 = Arc::new(TickMember::new_in_group(handle.clone(), 1, "ai"));
+        let other = TickMember::new(handle.clone(), 1);
+
+        let joins: Vec<_> = [ai1.clone(), ai2.clone()]
+            .into_iter()
+            .map(|member| {
+                std::thread::spawn(move || {
+                    while member.is_registered() {
+                        member.wait_for_tick();
+                    }
+                })
+            })
+            .collect();
+
+        handle.unregister_group("ai").unwrap();
+        for j in joins {
+            j.join().unwrap();
+        }
+        assert_eq!(
+            events.recv_timeout(Duration::from_secs(1)).unwrap().event,
+            TickEvent::GroupUnregistered {
+                group: "ai".to_string(),
+                members: vec![ai1.id, ai2.id],
+            }
+        );
+
+        // the remaining member isn't held back by the removed ones
+        other.wait_for_tick();
+        assert!(other.is_registered());
+    }
+
+    #[test]
+    fn migrate_moves_member_to_other_manager() {
+        let (_menu, menu_handle) = TickManager::new(Speed::Fps(500));
+        let (_game, game_handle) = TickManager::new(Speed::Fps(500));
+        let existing = TickMember::new(game_handle.clone(), 1);
+
+        let mut member = TickMember::new(menu_handle.clone(), 1);
+        member.wait_for_tick();
+        member.migrate(&game_handle).unwrap();
+        assert_eq!(member.id, 1);
+
+        // the menu manager no longer waits on the member
+        let menu_member = TickMember::new(menu_handle.clone(), 1);
+        menu_member.wait_for_tick();
+        menu_member.wait_for_tick();
+
+        // a manager that is gone doesn't take it, it stays with the game manager
+        let (gone, gone_handle) = TickManager::new(Speed::Fps(500));
+        gone_handle.shutdown_with_timeout(Duration::from_secs(1));
+        drop(gone);
+        assert_eq!(member.migrate(&gone_handle), Err(TickError::ManagerGone));
+        assert_eq!(member.id, 1);
+        assert!(member.is_registered());
+        drop(existing);
+        assert!(member.wait_for_tick_timeout(Duration::from_secs(1)).is_ok());
+    }
+
+    #[test]
+    fn builder_names_thread_and_tags_events() {
+        let (manager, handle) = TickManager::builder(Speed::Fps(500))
+            .thread_name("physics-clock")
+            .build();
+        assert_eq!(handle.manager_id(), manager.id());
+
+        let (other, _other_handle) = TickManager::new(Speed::Fps(500));
+        assert_ne!(other.id(), manager.id());
+
+        let events = handle.subscribe_events().unwrap();
+        let _member = TickMember::new_in_group(handle.clone(), 1, "ai");
+        handle.unregister_group("ai").unwrap();
+        let event = events.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert_eq!(event.manager_id, manager.id());
+
+        handle.shutdown_with_timeout(Duration::from_secs(1));
+        drop(manager);
+        assert!(handle.subscribe_events().is_none());
+    }
+
+    #[test]
+    fn build_unique_returns_existing_manager() {
+        let first = TickManager::builder(Speed::Fps(500)).build_unique("unique-test");
+        assert!(first.is_created());
+        let second = TickManager::builder(Speed::Fps(500)).build_unique("unique-test");
+        assert!(!second.is_created());
+        assert_eq!(second.handle().manager_id(), first.handle().manager_id());
+        assert!(TickManager::lookup("unique-test").is_some());
+
+        drop(second);
+        drop(first);
+        assert!(TickManager::lookup("unique-test").is_none());
+    }
+
+    #[test]
+    fn catch_ticks_reports_panics() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(500));
+        let events = handle.subscribe_events().unwrap();
+        let faulty = TickMember::new(handle.clone(), 1);
+        let other = TickMember::new(handle.clone(), 1);
+
+        let faulty_id = faulty.id;
+        let j = std::thread::spawn(move || {
+            let mut ticks = 0;
+            faulty.catch_ticks(PanicAction::Unregister, || {
+                ticks += 1;
+                if ticks == 2 {
+                    panic!("tick handler failed");
+                }
+                true
+            });
+            assert!(!faulty.is_registered());
+        });
+
+        for _ in 0..5 {
+            other.wait_for_tick();
+        }
+        j.join().unwrap();
+        assert_eq!(
+            events.recv_timeout(Duration::from_secs(1)).unwrap().event,
+            TickEvent::MemberPanicked {
+                id: faulty_id,
+                message: "tick handler failed".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn supervisor_restarts_panicking_worker() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(500));
+        let events = handle.subscribe_events().unwrap();
+        let starts = Arc::new(AtomicUsize::new(0));
+
+        let supervised = {
+            let starts = starts.clone();
+            handle.supervise(
+                MemberConfig::default(),
+                RestartPolicy::Restart {
+                    max: 3,
+                    backoff: Duration::from_millis(1),
+                },
+                move |member| {
+                    member.wait_for_tick();
+                    if starts.fetch_add(1, Ordering::SeqCst) < 2 {
+                        panic!("worker failed");
+                    }
+                },
+            )
+        };
+        let id = supervised.id;
+        supervised.join();
+
+        assert_eq!(starts.load(Ordering::SeqCst), 3);
+        let mut restarts = Vec::new();
+        while restarts.len() < 2 {
+            let event = events.recv_timeout(Duration::from_secs(1)).unwrap().event;
+            if let TickEvent::MemberRestarted {
+                id: restarted,
+                restarts: count,
+            } = event
+            {
+                assert_eq!(restarted, id);
+                restarts.push(count);
+            }
+        }
+        assert_eq!(restarts, vec![1, 2]);
+    }
+
+    #[test]
+    fn ignored_panics_respawn_after_a_backoff() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(500));
+        let starts = Arc::new(AtomicUsize::new(0));
+        let supervised = {
+            let starts = starts.clone();
+            handle.supervise(
+                MemberConfig::default(),
+                RestartPolicy::Ignore,
+                move |_member| {
+                    starts.fetch_add(1, Ordering::SeqCst);
+                    panic!("fails on entry");
+                },
+            )
+        };
+        std::thread::sleep(IGNORE_BACKOFF * 2 + IGNORE_BACKOFF / 2);
+        let started = starts.load(Ordering::SeqCst);
+        assert!((2..=4).contains(&started), "started {started} times");
+        assert!(!supervised.is_finished());
+    }
+
+    #[test]
+    fn late_joiner_receives_bootstrap_frame() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(500));
+        handle.sleep_until_next_frame().unwrap();
+        handle.sleep_until_next_frame().unwrap();
+
+        let member = TickMember::with_config(
+            handle.clone(),
+            MemberConfig {
+                bootstrap: true,
+                ..Default::default()
+            },
+        );
+        let info = member.bootstrap_info().unwrap();
+        assert!(info.tick_number >= 2);
+        assert!(info.delta > Duration::ZERO);
+
+        let plain = TickMember::new(handle.clone(), 1);
+        assert!(plain.bootstrap_info().is_none());
+    }
+
+    #[test]
+    fn registration_takes_effect_at_frame_boundary() {
+        let (_manager, handle) = TickManager::builder(Speed::Interval(Duration::from_millis(20)))
+            .join_at_frame_boundary(true)
+            .build();
+        handle.sleep_until_next_frame().unwrap();
+
+        let member = TickMember::new(handle.clone(), 1);
+        assert!(member.active_from() > handle.clock().tick_number());
+        member.wait_for_tick();
+        assert!(handle.clock().tick_number() >= member.active_from());
+    }
+
+    #[test]
+    fn topology_changes_are_applied_together() {
+        let (_manager, handle) = TickManager::builder(Speed::Interval(Duration::from_millis(20)))
+            .batch_topology_changes(true)
+            .build();
+        let events = handle.subscribe_events().unwrap();
+
+        let first = TickMember::new(handle.clone(), 1);
+        let second = TickMember::new(handle.clone(), 1);
+        second.set_speed_factor(3);
+        let first_id = first.id;
+        drop(first);
+
+        let event = events.recv_timeout(Duration::from_secs(1)).unwrap().event;
+        match event {
+            TickEvent::TopologyChanged {
+                added,
+                removed,
+                speed_factors,
+                ..
+            } => {
+                assert_eq!(added, vec![second.id]);
+                assert!(removed.is_empty(), "{} never took part", first_id);
+                assert_eq!(speed_factors, vec![(second.id, 3)]);
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+    }
+
+    #[test]
+    fn members_sleep_through_frames() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(500));
+        let clock = handle.clock();
+        let member = TickMember::new(handle.clone(), 1);
+
+        let target = clock.tick_number() + 10;
+        member.wait_until_tick(target);
+        assert!(clock.tick_number() >= target);
+
+        let start = clock.tick_number();
+        member.skip_ticks(5);
+        assert!(clock.tick_number() > start + 5);
+    }
+
+    #[test]
+    fn overload_switches_to_degraded_speed() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(200));
+        let events = handle.subscribe_events().unwrap();
+        handle
+            .set_overload_policy(Some(OverloadPolicy {
+                threshold: 1.0,
+                window: 3,
+                degraded_speed: Some(Speed::Fps(30)),
+            }))
+            .unwrap();
+
+        let member = TickMember::new(handle.clone(), 1);
+        let j = std::thread::spawn(move || {
+            for _ in 0..6 {
+                member.wait_for_tick();
+                std::thread::sleep(Duration::from_millis(15));
+            }
+        });
+
+        let event = events.recv_timeout(Duration::from_secs(2)).unwrap().event;
+        match event {
+            TickEvent::Overloaded {
+                utilization,
+                switched_to,
+            } => {
+                assert!(utilization > 1.0);
+                assert_eq!(switched_to, Some(Speed::Fps(30)));
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+        j.join().unwrap();
+    }
+
+    #[test]
+    fn utilization_hook_reports_busy_frames() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(50));
+        let (sender, receiver) = flume::unbounded();
+        handle
+            .on_utilization(move |utilization| {
+                let _ = sender.send(utilization);
+            })
+            .unwrap();
+
+        let member = TickMember::new(handle.clone(), 1);
+        for _ in 0..5 {
+            member.wait_for_tick();
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        member.wait_for_tick();
+
+        let latest = receiver.try_iter().last().unwrap();
+        assert!(latest > 0.2 && latest < 1.0, "utilization {}", latest);
+    }
+
+    #[test]
+    fn standby_takes_over_members() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(100));
+        let member = TickMember::new(handle.clone(), 1);
+        let id = member.id;
+        let j = std::thread::spawn(move || {
+            for _ in 0..30 {
+                member.wait_for_tick();
+            }
+            member
+        });
+        while handle.clock().tick_number() < 5 {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        let standby = handle.prepare_standby(TickManager::builder(Speed::Fps(200)));
+        let (_new_manager, new_handle) = standby.switch_over().unwrap();
+        let switched_at = new_handle.clock().tick_number();
+        assert!(switched_at >= 5);
+
+        let member = j.join().unwrap();
+        assert_eq!(member.id, id);
+        assert!(member.is_registered());
+        assert!(handle.clock().tick_number() > switched_at);
+        // commands sent through the old handle reach the new manager
+        assert!(handle.sleep_until_next_frame().unwrap().tick_number > switched_at);
+    }
+
+    #[test]
+    fn schedule_sim_matches_factors_and_offsets() {
+        let sim = ScheduleSim::new(Speed::Fps(10))
+            .member(1, 0)
+            .member(2, 0)
+            .member(4, 1);
+        let frames = sim.frames(1..5);
+        let due: Vec<Vec<MemberID>> = frames.iter().map(|f| f.due.clone()).collect();
+        assert_eq!(due, vec![vec![0, 2], vec![0, 1], vec![0], vec![0, 1]]);
+        assert_eq!(frames[2].at, Duration::from_millis(300));
+        // paced like the Tick Manager, without rounding every period
+        let sim = ScheduleSim::new(Speed::Fps(60)).member(1, 0);
+        assert_eq!(sim.frames(60..61)[0].at, Duration::from_secs(1));
+        let late = u32::MAX as u64 + 1;
+        assert_eq!(
+            sim.frames(late..late + 1)[0].at,
+            Speed::Fps(60).duration_of(late)
+        );
+        assert_eq!(next_due(5, 4, 1), 9);
+        assert_eq!(next_due(4, 4, 1), 5);
+
+        // the Tick Manager dispatches by the same rule
+        let (_manager, handle) = TickManager::new(Speed::Fps(200));
+        let member = TickMember::with_config(
+            handle.clone(),
+            MemberConfig {
+                speed_factor: 4,
+                offset: 1,
+                ..Default::default()
+            },
+        );
+        for _ in 0..3 {
+            member.wait_for_tick();
+            assert!(is_due(handle.clock().tick_number(), 4, 1));
+        }
+    }
+
+    #[test]
+    fn observer_and_render_roles_never_hold_back_frames() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(200));
+        let observer = TickMember::<Observer>::with_role(handle.clone(), MemberConfig::default());
+        let render = TickMember::<Render>::with_role(handle.clone(), MemberConfig::default());
+        let sim = TickMember::new(handle.clone(), 1);
+
+        // neither the idle observer nor the busy render member stop the simulation member
+        let start = handle.clock().tick_number();
+        for _ in 0..20 {
+            sim.wait_for_tick();
+        }
+        assert!(handle.clock().tick_number() >= start + 20);
+
+        // the observer still receives ticks once it starts listening
+        observer.wait_for_tick();
+        assert!(observer.is_registered());
+        drop(render);
+    }
+
+    #[test]
+    fn member_builder_registers_with_settings() {
+        let (manager, handle) = TickManager::new(Speed::Fps(200));
+        let member = TickMember::builder(handle.clone())
+            .speed_factor(2)
+            .offset(1)
+            .name("physics")
+            .group("sim")
+            .build()
+            .unwrap();
+        assert_eq!(member.name().as_deref(), Some("physics"));
+        for _ in 0..3 {
+            member.wait_for_tick();
+            assert!(is_due(handle.clock().tick_number(), 2, 1));
+        }
+
+        let observer = TickMember::builder(handle.clone())
+            .role::<Observer>()
+            .build()
+            .unwrap();
+        observer.wait_for_tick();
+
+        drop(manager);
+        let result = TickMember::builder(handle).build();
+        assert_eq!(result.unwrap_err(), TickError::ManagerGone);
+    }
+
+    #[test]
+    fn negotiates_slowest_common_speed() {
+        let plan = negotiate_speed(&[Speed::Fps(60), Speed::Fps(50)]).unwrap();
+        assert_eq!(plan.speed, Speed::Fps(300));
+        assert_eq!(plan.factors, vec![5, 6]);
+
+        let plan = negotiate_speed(&[
+            Speed::Interval(Duration::from_millis(100)),
+            Speed::Interval(Duration::from_millis(250)),
+        ])
+        .unwrap();
+        assert_eq!(plan.speed, Speed::Fps(20));
+        assert_eq!(plan.factors, vec![2, 5]);
+
+        let plan = negotiate_speed(&[
+            Speed::Interval(Duration::from_millis(30)),
+            Speed::Interval(Duration::from_millis(45)),
+        ])
+        .unwrap();
+        assert_eq!(plan.speed, Speed::Interval(Duration::from_millis(15)));
+        assert_eq!(plan.factors, vec![2, 3]);
+
+        assert!(negotiate_speed(&[]).is_none());
+        assert!(TickManager::try_new_auto(&[]).is_none());
+        assert!(TickManager::try_new_auto(&[Speed::Fps(60), Speed::Fps(0)]).is_none());
+
+        let (_manager, handle, plan) = TickManager::new_auto(&[Speed::Fps(100), Speed::Fps(50)]);
+        assert_eq!(plan.speed, Speed::Fps(100));
+        let slow = TickMember::new(handle, plan.factors[1]);
+        slow.wait_for_tick();
+        assert!(slow.is_registered());
+    }
+
+    #[test]
+    fn cycle_reports_coinciding_members() {
+        assert_eq!(cycle_length([2, 3, 4]), Some(12));
+        let huge = u32::MAX as usize;
+        assert_eq!(cycle_length([huge, huge - 1, huge - 2]), None);
+        let cycle = ScheduleSim::new(Speed::Fps(60))
+            .member(2, 0)
+            .member(3, 0)
+            .member(3, 1)
+            .cycle();
+        assert_eq!(cycle.length, 6);
+        assert_eq!(cycle.worst_frames(), Some((vec![0, 4], 2)));
+        let long = ScheduleSim::new(Speed::Fps(60))
+            .member(huge, 0)
+            .member(huge - 1, 0)
+            .member(huge - 2, 0)
+            .cycle();
+        assert_eq!(long.length, u64::MAX);
+        assert_eq!(long.worst_frames(), None);
+
+        let (_manager, handle) = TickManager::new(Speed::Fps(60));
+        let _a = TickMember::new(handle.clone(), 2);
+        let _b = TickMember::new(handle.clone(), 4);
+        let cycle = handle.cycle().unwrap();
+        assert_eq!(cycle.length, 4);
+        let frames: Vec<Vec<MemberID>> = cycle.frames().collect();
+        assert_eq!(frames, vec![vec![0, 1], vec![], vec![0], vec![]]);
+    }
+
+    #[test]
+    fn balanced_offsets_spread_equal_factors() {
+        assert_eq!(balanced_offset(4, [(4, 0), (4, 1)]), 2);
+        assert_eq!(balanced_offset(2, [(4, 0), (4, 2)]), 1);
+
+        let (_manager, handle) = TickManager::builder(Speed::Fps(60))
+            .balance_offsets(true)
+            .build();
+        let members: Vec<TickMember> = (0..4).map(|_| TickMember::new(handle.clone(), 4)).collect();
+        let fixed = TickMember::builder(handle.clone())
+            .speed_factor(4)
+            .offset(0)
+            .build()
+            .unwrap();
+        let cycle = handle.cycle().unwrap();
+        let frames: Vec<Vec<MemberID>> = cycle.frames().collect();
+        assert_eq!(frames, vec![vec![0, 4], vec![1], vec![2], vec![3]]);
+        drop((members, fixed));
+    }
+
+    #[test]
+    fn spread_work_spans_frames_without_load() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(100));
+        let events = handle.subscribe_events().unwrap();
+        let (sender, receiver) = flume::unbounded();
+        handle
+            .on_utilization(move |utilization| {
+                let _ = sender.send(utilization);
+            })
+            .unwrap();
+
+        let heavy = TickMember::builder(handle.clone())
+            .speed_factor(4)
+            .spread_work(true)
+            .build()
+            .unwrap();
+        let fast = TickMember::new(handle.clone(), 1);
+        let j = std::thread::spawn(move || {
+            for _ in 0..3 {
+                heavy.wait_for_tick();
+                // longer than a frame, shorter than the 4 frames until the next due one
+                std::thread::sleep(Duration::from_millis(20));
+            }
+        });
+        let start = handle.clock().tick_number();
+        for _ in 0..12 {
+            fast.wait_for_tick();
+        }
+        j.join().unwrap();
+
+        // the fast member kept ticking through the lease
+        assert!(handle.clock().tick_number() - start < 16);
+        assert!(receiver.try_iter().all(|utilization| utilization < 1.0));
+        assert!(
+            events
+                .try_iter()
+                .all(|e| !matches!(e.event, TickEvent::LeaseOverrun { .. }))
+        );
+    }
+
+    #[test]
+    fn lease_until_next_due_reports_overruns() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(100));
+        let events = handle.subscribe_events().unwrap();
+        let member = TickMember::new(handle.clone(), 2);
+        member.wait_for_tick();
+        let frame = handle.clock().tick_number();
+        assert_eq!(member.lease_until_next_due(), Some(frame + 2));
+        // outlast the lease
+        std::thread::sleep(Duration::from_millis(60));
+        member.wait_for_tick();
+
+        let overrun = events
+            .try_iter()
+            .any(|e| e.event == TickEvent::LeaseOverrun { id: member.id });
+        assert!(overrun);
+    }
+
+    #[test]
+    fn background_member_runs_within_its_slices() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(100));
+        let member = TickMember::builder(handle.clone())
+            .budget(Duration::from_millis(2))
+            .build()
+            .unwrap();
+
+        let mut steps = 0;
+        let mut longest = Duration::ZERO;
+        let mut slice_start = None;
+        member.run_sliced(|| {
+            let remaining = member.budget_remaining().unwrap();
+            assert!(remaining <= Duration::from_millis(2));
+            let start = *slice_start.get_or_insert(Instant::now());
+            std::thread::sleep(Duration::from_micros(500));
+            if member.is_budget_exhausted() {
+                longest = longest.max(start.elapsed());
+                slice_start = None;
+            }
+            steps += 1;
+            steps < 40
+        });
+        // several steps fit into every slice, but no slice runs much longer than the budget
+        assert!(handle.clock().tick_number() < 40);
+        assert!(longest < Duration::from_millis(5));
+    }
+
+    #[test]
+    fn ticks_carry_headroom_until_next_frame() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(50));
+        let member = TickMember::new(handle.clone(), 1);
+        assert!(member.tick_info().is_none());
+        member.wait_for_tick();
+        let info = member.tick_info().unwrap();
+        assert_eq!(info.tick_number, handle.clock().tick_number());
+        assert!(info.headroom > Duration::ZERO && info.headroom <= Duration::from_millis(20));
+
+        let observed = handle.sleep_until_next_frame().unwrap();
+        assert!(observed.headroom <= Duration::from_millis(20));
+    }
+
+    #[test]
+    fn watch_members_sends_snapshot_then_diffs() {
+        let (manager, handle) = TickManager::new(Speed::Fps(100));
+        let first = TickMember::builder(handle.clone())
+            .name("physics")
+            .build()
+            .unwrap();
+        let watch = handle.watch_members().unwrap();
+        match watch.recv_timeout(Duration::from_secs(1)).unwrap() {
+            MembersDiff::Snapshot(members) => {
+                assert_eq!(members.len(), 1);
+                assert_eq!(members[0].name.as_deref(), Some("physics"));
+            }
+            other => panic!("unexpected diff {:?}", other),
+        }
+
+        let second = TickMember::new(handle.clone(), 2);
+        let added = watch.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert!(matches!(added, MembersDiff::Added(ref m) if m.id == second.id));
+
+        second.set_speed_factor(3);
+        let changed = watch.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert!(matches!(changed, MembersDiff::Changed(ref m) if m.speed_factor == 3));
+
+        let id = first.id;
+        drop(first);
+        let removed = watch.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert_eq!(removed, MembersDiff::Removed(id));
+        assert_eq!(handle.list_members().unwrap().len(), 1);
+
+        handle.shutdown_with_timeout(Duration::from_secs(1));
+        drop(manager);
+        assert!(handle.watch_members().is_none());
+    }
+
+    #[test]
+    fn tagged_observers_only_see_tagged_frames() {
+        let (manager, handle) = TickManager::new(Speed::Fps(200));
+        handle.tag_frames("fifth", Some(TagRule::Every(5))).unwrap();
+        handle
+            .tag_frames(
+                "odd",
+                Some(TagRule::Custom(|info| info.tick_number % 2 == 1)),
+            )
+            .unwrap();
+        let fifth = handle.observe_tagged("fifth").unwrap();
+        let odd = handle.observe_tagged("odd").unwrap();
+
+        for _ in 0..3 {
+            let info = fifth.recv_timeout(Duration::from_secs(1)).unwrap();
+            assert_eq!(info.tick_number % 5, 0);
+            let info = odd.recv_timeout(Duration::from_secs(1)).unwrap();
+            assert_eq!(info.tick_number % 2, 1);
+        }
+
+        handle.shutdown_with_timeout(Duration::from_secs(1));
+        drop(manager);
+        assert!(handle.observe_tagged("fifth").is_none());
+    }
+
+    #[test]
+    fn timer_wheel_fires_in_due_order() {
+        let mut wheel = TimerWheel::new(0);
+        // spread over every level of the wheel
+        let dues = [1, 63, 64, 65, 4095, 4096, 300_000];
+        for due in dues.iter().rev() {
+            wheel.insert(*due, *due);
+        }
+        assert_eq!(wheel.len(), dues.len());
+        let mut fired = Vec::new();
+        let mut frame = 0;
+        while !wheel.is_empty() {
+            frame += 1;
+            for (due, item) in wheel.advance(frame) {
+                assert_eq!(due, frame);
+                fired.push(item);
+            }
+        }
+        assert_eq!(fired, dues);
+
+        let (_manager, handle) = TickManager::new(Speed::Fps(200));
+        let (_timer, frames) = handle.after_frames(3);
+        let info = frames.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert!(info.tick_number >= 3);
+    }
+
+    #[test]
+    fn scheduled_items_cancel_and_reschedule() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(200));
+
+        let (timeout, fired) = handle.after_frames(20);
+        assert!(timeout.is_pending());
+        timeout.cancel();
+        assert!(!timeout.is_pending());
+        assert!(!timeout.reschedule(1));
+        // the entry is dropped, closing the channel without firing
+        assert!(fired.recv_timeout(Duration::from_secs(1)).is_err());
+
+        let (timer, fired) = handle.after_frames(1000);
+        let start = handle.clock().tick_number();
+        assert!(timer.reschedule(2));
+        let info = fired.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert!(info.tick_number < start + 100);
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(!timer.is_pending());
+
+        let (repeat, fired) = handle.every_frames(3);
+        let first = fired.recv().unwrap().tick_number;
+        assert_eq!(fired.recv().unwrap().tick_number, first + 3);
+        repeat.cancel();
+        while fired.recv().is_ok() {}
+    }
+
+    #[test]
+    fn sim_timers_freeze_while_wall_timers_keep_firing() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(200));
+        handle.sleep_until_next_frame().unwrap();
+        let guard = handle.quiesce().unwrap();
+
+        let (_sim, sim_fired) = handle.after(Duration::from_millis(10), ClockDomain::Sim);
+        let (_wall, wall_fired) = handle.after(Duration::from_millis(10), ClockDomain::Wall);
+        let paused_at = handle.clock().tick_number();
+        let info = wall_fired.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert_eq!(info.tick_number, paused_at);
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(sim_fired.try_recv().is_err());
+
+        drop(guard);
+        let info = sim_fired.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert!(info.tick_number > paused_at);
+    }
+
+    #[test]
+    fn sim_time_freezes_while_paused_and_wall_time_does_not() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(200));
+        handle.sleep_until_next_frame().unwrap();
+        let guard = handle.quiesce().unwrap();
+        std::thread::sleep(Duration::from_millis(60));
+        drop(guard);
+
+        let info = handle.sleep_until_next_frame().unwrap();
+        assert!(info.wall_time >= info.sim_time + Duration::from_millis(50));
+        let clock = handle.clock();
+        assert!(clock.wall_time() >= clock.sim_time() + Duration::from_millis(50));
+    }
+
+    #[test]
+    fn seeded_managers_deliver_the_same_frame_seeds() {
+        let (_manager, handle) = TickManagerBuilder::new(Speed::Fps(200)).seed(7).build();
+        let info = handle.sleep_until_next_frame().unwrap();
+        assert_eq!(info.seed, Some(frame_seed(7, info.tick_number)));
+        assert_ne!(frame_seed(7, 1), frame_seed(7, 2));
+        assert_ne!(frame_seed(7, 1), frame_seed(8, 1));
+
+        let (_manager, handle) = TickManager::new(Speed::Fps(200));
+        assert_eq!(handle.sleep_until_next_frame().unwrap().seed, None);
+    }
+
+    #[test]
+    fn checkpoints_run_between_ticks_on_their_cadence() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(200));
+        let events = handle.subscribe_events().unwrap();
+        let busy = Arc::new(AtomicUsize::new(0));
+        let taken = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let (hook_busy, hook_taken) = (busy.clone(), taken.clone());
+        handle
+            .on_checkpoint(move |info| {
+                assert_eq!(hook_busy.load(Ordering::SeqCst), 0);
+                hook_taken.lock().unwrap().push(info.tick_number);
+            })
+            .unwrap();
+        handle.set_checkpoint_every(Some(3)).unwrap();
+
+        let member = TickMember::new(handle.clone(), 1);
+        let worker = std::thread::spawn(move || {
+            for _ in 0..10 {
+                member.wait_for_tick();
+                busy.fetch_add(1, Ordering::SeqCst);
+                std::thread::sleep(Duration::from_millis(2));
+                busy.fetch_sub(1, Ordering::SeqCst);
+            }
+        });
+
+        let completed = events
+            .iter()
+            .find_map(|e| match e.event {
+                TickEvent::CheckpointCompleted { tick_number, .. } => Some(tick_number),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(completed % 3, 0);
+        worker.join().unwrap();
+        assert!(taken.lock().unwrap().iter().all(|tick| tick % 3 == 0));
+    }
+
+    #[test]
+    fn deferred_closures_run_once_the_epoch_moves() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(200));
+        let member = TickMember::new(handle.clone(), 1);
+        member.wait_for_tick();
+        let clock = handle.clock();
+
+        let (sender, receiver) = flume::bounded(1);
+        let retired_in = clock.tick_number();
+        handle
+            .defer_until_next_frame(move || {
+                let _ = sender.send(());
+            })
+            .unwrap();
+        // the member is still in the middle of its tick, nothing is freed
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(receiver.try_recv().is_err());
+        assert!(clock.epoch() <= retired_in);
+
+        member.wait_for_tick();
+        receiver.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert!(clock.epoch() > retired_in);
+    }
+
+    #[test]
+    fn ordered_dispatch_sorts_by_group_phase_and_id() {
+        let order = OrderedDispatch::new().group_order(["input", "sim"]);
+        let (sender, _receiver) = flume::unbounded();
+        let info = |group: Option<&str>, offset| MemberInfo {
+            sender: sender.clone(),
+            state: MemberState::Finished,
+            last_tick: Instant::now(),
+            name: None,
+            group: group.map(str::to_string),
+            tenant: None,
+            offset,
+            priority: 0,
+            after: Vec::new(),
+            phase: 0,
+            kind: MemberKind::Participant,
+            spread_work: false,
+            leased: false,
+            budget: None,
+            state_cell: Arc::new(StateCell::new(MemberState::Finished)),
+            parked: None,
+        };
+        let mut members = [
+            (0, info(None, 0)),
+            (1, info(Some("audio"), 0)),
+            (2, info(Some("sim"), 1)),
+            (3, info(Some("sim"), 0)),
+            (4, info(Some("input"), 0)),
+            (5, info(Some("sim"), 0)),
+        ];
+        members.sort_by_key(|(id, member_info)| order.key(*id, member_info));
+        let ids: Vec<MemberID> = members.iter().map(|(id, _)| *id).collect();
+        assert_eq!(ids, vec![4, 3, 5, 2, 1, 0]);
+
+        // the debug assertions of the manager thread check every delivery
+        let (_manager, handle) = TickManagerBuilder::new(Speed::Fps(200))
+            .ordered_dispatch(order)
+            .build();
+        let workers: Vec<_> = ["sim", "input", "sim"]
+            .into_iter()
+            .map(|group| {
+                let member = TickMember::new_in_group(handle.clone(), 1, group);
+                std::thread::spawn(move || {
+                    for _ in 0..3 {
+                        member.wait_for_tick();
+                    }
+                })
+            })
+            .collect();
+        for worker in workers {
+            worker.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn members_receive_messages_with_their_next_tick() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(200));
+        let sender = TickMember::new(handle.clone(), 1);
+        let receiver = TickMember::new(handle.clone(), 2);
+        let receiver_id = receiver.id;
+        let worker = std::thread::spawn(move || {
+            let mut messages = Vec::new();
+            while messages.len() < 2 {
+                receiver.wait_for_tick();
+                messages.extend(receiver.messages());
+            }
+            messages
+        });
+
+        sender.wait_for_tick();
+        sender.send_to(receiver_id, 42u32).unwrap();
+        sender.send_to(receiver_id, "hello").unwrap();
+        while !worker.is_finished() {
+            sender.wait_for_tick();
+        }
+        let mut messages = worker.join().unwrap().into_iter();
+        let first = messages.next().unwrap();
+        assert_eq!(first.from, sender.id);
+        assert!(!first.is::<&str>());
+        assert_eq!(first.downcast::<u32>().unwrap(), 42);
+        assert_eq!(
+            messages.next().unwrap().downcast::<&str>().unwrap(),
+            "hello"
+        );
+    }
+
+    #[test]
+    fn topic_messages_are_visible_next_tick() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(200));
+        let publisher = TickMember::new(handle.clone(), 1);
+        let subscriber = TickMember::new(handle.clone(), 1);
+        subscriber.subscribe("damage").unwrap();
+        let worker = std::thread::spawn(move || {
+            loop {
+                subscriber.wait_for_tick();
+                let messages = subscriber.topic_messages();
+                if let Some(message) = messages.first() {
+                    let tick = subscriber.tick_info().unwrap().tick_number;
+                    return (message.clone(), tick);
+                }
+            }
+        });
+
+        publisher.wait_for_tick();
+        publisher.publish("damage", 12u32).unwrap();
+        publisher.publish("heal", 3u32).unwrap();
+        while !worker.is_finished() {
+            publisher.wait_for_tick();
+        }
+        let (message, received_in) = worker.join().unwrap();
+        assert_eq!(&*message.topic, "damage");
+        assert_eq!(message.get::<u32>(), Some(&12));
+        assert!(received_in > message.sent_in);
+    }
+
+    #[test]
+    fn command_buffers_apply_before_the_next_frame() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(200));
+        let member = TickMember::new(handle.clone(), 1);
+        member.wait_for_tick();
+        let submitted_in = member.tick_info().unwrap().tick_number;
+
+        let mut buffer = handle.command_buffer();
+        let applied_in = Arc::new(AtomicUsize::new(0));
+        let (clock, applied) = (handle.clock(), applied_in.clone());
+        buffer.run(move || applied.store(clock.tick_number() as usize, Ordering::SeqCst));
+        let pending = buffer.register::<Simulation>(MemberConfig {
+            speed_factor: 2,
+            ..Default::default()
+        });
+        buffer.set_speed_factor(member.id, 3);
+        assert_eq!(buffer.len(), 3);
+        buffer.submit().unwrap();
+
+        member.wait_for_tick();
+        let added = pending.wait().unwrap();
+        // applied between two frames, after the one it was filled in
+        assert!(applied_in.load(Ordering::SeqCst) as u64 >= submitted_in);
+        let members = handle.list_members().unwrap();
+        let speed_factor = |id| members.iter().find(|m| m.id == id).unwrap().speed_factor;
+        assert_eq!(speed_factor(member.id), 3);
+        assert_eq!(speed_factor(added.id), 2);
+    }
+
+    #[test]
+    fn wait_until_releases_on_the_first_matching_frame() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(500));
+        let start = handle.sleep_until_next_frame().unwrap().tick_number;
+        let info = handle
+            .wait_until(move |info| info.tick_number >= start + 10)
+            .unwrap();
+        assert_eq!(info.tick_number, start + 10);
+
+        // stateful predicates see every frame
+        let mut seen = 0;
+        let info = handle
+            .wait_until(move |_info| {
+                seen += 1;
+                seen == 3
+            })
+            .unwrap();
+        assert!(info.tick_number >= start + 12);
+    }
+
+    #[test]
+    fn fixed_point_time_uses_exact_periods() {
+        assert_eq!(Speed::Fps(60).duration_of(60), Duration::from_secs(1));
+        assert_eq!(
+            Speed::Fps(60).duration_of(1),
+            Duration::from_nanos(16_666_666)
+        );
+        let timeline = FixedTimeline::new(Instant::now(), 0, Duration::ZERO);
+        let deltas: Vec<Duration> = (1..=3)
+            .map(|frame| timeline.delta(&Speed::Fps(60), frame))
+            .collect();
+        assert_eq!(
+            deltas.iter().sum::<Duration>(),
+            Duration::from_nanos(50_000_000)
+        );
+
+        let (_manager, handle) = TickManagerBuilder::new(Speed::Fps(60))
+            .fixed_point_time(true)
+            .build();
+        let first = handle.sleep_until_next_frame().unwrap();
+        for _ in 0..5 {
+            let info = handle.sleep_until_next_frame().unwrap();
+            assert!([16_666_666, 16_666_667].contains(&info.delta.as_nanos()));
+        }
+        let last = handle.sleep_until_next_frame().unwrap();
+        let frames = last.tick_number - first.tick_number;
+        let elapsed = (last.sim_time - first.sim_time).as_nanos();
+        assert!(elapsed.abs_diff(frames as u128 * 16_666_667) <= frames as u128);
+    }
+
+    #[test]
+    fn frame_pacer_keeps_exactly_fps_frames_per_second() {
+        let start = Instant::now();
+        for fps in [60, 7, 144] {
+            let speed = Speed::Fps(fps);
+            let mut pacer = FramePacer::new(&speed, start);
+            for _ in 0..fps * 3 - 1 {
+                let due = pacer.next_due();
+                pacer.advance(&speed, due);
+            }
+            assert_eq!(pacer.next_due() - start, Duration::from_secs(3));
+        }
+
+        let (_manager, handle) = TickManager::new(Speed::Fps(100));
+        let first = handle.sleep_until_next_frame().unwrap();
+        let mut last = first;
+        for _ in 0..20 {
+            last = handle.sleep_until_next_frame().unwrap();
+        }
+        // frames are due a whole period apart, only falling behind can push them further
+        let scheduled = last.scheduled_at - first.scheduled_at;
+        assert!(
+            scheduled >= Duration::from_millis(10) * (last.tick_number - first.tick_number) as u32
+        );
+    }
+
+    #[test]
+    fn harness_reports_throughput_and_latency() {
+        let report = Harness::new(Speed::Fps(200))
+            .members(4, |i| SyntheticMember {
+                speed_factor: 1 + i % 2,
+                cost: Duration::from_micros(100),
+            })
+            .frames(20)
+            .run();
+        assert_eq!(report.members, 4);
+        assert!(report.frames >= 20);
+        assert!(report.member_ticks > 0);
+        assert!(report.frames_per_second() > 0.0);
+        assert!(report.latency.p50 <= report.latency.p99);
+        assert!(report.latency.p99 <= report.latency.max);
+    }
+
+    #[test]
+    fn parking_members_are_unparked_with_their_ticks() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(200));
+        let sender = TickMember::new(handle.clone(), 1);
+        let parked = TickMember::builder(handle.clone())
+            .wake(WakeStrategy::Park)
+            .build()
+            .unwrap();
+        let parked_id = parked.id;
+        // the member parks on the worker thread, not the one it was registered on
+        let worker = std::thread::spawn(move || {
+            let mut ticks = Vec::new();
+            let mut messages = Vec::new();
+            while messages.is_empty() {
+                parked.wait_for_tick();
+                ticks.push(parked.tick_info().unwrap().tick_number);
+                messages.extend(parked.messages());
+            }
+            (ticks, messages)
+        });
+
+        sender.wait_for_tick();
+        sender.send_to(parked_id, 7u8).unwrap();
+        while !worker.is_finished() {
+            sender.wait_for_tick();
+        }
+        let (ticks, messages) = worker.join().unwrap();
+        assert!(ticks.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(messages.len(), 1);
+        assert_eq!(
+            messages
+                .into_iter()
+                .next()
+                .unwrap()
+                .downcast::<u8>()
+                .unwrap(),
+            7
+        );
+    }
+
+    #[test]
+    fn wake_slot_wakes_the_waiting_thread() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(200));
+        let info = handle.sleep_until_next_frame().unwrap();
+        let slot = Arc::new(WakeSlot::default());
+        let waiter = {
+            let slot = slot.clone();
+            std::thread::spawn(move || {
+                let started = Instant::now();
+                loop {
+                    let seen = slot.sequence();
+                    if let Some(info) = slot.take() {
+                        return (info.tick_number, started.elapsed());
+                    }
+                    slot.wait(seen, Duration::from_secs(5));
+                }
+            })
+        };
+        std::thread::sleep(Duration::from_millis(20));
+        slot.put(info, waiter.thread());
+        let (tick_number, waited) = waiter.join().unwrap();
+        assert_eq!(tick_number, info.tick_number);
+        assert!(waited < Duration::from_secs(1), "{FUTEX_WAKE}: {waited:?}");
+    }
+
+    #[test]
+    fn member_state_is_shared_with_the_manager() {
+        let cell = StateCell::new(MemberState::Running);
+        cell.set(MemberState::Finished);
+        assert!(matches!(cell.get(), MemberState::Finished));
+
+        // the barrier only learns about finished members through their state cells
+        let (_manager, handle) = TickManager::new(Speed::Fps(200));
+        let member = TickMember::new(handle.clone(), 1);
+        member.wait_for_tick();
+        let first = member.tick_info().unwrap().tick_number;
+        // the manager marks the member Running before it sends the tick
+        assert!(matches!(member.state(), MemberState::Running));
+        member.set_state(MemberState::Hidden);
+        assert!(matches!(member.state(), MemberState::Hidden));
+        member.wait_for_tick();
+        assert!(member.tick_info().unwrap().tick_number > first);
+    }
+
+    #[test]
+    fn shutdown_skips_the_command_backlog() {
+        assert!(TickCommand::Shutdown.is_control());
+        assert!(!TickCommand::Unregister(0).is_control());
+
+        let (manager, handle) = TickManager::new(Speed::Fps(200));
+        let flood = std::thread::spawn(move || {
+            let mut sent = 0;
+            while handle
+                .send(TickCommand::Observe(flume::bounded(1).0))
+                .is_ok()
+            {
+                sent += 1;
+            }
+            sent
+        });
+        std::thread::sleep(Duration::from_millis(20));
+        let started = Instant::now();
+        drop(manager);
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert!(flood.join().unwrap() > 0);
+    }
+
+    #[test]
+    fn commands_wake_the_manager_between_frames() {
+        let (_manager, handle) = TickManager::new(Speed::Interval(Duration::from_secs(5)));
+        // the manager sleeps until the first frame is due, but answers right away
+        let started = Instant::now();
+        for _ in 0..10 {
+            assert!(handle.cycle().is_some());
+        }
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(handle.clock().tick_number(), 0);
+    }
+
+    #[test]
+    fn drop_policy_bounds_the_wait_for_a_wedged_manager() {
+        let (manager, handle) = TickManagerBuilder::new(Speed::Fps(200))
+            .drop_policy(DropPolicy::JoinWithTimeout(Duration::from_millis(50)))
+            .build();
+        let (wedged_sender, wedged) = flume::bounded(1);
+        handle.set_checkpoint_every(Some(1)).unwrap();
+        handle
+            .on_checkpoint(move |_info| {
+                let _ = wedged_sender.try_send(());
+                std::thread::sleep(Duration::from_millis(500));
+            })
+            .unwrap();
+        wedged.recv().unwrap();
+        let started = Instant::now();
+        drop(manager);
+        assert!(started.elapsed() < Duration::from_millis(400));
+
+        let (manager, _handle) = TickManagerBuilder::new(Speed::Fps(200))
+            .drop_policy(DropPolicy::Detach)
+            .build();
+        drop(manager);
+    }
+
+    #[test]
+    fn shutdown_with_timeout_cancels_stragglers() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(200));
+        let wedged = TickMember::new(handle.clone(), 1);
+        let waiting = TickMember::new(handle.clone(), 1);
+        let waiter = std::thread::spawn(move || {
+            while waiting.is_registered() {
+                waiting.wait_for_tick();
+            }
+        });
+        // the wedged member never finishes the tick it got
+        wedged.wait_for_tick();
+
+        let started = Instant::now();
+        let cancelled = handle.shutdown_with_timeout(Duration::from_millis(100));
+        assert!(started.elapsed() >= Duration::from_millis(100));
+        assert_eq!(cancelled, vec![wedged.id]);
+        waiter.join().unwrap();
+        wedged.wait_for_tick();
+        assert!(!wedged.is_registered());
+    }
+
+    #[test]
+    fn events_describe_their_member() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(500));
+        let events = handle.subscribe_events().unwrap();
+        let member = TickMember::with_config(
+            handle.clone(),
+            MemberConfig {
+                speed_factor: 2,
+                name: Some("physics".to_string()),
+                group: Some("world".to_string()),
+                ..Default::default()
+            },
+        );
+        let descriptor = member.descriptor();
+        assert_eq!(
+            descriptor.to_string(),
+            format!("member {} \"physics\" (group world, factor 2)", member.id)
+        );
+
+        member.catch_ticks(PanicAction::Unregister, || panic!("tick handler failed"));
+        let event = events.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert!(matches!(event.event, TickEvent::MemberPanicked { .. }));
+        assert_eq!(event.member, Some(descriptor));
+    }
+
+    #[test]
+    fn testkit_counts_ticks_per_speed_factor() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(500));
+        let mut kit = TestKit::new(handle);
+        let fast = kit.spawn_counting(3, 1);
+        let slow = kit.spawn_counting(2, 2);
+
+        let run = kit.run_ticks(40);
+        assert_eq!(run.frames(), 40);
+        kit.assert_counts(&run, 2);
+        assert!(kit.count(fast[0], &run) > kit.count(slow[0], &run));
+        assert_eq!(kit.counts(&run).len(), 5);
+    }
+
+    #[test]
+    fn clock_waits_for_frame_without_registering() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(500));
+        let clock = handle.clock();
+        let target = clock.tick_number() + 5;
+
+        let waiter = {
+            let clock = clock.clone();
+            std::thread::spawn(move || clock.wait_for_frame(target))
+        };
+        assert!(waiter.join().unwrap() >= target);
+        assert!(clock.tick_number() >= target);
+        assert!(clock.wait_for_frame(1) >= 1);
+        assert_eq!(
+            clock.wait_for_frame_timeout(u64::MAX, Duration::from_millis(20)),
+            None
+        );
+        // the waiter that timed out isn't left behind
+        assert_eq!(clock.waiting(), 0);
+    }
+
+    #[cfg(feature = "watch")]
+    #[test]
+    fn watch_follows_latest_frame() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(500));
+        let mut watch = handle.watch();
+        let first = watch.changed().unwrap();
+        assert!(watch.latest().unwrap().tick_number >= first.tick_number);
+
+        let mut reader = watch.clone();
+        let second = std::thread::spawn(move || reader.changed())
+            .join()
+            .unwrap()
+            .unwrap();
+        assert!(second.tick_number > first.tick_number);
+        assert!(watch.has_changed());
+        assert!(watch.changed_timeout(Duration::from_secs(1)).is_some());
+
+        let waiting = std::thread::spawn(move || watch.changed());
+        handle.shutdown_with_timeout(Duration::from_secs(1));
+        assert_eq!(waiting.join().unwrap().unwrap_err(), TickError::ManagerGone);
+    }
+
+    #[test]
+    fn tick_queue_hands_jobs_over_next_frame() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(20));
+        let member = TickMember::new(handle.clone(), 1);
+        let queue = TickQueue::new(&handle);
+
+        member.wait_for_tick();
+        let pushed_in = member.tick_info().unwrap().tick_number;
+        // the frame just started, the next one is 50ms away
+        queue.push(1);
+        queue.push(2);
+        queue.push(3);
+        assert_eq!(queue.pop_front(), None);
+        assert_eq!(queue.pending_len(), 3);
+
+        member.wait_for_tick();
+        assert!(member.tick_info().unwrap().tick_number > pushed_in);
+        assert_eq!(queue.pop_back(), Some(3));
+        assert_eq!(queue.pop_front(), Some(1));
+        assert_eq!(queue.drain_sealed(), vec![2]);
+    }
+
+    #[test]
+    fn set_speed_changes_frame_rate_at_runtime() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(20));
+        let member = TickMember::new(handle.clone(), 1);
+        member.wait_for_tick();
+
+        handle.set_speed(Speed::Fps(500)).unwrap();
+        let first = member.tick_info().unwrap().tick_number;
+        let started = Instant::now();
+        for _ in 0..20 {
+            member.wait_for_tick();
+        }
+        // 20 frames at 20 fps would take a second
+        assert!(started.elapsed() < Duration::from_millis(500));
+        assert!(member.tick_info().unwrap().tick_number >= first + 20);
+    }
+
+    #[cfg(all(feature = "shm", unix))]
+    #[test]
+    fn shm_reader_follows_published_frames() {
+        let path = std::env::temp_dir().join(format!("tick-shm-{}", std::process::id()));
+        let publisher = ShmPublisher::create(&path).unwrap();
+        let reader = ShmReader::open(&path).unwrap();
+        assert_eq!(reader.latest(), None);
+
+        let (_manager, handle) = TickManager::builder(Speed::Fps(200))
+            .publish_to_shm(publisher)
+            .build();
+        let frame = reader
+            .wait_for_frame(3, Duration::from_secs(1))
+            .expect("frames are published");
+        assert!(frame.tick_number >= 3);
+        assert_eq!(frame.period, Speed::Fps(200).get_duration());
+        assert!(handle.clock().tick_number() >= frame.tick_number);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn pause_stops_frames_until_resume() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(200));
+        let member = TickMember::new(handle.clone(), 1);
+        member.wait_for_tick();
+
+        handle.pause().unwrap();
+        std::thread::sleep(Duration::from_millis(20));
+        let paused_at = handle.clock().tick_number();
+        let sim_time = handle.clock().sim_time();
+        std::thread::sleep(Duration::from_millis(50));
+        assert_eq!(handle.clock().tick_number(), paused_at);
+        assert!(member.is_registered());
+
+        handle.resume().unwrap();
+        member.wait_for_tick();
+        let info = member.tick_info().unwrap();
+        assert_eq!(info.tick_number, paused_at + 1);
+        // no burst of missed frames and the pause isn't simulated
+        assert!(info.delta < Duration::from_millis(25));
+        assert!(info.sim_time - sim_time < Duration::from_millis(25));
+    }
+
+    /// polls the future on this thread, parking between polls
+    #[cfg(feature = "async")]
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        struct Unparker(std::thread::Thread);
+        impl std::task::Wake for Unparker {
+            fn wake(self: Arc<Self>) {
+                self.0.unpark();
+            }
+        }
+        let waker = std::task::Waker::from(Arc::new(Unparker(std::thread::current())));
+        let mut cx = std::task::Context::from_waker(&waker);
+        let mut future = std::pin::pin!(future);
+        loop {
+            if let std::task::Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+            std::thread::park();
+        }
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn awaited_frames_register_one_waiter() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(200));
+        let clock = handle.clock();
+        let waker = std::task::Waker::noop();
+        let mut cx = std::task::Context::from_waker(waker);
+        {
+            let mut future = std::pin::pin!(clock.wait_for_frame_async(u64::MAX));
+            for _ in 0..5 {
+                assert!(future.as_mut().poll(&mut cx).is_pending());
+            }
+            assert_eq!(clock.waiting(), 1);
+        }
+        // dropping the future unregisters it
+        assert_eq!(clock.waiting(), 0);
+
+        let target = clock.tick_number() + 3;
+        assert!(block_on(clock.wait_for_frame_async(target)) >= target);
+        handle.shutdown_with_timeout(Duration::from_secs(1));
+        assert!(block_on(clock.wait_for_frame_async(u64::MAX)) < u64::MAX);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn members_can_be_awaited() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(200));
+        let awaited = TickMember::new(handle.clone(), 1);
+        let parked = TickMember::builder(handle.clone())
+            .wake(WakeStrategy::Park)
+            .build()
+            .unwrap();
+        let parked = std::thread::spawn(move || {
+            // parks once blocking, then gets its ticks over the channel while awaited
+            parked.wait_for_tick();
+            for _ in 0..5 {
+                block_on(parked.wait_for_tick_async());
+            }
+            parked.tick_info().unwrap().tick_number
+        });
+
+        // keeps ticking until the other member is done, which can only tick along
+        block_on(async {
+            while !parked.is_finished() {
+                awaited.wait_for_tick_async().await;
+            }
+        });
+        assert!(awaited.tick_info().unwrap().tick_number >= 6);
+        assert!(parked.join().unwrap() >= 6);
+    }
+
+    #[test]
+    fn leak_detection_reports_growing_collections() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(500));
+        let events = handle.subscribe_events().unwrap();
+        handle
+            .set_leak_detection(Some(LeakPolicy {
+                sample_every: 1,
+                growth_samples: 3,
+                min_size: 4,
+            }))
+            .unwrap();
+
+        // waiters that are never released pile up
+        let mut waiters = Vec::new();
+        let reported = loop {
+            waiters.push(handle.frame_condition(|_| false).unwrap());
+            handle.sleep_until_next_frame();
+            if let Ok(event) = events.try_recv() {
+                break event.event;
+            }
+            assert!(waiters.len() < 100, "the growth was never reported");
+        };
+        match reported {
+            TickEvent::UnboundedGrowth { collection, size } => {
+                assert_eq!(collection, "frame_conditions");
+                assert!(size >= 4);
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+        let stats = handle.memory_stats().unwrap();
+        assert_eq!(stats.frame_conditions, waiters.len());
+        assert!(stats.total() >= waiters.len());
+    }
+
+    #[test]
+    fn tenant_quota_refuses_registrations_beyond_it() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(200));
+        handle
+            .set_tenant_quota(
+                "plugin",
+                Some(TenantQuota {
+                    max_members: Some(3),
+                    max_rate: Some(1.01),
+                }),
+            )
+            .unwrap();
+        let member = |factor| {
+            TickMember::builder(handle.clone())
+                .tenant("plugin")
+                .speed_factor(factor)
+                .build()
+        };
+
+        let _halves = [member(2).unwrap(), member(2).unwrap()];
+        assert_eq!(
+            member(4).unwrap_err(),
+            TickError::QuotaExceeded {
+                tenant: "plugin".to_string(),
+                quota: QuotaKind::Rate,
+            }
+        );
+        let _slow = member(1000).unwrap();
+        assert!(matches!(
+            member(1000),
+            Err(TickError::QuotaExceeded {
+                quota: QuotaKind::Members,
+                ..
+            })
+        ));
+        // other tenants aren't limited
+        assert!(TickMember::builder(handle.clone()).build().is_ok());
+    }
+
+    #[test]
+    fn wait_for_tick_returns_the_frame() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(200));
+        let member = TickMember::new(handle.clone(), 1);
+        let first = member.wait_for_tick().unwrap();
+        let second = member.wait_for_tick().unwrap();
+        assert!(second.tick_number > first.tick_number);
+        assert!(second.delta > Duration::ZERO);
+        assert!(second.scheduled_at >= first.scheduled_at);
+        assert_eq!(member.tick_info().unwrap().tick_number, second.tick_number);
+
+        let removed = TickMember::new_in_group(handle.clone(), 1, "gone");
+        handle.unregister_group("gone").unwrap();
+        while removed.wait_for_tick().is_some() {}
+        assert!(!removed.is_registered());
+    }
+
+    #[test]
+    fn restricted_handle_stays_in_its_namespace() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(200));
+        handle
+            .set_tenant_quota(
+                "plugin",
+                Some(TenantQuota {
+                    max_members: Some(1),
+                    max_rate: None,
+                }),
+            )
+            .unwrap();
+        let plugin = handle
+            .restricted("plugin", Capabilities::default())
+            .unwrap();
+        assert_eq!(plugin.namespace(), Some(&Namespace::from("plugin")));
+
+        let member = TickMember::builder(plugin.clone())
+            .tenant("other")
+            .build()
+            .unwrap();
+        assert!(member.wait_for_tick().is_some());
+        assert!(matches!(
+            TickMember::builder(plugin.clone()).build(),
+            Err(TickError::QuotaExceeded { .. })
+        ));
+
+        assert!(plugin.send(TickCommand::SetSpeed(Speed::Fps(1))).is_err());
+        assert!(plugin.send(TickCommand::Shutdown).is_err());
+        assert!(
+            plugin
+                .send(TickCommand::SetTenantQuota("plugin".to_string(), None))
+                .is_err()
+        );
+        // the frames keep coming
+        assert!(member.wait_for_tick().is_some());
+
+        // nothing outside the namespace, and nothing that runs on the manager thread
+        let outsider = TickMember::new(handle.clone(), 1);
+        assert!(plugin.send(TickCommand::Run(Box::new(|| {}))).is_err());
+        let (sender, _receiver) = flume::unbounded();
+        assert!(plugin.send(TickCommand::TickNow(sender)).is_err());
+        plugin.send(TickCommand::Unregister(outsider.id)).unwrap();
+        plugin.send(TickCommand::SkipTicks(member.id, 1)).unwrap();
+        assert!(outsider.wait_for_tick().is_some());
+        assert!(outsider.is_registered());
+
+        let speed = handle
+            .restricted(
+                "speed",
+                Capabilities {
+                    control_speed: true,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert!(speed.send(TickCommand::SetSpeed(Speed::Fps(100))).is_ok());
+        // restricting again can't add capabilities or change the namespace
+        let all = Capabilities {
+            control_speed: true,
+            shutdown: true,
+            configure: true,
+        };
+        assert_eq!(
+            speed.restricted("elsewhere", all).unwrap_err(),
+            TickError::OutsideNamespace("speed".to_string())
+        );
+        let nested = speed.restricted("speed", all).unwrap();
+        assert_eq!(nested.namespace(), Some(&Namespace::from("speed")));
+        assert!(nested.send(TickCommand::Shutdown).is_err());
+    }
+
+    #[test]
+    fn catch_up_delivers_missed_frames_back_to_back() {
+        let (_manager, handle) = TickManagerBuilder::new(Speed::Fps(100)).catch_up(3).build();
+        let events = handle.subscribe_events().unwrap();
+        let member = TickMember::new(handle.clone(), 1);
+        let before = member.wait_for_tick().unwrap();
+        // a long frame, about ten periods
+        std::thread::sleep(Duration::from_millis(100));
+
+        let ticks: Vec<TickInfo> = (0..4).map(|_| member.wait_for_tick().unwrap()).collect();
+        // no frame was started without the member
+        for (n, tick) in ticks.iter().enumerate() {
+            assert_eq!(tick.tick_number, before.tick_number + 1 + n as u64);
+            assert_eq!(tick.delta, Duration::from_millis(10));
+        }
+        // the three frames caught up didn't wait for their period
+        let caught_up = ticks[3].scheduled_at - ticks[0].scheduled_at;
+        assert!(ticks[3].wall_time - ticks[0].wall_time < caught_up);
+
+        let dropped = events
+            .try_iter()
+            .find_map(|event| match event.event {
+                TickEvent::FramesDropped { frame, dropped } => Some((frame, dropped)),
+                _ => None,
+            })
+            .expect("frames beyond the catch up were dropped");
+        assert_eq!(dropped.0, before.tick_number + 1);
+        assert!(dropped.1 >= 5, "dropped {}", dropped.1);
+    }
+
+    #[test]
+    fn callback_members_run_boxed_closures() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(200));
+        let events = handle.subscribe_events().unwrap();
+        let count = Arc::new(AtomicUsize::new(0));
+        let callback: TickCallback = {
+            let count = count.clone();
+            Box::new(move |_info| {
+                count.fetch_add(1, Ordering::SeqCst);
+            })
+        };
+        let token = handle.register_callback(MemberConfig::default(), callback);
+        handle.wait_until(|info| info.tick_number >= 10);
+        assert!(count.load(Ordering::SeqCst) > 0);
+        token.unregister();
+        let after = count.load(Ordering::SeqCst);
+        handle.wait_until(|info| info.tick_number >= 15);
+        assert_eq!(count.load(Ordering::SeqCst), after);
+
+        let failing = handle.register_callback(
+            MemberConfig::default(),
+            Box::new(|_info| panic!("script error")),
+        );
+        let id = failing.id();
+        let event = events
+            .iter()
+            .find(|event| matches!(event.event, TickEvent::MemberPanicked { .. }))
+            .unwrap();
+        assert_eq!(event.event.member_id(), Some(id));
+        while !failing.is_finished() {
+            std::thread::yield_now();
+        }
+    }
+
+    #[test]
+    fn try_new_fails_once_the_manager_is_gone() {
+        let (manager, handle) = TickManager::new(Speed::Fps(100));
+        let member = TickMember::try_new(handle.clone(), 1).unwrap();
+        assert_eq!(member.id, 0);
+
+        drop(member);
+        drop(manager);
+        let error = TickMember::try_new(handle, 1).unwrap_err();
+        assert_eq!(error, TickError::ManagerGone);
+        assert_eq!(error.to_string(), "the Tick Manager has shut down");
+    }
+
+    #[cfg(feature = "scripting")]
+    #[test]
+    fn script_errors_are_reported_as_events() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(200));
+        let events = handle.subscribe_events().unwrap();
+        let host = MemberHost::new(handle.clone());
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let id = {
+            let calls = calls.clone();
+            host.register(
+                1,
+                Box::new(move |_info| {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    Err("undefined variable `x`".to_string())
+                }),
+            )
+            .unwrap()
+        };
+        assert_eq!(host.members(), vec![id]);
+        assert!(host.set_factor(id, 2));
+        assert!(!host.set_factor(id + 1, 2));
+
+        let failures: Vec<String> = events
+            .iter()
+            .filter_map(|event| match event.event {
+                TickEvent::MemberFailed {
+                    id: failed,
+                    message,
+                } if failed == id => Some(message),
+                _ => None,
+            })
+            .take(2)
+            .collect();
+        assert_eq!(failures, vec!["undefined variable `x`"; 2]);
+        // the member kept getting ticks after its first error
+        assert!(calls.load(Ordering::SeqCst) >= 2);
+
+        assert!(host.unregister(id));
+        assert!(!host.unregister(id));
+        assert!(host.members().is_empty());
+    }
+
+    #[test]
+    fn frame_limiter_paces_a_loop() {
+        let (manager, handle) = TickManager::new(Speed::Fps(100));
+        let mut limiter = FrameLimiter::new(&handle);
+        let first = limiter.wait().unwrap();
+        let second = limiter.wait().unwrap();
+        assert!(second.tick_number > first.tick_number);
+        assert!(second.delta >= Duration::from_millis(5));
+
+        std::thread::sleep(Duration::from_millis(50));
+        let late = limiter.wait().unwrap();
+        assert!(late.skipped_frames());
+        assert_eq!(late.skipped, late.tick_number - second.tick_number - 1);
+
+        drop(manager);
+        assert_eq!(limiter.wait(), None);
+    }
+
+    #[test]
+    fn bounded_and_non_blocking_waits() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(20));
+        let member = TickMember::new(handle.clone(), 1);
+        let first = member.wait_for_tick().unwrap();
+
+        // the next frame is about 50ms away
+        assert!(member.try_wait_for_tick().is_none());
+        assert_eq!(
+            member
+                .wait_for_tick_timeout(Duration::from_millis(1))
+                .unwrap_err(),
+            TickError::Timeout
+        );
+        let next = member
+            .wait_for_tick_timeout(Duration::from_secs(1))
+            .unwrap();
+        assert!(next.tick_number > first.tick_number);
+
+        handle.send(TickCommand::Unregister(member.id)).unwrap();
+        assert_eq!(
+            member
+                .wait_for_tick_timeout(Duration::from_secs(1))
+                .unwrap_err(),
+            TickError::Unregistered
+        );
+        assert!(member.try_wait_for_tick().is_none());
+    }
+
+    #[test]
+    fn speed_log_reconstructs_sim_time() {
+        let (_manager, handle) = TickManagerBuilder::new(Speed::Fps(100))
+            .fixed_point_time(true)
+            .build();
+        let member = TickMember::new(handle.clone(), 1);
+        let mut ticks: Vec<TickInfo> = (0..5).map(|_| member.wait_for_tick().unwrap()).collect();
+        handle.set_speed(Speed::Fps(300)).unwrap();
+        ticks.extend((0..10).map(|_| member.wait_for_tick().unwrap()));
+
+        let log = handle.speed_log().unwrap();
+        assert_eq!(log.changes().len(), 2);
+        let change = &log.changes()[1];
+        assert_eq!(change.speed, Speed::Fps(300));
+        assert!(change.from_frame > ticks[4].tick_number);
+        assert_eq!(log.speed_at(1), Some(&Speed::Fps(100)));
+        for tick in &ticks {
+            assert_eq!(log.sim_time_at(tick.tick_number), Some(tick.sim_time));
+        }
+    }
+
+    #[test]
+    fn manual_speed_single_steps() {
+        let (_manager, handle) = TickManager::new(Speed::Manual(Duration::from_millis(10)));
+        let member = TickMember::new(handle.clone(), 1);
+        let ticked = {
+            let member = Arc::new(member);
+            let worker = member.clone();
+            let ticked = Arc::new(AtomicUsize::new(0));
+            let count = ticked.clone();
+            std::thread::spawn(move || {
+                while worker.wait_for_tick().is_some() {
+                    count.fetch_add(1, Ordering::SeqCst);
+                }
+            });
+            ticked
+        };
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert_eq!(handle.clock().tick_number(), 0);
+
+        for step in 1..=5u64 {
+            let info = handle.tick_now().unwrap();
+            assert_eq!(info.tick_number, step);
+            assert_eq!(info.sim_time, Duration::from_millis(10 * step));
+            assert_eq!(info.delta, Duration::from_millis(10));
+        }
+        // the frames wait for the member, so it finished every one of them before the next
+        handle.tick_now().unwrap();
+        assert!((5..=6).contains(&ticked.load(Ordering::SeqCst)));
+        std::thread::sleep(Duration::from_millis(30));
+        assert_eq!(handle.clock().tick_number(), 6);
+    }
+
+    #[test]
+    fn mock_clock_drives_the_frames() {
+        let time = MockClock::new();
+        let (_manager, handle) = TickManager::new_with_clock(Speed::Fps(10), time.clone());
+        let clock = handle.clock();
+
+        // a frame is 100ms away in mock time, however long the test really waits
+        assert_eq!(
+            clock.wait_for_frame_timeout(1, Duration::from_millis(30)),
+            None
+        );
+        time.advance(Duration::from_millis(100));
+        assert_eq!(
+            clock.wait_for_frame_timeout(1, Duration::from_secs(1)),
+            Some(1)
+        );
+        assert_eq!(clock.sim_time(), Duration::from_millis(100));
+
+        time.advance(Duration::from_millis(50));
+        assert_eq!(
+            clock.wait_for_frame_timeout(2, Duration::from_millis(30)),
+            None
+        );
+        time.advance(Duration::from_millis(50));
+        assert_eq!(
+            clock.wait_for_frame_timeout(2, Duration::from_secs(1)),
+            Some(2)
+        );
+        assert_eq!(clock.sim_time(), Duration::from_millis(200));
+    }
+
+    #[test]
+    fn delta_is_simulated_and_raw_delta_measured() {
+        let time = MockClock::new();
+        let (_manager, handle) = TickManagerBuilder::new(Speed::Fps(10))
+            .fixed_point_time(true)
+            .clock(time.clone())
+            .build();
+        let clock = handle.clock();
+
+        // the first frame starts late
+        time.advance(Duration::from_millis(150));
+        assert_eq!(
+            clock.wait_for_frame_timeout(1, Duration::from_secs(1)),
+            Some(1)
+        );
+        assert_eq!(clock.delta(), Duration::from_millis(100));
+        assert_eq!(clock.raw_delta(), Duration::from_millis(150));
+        assert_eq!(clock.sim_time(), Duration::from_millis(100));
+
+        time.advance(Duration::from_millis(50));
+        assert_eq!(
+            clock.wait_for_frame_timeout(2, Duration::from_secs(1)),
+            Some(2)
+        );
+        assert_eq!(clock.delta(), Duration::from_millis(100));
+        assert_eq!(clock.raw_delta(), Duration::from_millis(50));
+    }
+
+    #[test]
+    fn member_context_is_handed_back() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(100));
+        let member = TickMember::builder(handle)
+            .context(Arc::new(String::from("player")))
+            .build()
+            .unwrap();
+
+        assert!(member.wait_for_tick().is_some());
+        assert_eq!(
+            member.context::<String>().as_deref().map(String::as_str),
+            Some("player")
+        );
+        assert!(member.context::<u32>().is_none());
+    }
+
+    #[test]
+    fn frames_with_only_hidden_members_are_skipped() {
+        let time = MockClock::new();
+        let (_manager, handle) = TickManagerBuilder::new(Speed::Fps(10))
+            .clock(time.clone())
+            .when_all_hidden(HiddenPolicy::Skip)
+            .build();
+        let clock = handle.clock();
+        let member = TickMember::builder(handle.clone()).build().unwrap();
+
+        time.advance(Duration::from_millis(100));
+        assert_eq!(member.wait_for_tick().unwrap().tick_number, 1);
+        member.set_state(MemberState::Hidden);
+        for _ in 0..3 {
+            time.advance(Duration::from_millis(100));
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(clock.tick_number(), 1);
+
+        member.set_state(MemberState::Finished);
+        time.advance(Duration::from_millis(100));
+        assert_eq!(member.wait_for_tick().unwrap().tick_number, 2);
+    }
+
+    #[test]
+    fn idle_and_active_events_follow_hidden_members() {
+        let (_manager, handle) = TickManagerBuilder::new(Speed::Fps(500))
+            .when_all_hidden(HiddenPolicy::Skip)
+            .build();
+        let events = handle.subscribe_events().unwrap();
+        handle
+            .set_idle_policy(Some(IdlePolicy {
+                idle_after: 5,
+                active_after: 2,
+            }))
+            .unwrap();
+        let member = TickMember::new(handle.clone(), 1);
+        assert!(member.wait_for_tick().is_some());
+
+        let is_change =
+            |event: &TickEvent| matches!(event, TickEvent::Idle { .. } | TickEvent::Active { .. });
+        member.set_state(MemberState::Hidden);
+        let event = loop {
+            let event = events.recv_timeout(Duration::from_secs(1)).unwrap().event;
+            if is_change(&event) {
+                break event;
+            }
+        };
+        assert!(matches!(event, TickEvent::Idle { .. }));
+
+        let active = loop {
+            assert!(member.wait_for_tick().is_some());
+            if let Some(event) = events.try_iter().map(|e| e.event).find(is_change) {
+                break event;
+            }
+        };
+        assert!(matches!(active, TickEvent::Active { .. }));
+    }
+
+    #[test]
+    fn member_moves_to_another_phase() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(500));
+        let member = TickMember::new(handle.clone(), 4);
+        member.set_offset(2);
+
+        for _ in 0..3 {
+            assert_eq!(member.wait_for_tick().unwrap().tick_number % 4, 2);
+        }
+    }
+
+    #[test]
+    fn groups_only_wait_for_their_own_members() {
+        let (_manager, handle) = TickManagerBuilder::new(Speed::Fps(500))
+            .group_barriers(true)
+            .build();
+        // gets its first tick and never finishes it
+        let _audio = TickMember::builder(handle.clone())
+            .group("audio")
+            .build()
+            .unwrap();
+        let physics = TickMember::builder(handle.clone())
+            .group("physics")
+            .build()
+            .unwrap();
+
+        let mut last = 0;
+        for _ in 0..5 {
+            let tick = physics
+                .wait_for_tick_timeout(Duration::from_secs(1))
+                .unwrap()
+                .tick_number;
+            assert!(tick > last);
+            last = tick;
+        }
+    }
+
+    #[cfg(all(
+        feature = "monotonic-raw",
+        any(target_os = "linux", target_os = "android")
+    ))]
+    #[test]
+    fn manager_paces_on_the_raw_monotonic_clock() {
+        let time = RawMonotonicClock::new();
+        let before = time.now();
+        time.sleep_until(before + Duration::from_millis(5));
+        assert!(time.now() >= before + Duration::from_millis(5));
+
+        let (_manager, handle) = TickManagerBuilder::new(Speed::Fps(200)).clock(time).build();
+        let member = TickMember::new(handle, 1);
+        let first = member.wait_for_tick().unwrap().tick_number;
+        assert!(member.wait_for_tick().unwrap().tick_number > first);
+    }
+
+    #[test]
+    fn higher_priorities_are_dispatched_first() {
+        let order = OrderedDispatch::new();
+        let (sender, _receiver) = flume::unbounded();
+        let info = |priority| MemberInfo {
+            sender: sender.clone(),
+            state: MemberState::Finished,
+            last_tick: Instant::now(),
+            name: None,
+            group: None,
+            tenant: None,
+            offset: 0,
+            priority,
+            after: Vec::new(),
+            phase: 0,
+            kind: MemberKind::Participant,
+            spread_work: false,
+            leased: false,
+            budget: None,
+            state_cell: Arc::new(StateCell::new(MemberState::Finished)),
+            parked: None,
+        };
+        let mut members = [(0, info(0)), (1, info(10)), (2, info(-1)), (3, info(10))];
+        members.sort_by_key(|(id, member_info)| order.key(*id, member_info));
+        let ids: Vec<MemberID> = members.iter().map(|(id, _)| *id).collect();
+        assert_eq!(ids, vec![1, 3, 0, 2]);
+
+        let (_manager, handle) = TickManager::new(Speed::Fps(200));
+        let member = TickMember::builder(handle).priority(10).build().unwrap();
+        assert!(member.wait_for_tick().is_some());
+    }
+
+    #[test]
+    fn calibration_measures_the_frame_rate() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(100));
+        let report = handle.calibrate(Duration::from_millis(300));
+
+        assert!(report.frames >= 10, "{:?}", report);
+        assert!((report.target_rate - 100.0).abs() < 0.01);
+        assert!(
+            (50.0..150.0).contains(&report.achieved_rate),
+            "{:?}",
+            report
+        );
+        assert!(report.recommended_spin >= report.timer_granularity);
+        assert!(report.lateness.max >= report.lateness.p50);
+    }
+
+    #[test]
+    fn observers_get_ticks_while_a_participant_holds_the_frame() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(500));
+        // gets its first tick and never finishes it
+        let _blocker = TickMember::new(handle.clone(), 1);
+        let observer = TickMember::builder(handle.clone())
+            .role::<Observer>()
+            .build()
+            .unwrap();
+
+        let mut last = 0;
+        for _ in 0..5 {
+            let tick = observer
+                .wait_for_tick_timeout(Duration::from_secs(1))
+                .unwrap()
+                .tick_number;
+            assert!(tick > last);
+            last = tick;
+        }
+    }
+
+    #[test]
+    fn scheduler_polls_frames_without_a_thread() {
+        let start = Instant::now();
+        let mut scheduler = TickScheduler::new(Speed::Fps(10), start);
+        let every_frame = scheduler.add_member(1, 0);
+        let every_other = scheduler.add_member(2, 0);
+
+        let early = scheduler.poll(start + Duration::from_millis(50));
+        assert_eq!(early.frame, None);
+        assert_eq!(
+            early.next_deadline,
+            Some(start + Duration::from_millis(100))
+        );
+
+        let first = scheduler.poll(start + Duration::from_millis(100));
+        assert_eq!(first.frame, Some(1));
+        assert_eq!(first.due_members, vec![every_frame]);
+        assert_eq!(
+            first.next_deadline,
+            Some(start + Duration::from_millis(200))
+        );
+
+        let second = scheduler.poll(start + Duration::from_millis(200));
+        assert_eq!(second.due_members, vec![every_frame, every_other]);
+
+        scheduler.set_speed(Speed::Manual(Duration::from_millis(10)), start);
+        let manual = scheduler.poll(start);
+        assert_eq!(manual.frame, Some(3));
+        assert_eq!(manual.next_deadline, None);
+    }
+
+    #[test]
+    fn free_run_lets_a_slow_member_miss_ticks() {
+        let (_manager, handle) = TickManagerBuilder::new(Speed::Fps(500))
+            .free_run(true)
+            .build();
+        // gets its first tick and never finishes it
+        let _laggard = TickMember::new(handle.clone(), 1);
+        let renderer = TickMember::new(handle.clone(), 1);
+
+        let mut last = 0;
+        for _ in 0..5 {
+            let tick = renderer
+                .wait_for_tick_timeout(Duration::from_secs(1))
+                .unwrap()
+                .tick_number;
+            assert!(tick > last);
+            last = tick;
+        }
+    }
+
+    #[test]
+    fn scheduler_hands_a_reactor_its_timeouts() {
+        let start = Instant::now();
+        let mut scheduler = TickScheduler::new(Speed::Fps(10), start);
+        let member = scheduler.add_member(1, 0);
+        assert_eq!(
+            scheduler.timeout(start + Duration::from_millis(30)),
+            Some(Duration::from_millis(70))
+        );
+
+        // a socket event woke the reactor before the frame was due
+        let mut frames = Vec::new();
+        let timeout = scheduler.dispatch(start + Duration::from_millis(60), |frame, due| {
+            frames.push((frame, due.to_vec()))
+        });
+        assert!(frames.is_empty());
+        assert_eq!(timeout, Some(Duration::from_millis(40)));
+
+        let timeout = scheduler.dispatch(start + Duration::from_millis(100), |frame, due| {
+            frames.push((frame, due.to_vec()))
+        });
+        assert_eq!(frames, vec![(1, vec![member])]);
+        assert_eq!(timeout, Some(Duration::from_millis(100)));
+    }
+
+    #[test]
+    fn frame_fence_limits_how_far_frames_run_ahead() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(500));
+        let clock = handle.clock();
+        handle.pause().unwrap();
+        let fence = handle.frame_fence(2);
+        // the fence is set once a query sent after it returns
+        handle.speed_log().unwrap();
+        let start = clock.tick_number();
+        handle.resume().unwrap();
+
+        std::thread::sleep(Duration::from_millis(50));
+        assert_eq!(clock.tick_number(), start + 2);
+
+        fence.signal(start + 1);
+        assert_eq!(
+            clock.wait_for_frame_timeout(start + 3, Duration::from_secs(1)),
+            Some(start + 3)
+        );
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(clock.tick_number(), start + 3);
+
+        drop(fence);
+        assert!(
+            clock
+                .wait_for_frame_timeout(start + 6, Duration::from_secs(1))
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn lockstep_waits_for_every_acknowledgement() {
+        let (_manager, handle) = TickManagerBuilder::new(Speed::Fps(1000))
+            .lockstep(true)
+            .build();
+        let clock = handle.clock();
+        let (sender, ticks) = flume::unbounded();
+        let fast = TickMember::new(handle.clone(), 1);
+        let slow = TickMember::new(handle.clone(), 3);
+        std::thread::spawn(move || {
+            while let Some(info) = fast.wait_for_tick() {
+                if sender.send(info.tick_number).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let tick = slow.wait_for_tick().unwrap().tick_number;
+        std::thread::sleep(Duration::from_millis(30));
+        // the fast member keeps acknowledging, but the slow one holds the frames
+        assert_eq!(clock.tick_number(), tick);
+
+        slow.ack();
+        let next = ticks.iter().find(|&t| t > tick).unwrap();
+        assert_eq!(next, tick + 1);
+        handle.send(TickCommand::Shutdown).unwrap();
+    }
+
+    #[test]
+    fn dependent_member_is_ticked_after_its_dependency() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(500));
+        let physics = TickMember::new(handle.clone(), 1);
+        let render = TickMember::builder(handle.clone())
+            .after(physics.id)
+            .build()
+            .unwrap();
+
+        // ready for its first tick without waiting for it yet
+        render.ack();
+        let tick = physics.wait_for_tick().unwrap().tick_number;
+        assert!(
+            render
+                .wait_for_tick_timeout(Duration::from_millis(30))
+                .is_err()
+        );
+
+        physics.ack();
+        let rendered = render
+            .wait_for_tick_timeout(Duration::from_secs(1))
+            .unwrap();
+        assert_eq!(rendered.tick_number, tick);
+    }
+
+    #[test]
+    fn pipelined_frames_queue_ticks_up_to_the_depth() {
+        let (_manager, handle) = TickManagerBuilder::new(Speed::Fps(1000))
+            .pipeline_depth(3)
+            .build();
+        let clock = handle.clock();
+        let member = TickMember::new(handle.clone(), 1);
+
+        let tick = member.wait_for_tick().unwrap().tick_number;
+        std::thread::sleep(Duration::from_millis(30));
+        // two more ticks are queued behind the one still processing, then the frames wait
+        assert_eq!(clock.tick_number(), tick + 2);
+
+        let start = Instant::now();
+        assert_eq!(member.wait_for_tick().unwrap().tick_number, tick + 1);
+        assert_eq!(member.wait_for_tick().unwrap().tick_number, tick + 2);
+        assert!(start.elapsed() < Duration::from_millis(20));
+        handle.send(TickCommand::Shutdown).unwrap();
+    }
+
+    #[test]
+    fn later_phases_are_ticked_after_earlier_ones_finished() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(500));
+        let input = TickMember::new(handle.clone(), 1);
+        let update = TickMember::builder(handle.clone())
+            .phase(1)
+            .build()
+            .unwrap();
+        let render = TickMember::builder(handle.clone())
+            .phase(2)
+            .build()
+            .unwrap();
+
+        update.ack();
+        render.ack();
+        let tick = input.wait_for_tick().unwrap().tick_number;
+        assert!(
+            update
+                .wait_for_tick_timeout(Duration::from_millis(30))
+                .is_err()
+        );
+
+        input.ack();
+        let updated = update
+            .wait_for_tick_timeout(Duration::from_secs(1))
+            .unwrap();
+        assert_eq!(updated.tick_number, tick);
+        // the update is still running
+        assert!(
+            render
+                .wait_for_tick_timeout(Duration::from_millis(30))
+                .is_err()
+        );
+
+        update.ack();
+        let rendered = render
+            .wait_for_tick_timeout(Duration::from_secs(1))
+            .unwrap();
+        assert_eq!(rendered.tick_number, tick);
+    }
+
+    #[test]
+    fn pipelined_frames_queue_in_the_wake_slot_of_a_parking_member() {
+        let (_manager, handle) = TickManagerBuilder::new(Speed::Fps(1000))
+            .pipeline_depth(3)
+            .build();
+        let clock = handle.clock();
+        let member = TickMember::builder(handle.clone())
+            .wake(WakeStrategy::Park)
+            .build()
+            .unwrap();
+
+        let tick = member.wait_for_tick().unwrap().tick_number;
+        std::thread::sleep(Duration::from_millis(30));
+        assert_eq!(clock.tick_number(), tick + 2);
+
+        // none of the queued ticks is lost, so the pipeline keeps going
+        for expected in tick + 1..tick + 10 {
+            assert_eq!(member.wait_for_tick().unwrap().tick_number, expected);
+        }
+        handle.send(TickCommand::Shutdown).unwrap();
+    }
+
+    #[test]
+    fn staged_members_hold_the_next_frame_without_a_barrier_mode() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(1000));
+        let clock = handle.clock();
+        let physics = TickMember::new(handle.clone(), 1);
+        let render = TickMember::builder(handle.clone())
+            .after(physics.id)
+            .build()
+            .unwrap();
+
+        render.ack();
+        let tick = physics.wait_for_tick().unwrap().tick_number;
+        std::thread::sleep(Duration::from_millis(30));
+        // the staged tick of render comes first, the frames don't move past it
+        assert_eq!(clock.tick_number(), tick);
+
+        physics.ack();
+        let rendered = render
+            .wait_for_tick_timeout(Duration::from_secs(1))
+            .unwrap();
+        assert_eq!(rendered.tick_number, tick);
+    }
+
+    #[test]
+    fn phases_only_wait_for_earlier_members_of_the_same_frame() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(500));
+        // still on the tick of an earlier frame, and not due in the ones in between
+        let slow = TickMember::builder(handle.clone())
+            .speed_factor(2)
+            .build()
+            .unwrap();
+        slow.wait_for_tick().unwrap();
+        let input = TickMember::new(handle.clone(), 1);
+        let update = TickMember::builder(handle.clone())
+            .phase(1)
+            .build()
+            .unwrap();
+
+        update.ack();
+        let tick = input.wait_for_tick().unwrap().tick_number;
+        assert!(
+            update
+                .wait_for_tick_timeout(Duration::from_millis(30))
+                .is_err()
+        );
+
+        input.ack();
+        let updated = update
+            .wait_for_tick_timeout(Duration::from_secs(1))
+            .unwrap();
+        assert_eq!(updated.tick_number, tick);
+    }
+
+    #[test]
+    fn callbacks_get_the_context_with_every_tick() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(200));
+        let counter = Arc::new(AtomicUsize::new(0));
+        let config = MemberConfig {
+            context: Some(counter.clone()),
+            ..MemberConfig::default()
+        };
+        let token = handle.register_with_context(
+            config,
+            Box::new(|_info, context| {
+                let counter = context
+                    .and_then(|context| context.downcast_ref::<AtomicUsize>())
+                    .ok_or("no context")?;
+                counter.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            }),
+        );
+        handle.wait_until(|info| info.tick_number >= 10);
+        token.unregister();
+        assert!(counter.load(Ordering::SeqCst) > 0);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn frame_drivers_wake_awaiting_tasks_without_a_tick_manager() {
+        let mut driver = FrameDriver::new(Speed::Fps(200));
+        let clock = driver.clock().clone();
+        let waiting = std::thread::spawn(move || block_on(clock.wait_for_frame_async(3)));
+        assert!(driver.frame(Instant::now()).is_none());
+        while driver.clock().tick_number() < 3 {
+            let due = driver.next_due().unwrap();
+            std::thread::sleep(due.saturating_duration_since(Instant::now()));
+            driver.frame(Instant::now());
+        }
+        assert_eq!(waiting.join().unwrap(), 3);
+
+        driver.set_speed(Speed::Manual(Duration::from_millis(10)));
+        assert!(driver.next_due().is_none());
+        let sim_time = driver.clock().sim_time();
+        let info = driver.frame(Instant::now()).unwrap();
+        assert_eq!(info.tick_number, 4);
+        assert_eq!(info.sim_time - sim_time, Duration::from_millis(10));
+
+        // dropping the driver closes the clock
+        let clock = driver.clock().clone();
+        drop(driver);
+        assert_eq!(block_on(clock.wait_for_frame_async(u64::MAX)), 4);
+    }
+
+    #[cfg(feature = "futex")]
+    #[test]
+    fn the_barrier_wakes_on_a_state_set_instead_of_polling() {
+        let barrier = Arc::new(FrameBarrier::default());
+        let cell = Arc::new(StateCell::new(MemberState::Running).watched_by(barrier.clone()));
+
+        // a stale sequence returns right away
+        let seen = barrier.sequence();
+        cell.set(MemberState::Finished);
+        let start = Instant::now();
+        barrier.wait(seen, Duration::from_secs(5));
+        assert!(start.elapsed() < Duration::from_secs(1));
+
+        let seen = barrier.sequence();
+        let setter = {
+            let cell = cell.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(Duration::from_millis(20));
+                cell.set(MemberState::Running);
+            })
+        };
+        let start = Instant::now();
+        barrier.wait(seen, Duration::from_secs(5));
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert_ne!(barrier.sequence(), seen);
+        setter.join().unwrap();
+
+        // and the frames keep waiting for the member on it
+        let (_manager, handle) = TickManager::new(Speed::Fps(500));
+        let member = TickMember::new(handle.clone(), 1);
+        for _ in 0..10 {
+            assert!(member.wait_for_tick().is_some());
+        }
+    }
+
+    #[test]
+    fn schedulers_run_on_a_reactor_between_its_events() {
+        /// sleeps out the timeout and reports one event per wait, interrupted once
+        struct SleepingReactor {
+            waits: u32,
+            timeouts: Vec<Option<Duration>>,
+        }
+        impl Reactor for SleepingReactor {
+            type Events = Vec<u32>;
+
+            fn wait(
+                &mut self,
+                events: &mut Vec<u32>,
+                timeout: Option<Duration>,
+            ) -> std::io::Result<()> {
+                self.waits += 1;
+                self.timeouts.push(timeout);
+                if self.waits == 2 {
+                    return Err(std::io::ErrorKind::Interrupted.into());
+                }
+                std::thread::sleep(timeout.unwrap_or_default());
+                events.clear();
+                events.push(self.waits);
+                Ok(())
+            }
+        }
+
+        let mut scheduler = TickScheduler::new(Speed::Fps(200), Instant::now());
+        let member = scheduler.add_member(1, 0);
+        let mut reactor = SleepingReactor {
+            waits: 0,
+            timeouts: Vec::new(),
+        };
+        let mut seen = Vec::new();
+        let mut frames = Vec::new();
+        scheduler
+            .run_on(
+                &mut reactor,
+                &mut Vec::new(),
+                |events| {
+                    seen.append(events);
+                    if seen.len() < 5 {
+                        std::ops::ControlFlow::Continue(())
+                    } else {
+                        std::ops::ControlFlow::Break(())
+                    }
+                },
+                |frame, due| frames.push((frame, due.to_vec())),
+            )
+            .unwrap();
+        assert_eq!(seen, vec![1, 3, 4, 5, 6]);
+        assert!(reactor.timeouts.iter().all(|timeout| timeout.is_some()));
+        // every wait slept until the next frame, which started before the next wait
+        assert!(frames.len() >= 4);
+        assert!(frames.iter().all(|(_frame, due)| due == &vec![member]));
+    }
+
+    #[test]
+    fn rescheduling_too_far_out_keeps_wall_items_and_saturates_sim_items() {
+        assert_eq!(Speed::Interval(Duration::MAX).duration_of(2), Duration::MAX);
+        assert_eq!(Speed::Fps(1).duration_of(3), Duration::from_secs(3));
+
+        let (_manager, handle) = TickManager::new(Speed::Fps(1));
+        let (wall, wall_fired) = handle.after(Duration::from_millis(20), ClockDomain::Wall);
+        let (sim, sim_fired) = handle.after(Duration::from_millis(20), ClockDomain::Sim);
+        assert!(wall.reschedule(u64::MAX));
+        assert!(sim.reschedule(u64::MAX));
+        // the wall item fires at its old time, the manager is still running
+        assert!(wall_fired.recv_timeout(Duration::from_secs(1)).is_ok());
+        assert!(sim_fired.try_recv().is_err());
+        assert!(handle.speed_log().is_some());
+    }
+
+    #[test]
+    fn skipping_all_ticks_saturates_instead_of_overflowing() {
+        let (_manager, handle) = TickManager::new(Speed::Fps(200));
+        let skipping = TickMember::new(handle.clone(), 3);
+        let member = TickMember::new(handle.clone(), 1);
+        handle
+            .send(TickCommand::SkipTicks(skipping.id, u64::MAX))
+            .unwrap();
+        for _ in 0..3 {
+            assert!(member.wait_for_tick_timeout(Duration::from_secs(1)).is_ok());
+        }
+        assert!(handle.speed_log().is_some());
+    }
 }
@@ -0,0 +1,101 @@
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{LatencyStats, TickCommand, TickManagerHandle};
+
+/// the sleep the timer granularity is measured with
+const PROBE_SLEEP: Duration = Duration::from_micros(50);
+const PROBES: usize = 50;
+
+/// what TickManagerHandle::calibrate measured on this machine
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalibrationReport {
+    /// main frames observed during the run
+    pub frames: u64,
+    /// frames per second the speed asks for
+    pub target_rate: f64,
+    /// frames per second that actually started
+    pub achieved_rate: f64,
+    /// how long after being due a frame was seen by an observer
+    pub lateness: LatencyStats,
+    /// how far the time between two frames was off the frame period
+    pub jitter: LatencyStats,
+    /// how much a short sleep overshoots, the finest pacing the OS timer allows
+    pub timer_granularity: Duration,
+    /// how long before a deadline a pacing loop should spin instead of sleeping
+    /// to start on time, the worst overshoot of a short sleep
+    pub recommended_spin: Duration,
+    /// the timer is too coarse for the rate and frames will start late, consider
+    /// TickManagerBuilder::catch_up or fixed_point_time to keep the simulated time exact
+    pub coarse: bool,
+}
+
+impl TickManagerHandle {
+    /// observes the main frames for `duration` and probes the OS timer, blocks until done.
+    /// the frame measurements are zero if the Tick Manager shut down
+    pub fn calibrate(&self, duration: Duration) -> CalibrationReport {
+        let mut overshoots = probe_timer();
+        overshoots.sort();
+        let timer_granularity = overshoots[overshoots.len() / 2];
+        let recommended_spin = overshoots[overshoots.len() - 1];
+
+        let period = self
+            .speed_log()
+            .and_then(|log| {
+                log.changes()
+                    .last()
+                    .map(|change| change.speed.get_duration())
+            })
+            .unwrap_or_default();
+        let (sender, receiver) = flume::bounded(64);
+        let _ = self.send(TickCommand::Observe(sender));
+
+        let deadline = Instant::now() + duration;
+        let mut lateness = Vec::new();
+        let mut jitter = Vec::new();
+        let mut first: Option<Duration> = None;
+        let mut last = Duration::ZERO;
+        while let Ok(info) = receiver.recv_deadline(deadline) {
+            lateness.push(Instant::now().saturating_duration_since(info.scheduled_at));
+            // the first frame's delta reaches back to before the run
+            if first.is_some() {
+                jitter.push(info.raw_delta.abs_diff(period));
+            }
+            first.get_or_insert(info.wall_time);
+            last = info.wall_time;
+        }
+        lateness.sort();
+        jitter.sort();
+
+        let frames = lateness.len() as u64;
+        let measured = last.saturating_sub(first.unwrap_or_default());
+        let rate = |period: Duration| 1.0 / period.as_secs_f64().max(f64::EPSILON);
+        CalibrationReport {
+            frames,
+            target_rate: if period.is_zero() { 0.0 } else { rate(period) },
+            achieved_rate: if frames > 1 {
+                rate(measured / (frames - 1) as u32)
+            } else {
+                0.0
+            },
+            lateness: LatencyStats::new(&lateness),
+            jitter: LatencyStats::new(&jitter),
+            timer_granularity,
+            recommended_spin,
+            coarse: timer_granularity > period / 10,
+        }
+    }
+}
+
+/// how much each of a row of short sleeps overshot
+fn probe_timer() -> Vec<Duration> {
+    (0..PROBES)
+        .map(|_| {
+            let started = Instant::now();
+            thread::sleep(PROBE_SLEEP);
+            started.elapsed().saturating_sub(PROBE_SLEEP)
+        })
+        .collect()
+}
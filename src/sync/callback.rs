@@ -0,0 +1,158 @@
+use std::{
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    thread::{self, JoinHandle},
+};
+
+use crate::{
+    MemberConfig, MemberContext, MemberID, TickCommand, TickError, TickInfo, TickManagerHandle,
+    TickMember, panic_message,
+};
+
+/// a tick handler created at runtime, e.g. by a Lua or Rhai scripting layer
+pub type TickCallback = Box<dyn FnMut(TickInfo) + Send>;
+
+/// a tick handler that can fail, the error is reported as a message
+pub type FallibleTickCallback = Box<dyn FnMut(TickInfo) -> Result<(), String> + Send>;
+
+/// a fallible tick handler that gets the context of its member with every tick,
+/// None if it was registered without one, see MemberConfig::context
+pub type ContextTickCallback =
+    Box<dyn FnMut(TickInfo, Option<&MemberContext>) -> Result<(), String> + Send>;
+
+/// a member registered with TickManagerHandle::register_callback or register_fallible.
+/// unregistering it, or dropping it, removes the member and waits for its thread
+pub struct MemberToken {
+    id: MemberID,
+    handle: TickManagerHandle,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl MemberToken {
+    pub fn id(&self) -> MemberID {
+        self.id
+    }
+
+    /// whether the member is gone, because it was removed or its callback panicked
+    pub fn is_finished(&self) -> bool {
+        self.worker.as_ref().is_none_or(JoinHandle::is_finished)
+    }
+
+    /// removes the member and blocks until its callback returned for the last time
+    pub fn unregister(mut self) {
+        self.stop();
+    }
+
+    fn stop(&mut self) {
+        let Some(worker) = self.worker.take() else {
+            return;
+        };
+        let _ = self.handle.send(TickCommand::Unregister(self.id));
+        // the callback is run from the worker, a token dropped inside it can't wait for itself
+        if worker.thread().id() != thread::current().id() {
+            let _ = worker.join();
+        }
+    }
+}
+
+impl fmt::Debug for MemberToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemberToken").field("id", &self.id).finish()
+    }
+}
+
+impl Drop for MemberToken {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+impl TickManagerHandle {
+    /// registers a member that calls `callback` with every tick on its own thread.
+    /// takes the callback boxed, so closures created at runtime can be registered without
+    /// generics. a panicking callback is reported as TickEvent::MemberPanicked and the member
+    /// is unregistered
+    pub fn register_callback(
+        &self,
+        config: MemberConfig,
+        mut callback: TickCallback,
+    ) -> MemberToken {
+        self.register_fallible(
+            config,
+            Box::new(move |info| {
+                callback(info);
+                Ok(())
+            }),
+        )
+    }
+
+    /// like register_callback, but every error the callback returns is reported as
+    /// TickEvent::MemberFailed and the member keeps getting its ticks
+    pub fn register_fallible(
+        &self,
+        config: MemberConfig,
+        callback: FallibleTickCallback,
+    ) -> MemberToken {
+        self.try_register_fallible(config, callback)
+            .unwrap_or_else(|e| panic!("Could not register the member: {}", e))
+    }
+
+    /// like register_fallible, but returns an error instead of panicking
+    pub fn try_register_fallible(
+        &self,
+        config: MemberConfig,
+        mut callback: FallibleTickCallback,
+    ) -> Result<MemberToken, TickError> {
+        self.try_register_with_context(config, Box::new(move |info, _context| callback(info)))
+    }
+
+    /// like register_fallible, but the callback gets MemberConfig::context with every tick,
+    /// so closures created at runtime or FFI callbacks reach their state without globals
+    pub fn register_with_context(
+        &self,
+        config: MemberConfig,
+        callback: ContextTickCallback,
+    ) -> MemberToken {
+        self.try_register_with_context(config, callback)
+            .unwrap_or_else(|e| panic!("Could not register the member: {}", e))
+    }
+
+    /// like register_with_context, but returns an error instead of panicking
+    pub fn try_register_with_context(
+        &self,
+        config: MemberConfig,
+        callback: ContextTickCallback,
+    ) -> Result<MemberToken, TickError> {
+        let context = config.context.clone();
+        let member = TickMember::try_with_config(self.clone(), config)?;
+        let id = member.id;
+        let handle = self.clone();
+        let worker = thread::spawn(move || run_callback(&handle, member, context, callback));
+        Ok(MemberToken {
+            id,
+            handle: self.clone(),
+            worker: Some(worker),
+        })
+    }
+}
+
+fn run_callback(
+    handle: &TickManagerHandle,
+    member: TickMember,
+    context: Option<MemberContext>,
+    mut callback: ContextTickCallback,
+) {
+    while let Some(info) = member.wait_for_tick() {
+        match panic::catch_unwind(AssertUnwindSafe(|| callback(info, context.as_ref()))) {
+            Ok(Ok(())) => {}
+            Ok(Err(message)) => {
+                let _ = handle.send(TickCommand::ReportError(member.id, message));
+            }
+            Err(payload) => {
+                let message = panic_message(payload.as_ref());
+                let _ = handle.send(TickCommand::ReportPanic(member.id, message, true));
+                return;
+            }
+        }
+    }
+}
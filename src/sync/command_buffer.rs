@@ -0,0 +1,64 @@
+use crate::{
+    MemberConfig, MemberID, PendingMember, Role, SpeedFactor, TickCommand, TickManagerHandle,
+};
+
+/// collects commands during a tick and hands them to the Tick Manager as one batch,
+/// which applies them together right before the next main frame starts
+#[must_use = "the commands are only applied once the buffer is submitted"]
+pub struct CommandBuffer {
+    manager_handle: TickManagerHandle,
+    commands: Vec<TickCommand>,
+}
+
+impl CommandBuffer {
+    pub fn new(manager_handle: TickManagerHandle) -> Self {
+        Self {
+            manager_handle,
+            commands: Vec::new(),
+        }
+    }
+
+    /// registers a member with the role R, it exists once the buffer was applied
+    pub fn register<R: Role>(&mut self, mut config: MemberConfig) -> PendingMember<R> {
+        config.kind = R::KIND;
+        let (sender, receiver) = flume::bounded(10);
+        self.commands
+            .push(TickCommand::Register(sender, Box::new(config.clone())));
+        PendingMember::new(self.manager_handle.clone(), config, receiver)
+    }
+
+    pub fn set_speed_factor(&mut self, id: MemberID, speed_factor: SpeedFactor) {
+        self.commands
+            .push(TickCommand::SetSpeedFactor(id, speed_factor));
+    }
+
+    pub fn unregister(&mut self, id: MemberID) {
+        self.commands.push(TickCommand::Unregister(id));
+    }
+
+    /// runs the closure on the manager thread when the buffer is applied
+    pub fn run(&mut self, command: impl FnOnce() + Send + 'static) {
+        self.commands.push(TickCommand::Run(Box::new(command)));
+    }
+
+    /// adds any other command, a Shutdown stops the Tick Manager when it comes up
+    pub fn push(&mut self, command: TickCommand) {
+        self.commands.push(command);
+    }
+
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// sends the commands to the Tick Manager, they are applied in the order they were added
+    pub fn submit(self) -> Result<(), flume::SendError<TickCommand>> {
+        if self.commands.is_empty() {
+            return Ok(());
+        }
+        self.manager_handle.send(TickCommand::Apply(self.commands))
+    }
+}
@@ -0,0 +1,64 @@
+use std::fmt;
+
+use flume::RecvTimeoutError;
+
+use crate::{QuotaKind, TickCommand};
+
+/// what can go wrong talking to the Tick Manager
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TickError {
+    /// the Tick Manager has shut down
+    ManagerGone,
+    /// the Tick Manager didn't reply in time
+    Timeout,
+    /// the member was removed from the Tick Manager
+    Unregistered,
+    /// the Tick Manager sent a different reply than expected
+    UnexpectedReply(String),
+    /// the member would have exceeded the TenantQuota of its tenant
+    QuotaExceeded { tenant: String, quota: QuotaKind },
+    /// a restricted handle can't be restricted to another namespace than its own
+    OutsideNamespace(String),
+}
+
+impl fmt::Display for TickError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TickError::ManagerGone => write!(f, "the Tick Manager has shut down"),
+            TickError::Timeout => {
+                write!(f, "did not receive a reply from the Tick Manager in time")
+            }
+            TickError::Unregistered => write!(f, "the member is no longer registered"),
+            TickError::UnexpectedReply(reply) => {
+                write!(f, "unexpected reply from the Tick Manager: {}", reply)
+            }
+            TickError::QuotaExceeded { tenant, quota } => {
+                let limit = match quota {
+                    QuotaKind::Members => "member",
+                    QuotaKind::Rate => "rate",
+                };
+                write!(f, "tenant {} is at its {} quota", tenant, limit)
+            }
+            TickError::OutsideNamespace(namespace) => {
+                write!(f, "the handle is restricted to the namespace {}", namespace)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TickError {}
+
+impl From<RecvTimeoutError> for TickError {
+    fn from(e: RecvTimeoutError) -> Self {
+        match e {
+            RecvTimeoutError::Timeout => TickError::Timeout,
+            RecvTimeoutError::Disconnected => TickError::ManagerGone,
+        }
+    }
+}
+
+impl From<flume::SendError<TickCommand>> for TickError {
+    fn from(_: flume::SendError<TickCommand>) -> Self {
+        TickError::ManagerGone
+    }
+}
@@ -0,0 +1,39 @@
+use crate::{TickCommand, TickManagerHandle};
+
+/// lets a render member hold the simulation back until presentation caught up: frame N + depth
+/// only starts once the fence was signaled for frame N. dropping the fence lifts the gate
+#[derive(Debug)]
+pub struct FrameFence {
+    handle: TickManagerHandle,
+    depth: u64,
+}
+
+impl FrameFence {
+    /// the GPU submission of the frame completed, releases the frames up to frame + depth
+    pub fn signal(&self, frame: u64) {
+        let _ = self.handle.send(TickCommand::SignalFence(frame));
+    }
+
+    /// how many frames the simulation may run ahead of the last signaled one
+    pub fn depth(&self) -> u64 {
+        self.depth
+    }
+}
+
+impl Drop for FrameFence {
+    fn drop(&mut self) {
+        let _ = self.handle.send(TickCommand::SetFrameFence(None));
+    }
+}
+
+impl TickManagerHandle {
+    /// gates the main frames on a new fence with the given pipelining depth, replacing an
+    /// earlier one. the next `depth` frames start before the first signal
+    pub fn frame_fence(&self, depth: u64) -> FrameFence {
+        let _ = self.send(TickCommand::SetFrameFence(Some(depth)));
+        FrameFence {
+            handle: self.clone(),
+            depth,
+        }
+    }
+}
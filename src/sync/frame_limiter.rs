@@ -0,0 +1,72 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use crate::{TickClock, TickManagerHandle};
+
+/// how often wait checks whether the Tick Manager shut down while no frame starts
+const SHUTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// caps a render loop to the frame rate of a Tick Manager without registering a member,
+/// the loop is never waited for and just misses frames while it is slow
+#[derive(Debug)]
+pub struct FrameLimiter {
+    handle: TickManagerHandle,
+    clock: Arc<TickClock>,
+    /// the frame the last wait returned with, None before the first one
+    last_frame: Option<u64>,
+    last_wait: Instant,
+}
+
+/// a frame the loop of a FrameLimiter was released for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LimitedFrame {
+    pub tick_number: u64,
+    /// wall time since the previous wait returned, or since the limiter was created
+    pub delta: Duration,
+    /// frames that started since the previous wait returned, without the loop waiting for them
+    pub skipped: u64,
+}
+
+impl LimitedFrame {
+    pub fn skipped_frames(&self) -> bool {
+        self.skipped > 0
+    }
+}
+
+impl FrameLimiter {
+    pub fn new(handle: &TickManagerHandle) -> Self {
+        Self {
+            handle: handle.clone(),
+            clock: handle.clock(),
+            last_frame: None,
+            last_wait: Instant::now(),
+        }
+    }
+
+    /// blocks until the next main frame starts, None once the Tick Manager has shut down
+    pub fn wait(&mut self) -> Option<LimitedFrame> {
+        let last = self.last_frame.unwrap_or_else(|| self.clock.tick_number());
+        let reached = loop {
+            if let Some(reached) = self
+                .clock
+                .wait_for_frame_timeout(last + 1, SHUTDOWN_POLL_INTERVAL)
+            {
+                break reached;
+            }
+            if self.handle.is_shut_down() || self.clock.is_closed() {
+                return None;
+            }
+        };
+        let now = Instant::now();
+        let frame = LimitedFrame {
+            tick_number: reached,
+            delta: now - self.last_wait,
+            skipped: reached - last - 1,
+        };
+        self.last_frame = Some(reached);
+        self.last_wait = now;
+        Some(frame)
+    }
+}
@@ -0,0 +1,161 @@
+use std::{
+    hint, thread,
+    time::{Duration, Instant},
+};
+
+use crate::{Speed, SpeedFactor, TickManager, TickMember};
+
+/// a member of a harness run that spends `cost` of busy work on every tick it gets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyntheticMember {
+    pub speed_factor: SpeedFactor,
+    pub cost: Duration,
+}
+
+/// runs a Tick Manager with synthetic members for a number of main frames and measures it,
+/// to find out whether the crate keeps up with a given member count
+#[derive(Debug, Clone)]
+pub struct Harness {
+    speed: Speed,
+    members: Vec<SyntheticMember>,
+    frames: u64,
+}
+
+impl Harness {
+    /// a run of 100 frames at the given speed without members
+    pub fn new(speed: Speed) -> Self {
+        Self {
+            speed,
+            members: Vec::new(),
+            frames: 100,
+        }
+    }
+
+    /// adds a single member
+    pub fn member(mut self, speed_factor: SpeedFactor, cost: Duration) -> Self {
+        self.members.push(SyntheticMember { speed_factor, cost });
+        self
+    }
+
+    /// adds `count` members, `distribution` picks the speed factor and cost of the i-th one
+    pub fn members(
+        mut self,
+        count: usize,
+        mut distribution: impl FnMut(usize) -> SyntheticMember,
+    ) -> Self {
+        self.members.extend((0..count).map(&mut distribution));
+        self
+    }
+
+    /// how many main frames are measured
+    pub fn frames(mut self, frames: u64) -> Self {
+        self.frames = frames.max(1);
+        self
+    }
+
+    /// registers the members, runs the frames and blocks until they are done
+    pub fn run(self) -> HarnessReport {
+        let (_manager, handle) = TickManager::new(self.speed);
+        let members: Vec<(TickMember, Duration)> = self
+            .members
+            .iter()
+            .map(|m| (TickMember::new(handle.clone(), m.speed_factor), m.cost))
+            .collect();
+
+        let first = handle.clock().tick_number() + 1;
+        let last = first + self.frames;
+        let workers: Vec<_> = members
+            .into_iter()
+            .map(|(member, cost)| thread::spawn(move || tick_member(member, cost, last)))
+            .collect();
+
+        let started = handle.wait_until(move |info| info.tick_number >= first);
+        let finished = handle.wait_until(move |info| info.tick_number >= last);
+        let mut latencies: Vec<Duration> = workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap())
+            .collect();
+        latencies.sort();
+
+        // the first frame may already have passed while the members were spawned
+        let (frames, elapsed) = match (started, finished) {
+            (Some(started), Some(finished)) => (
+                finished.tick_number - started.tick_number,
+                finished.wall_time - started.wall_time,
+            ),
+            _ => (0, Duration::ZERO),
+        };
+        HarnessReport {
+            frames,
+            members: self.members.len(),
+            elapsed,
+            member_ticks: latencies.len() as u64,
+            latency: LatencyStats::new(&latencies),
+        }
+    }
+}
+
+/// waits for ticks and burns `cost` on each, returns how late every tick arrived
+fn tick_member(member: TickMember, cost: Duration, last: u64) -> Vec<Duration> {
+    let mut latencies = Vec::new();
+    while let Some(info) = member.wait_for_tick() {
+        if info.tick_number > last {
+            break;
+        }
+        latencies.push(Instant::now().saturating_duration_since(info.scheduled_at));
+        let work_started = Instant::now();
+        while work_started.elapsed() < cost {
+            hint::spin_loop();
+        }
+    }
+    latencies
+}
+
+/// what a harness run measured
+#[derive(Debug, Clone, PartialEq)]
+pub struct HarnessReport {
+    /// main frames the run was measured over
+    pub frames: u64,
+    pub members: usize,
+    /// wall time from the first to the last measured frame
+    pub elapsed: Duration,
+    /// ticks delivered to all members together
+    pub member_ticks: u64,
+    /// time from a frame being due until a member woke up with its tick
+    pub latency: LatencyStats,
+}
+
+impl HarnessReport {
+    pub fn frames_per_second(&self) -> f64 {
+        self.frames as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+
+    pub fn member_ticks_per_second(&self) -> f64 {
+        self.member_ticks as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+/// distribution of tick latencies, all zero if no tick was delivered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LatencyStats {
+    pub mean: Duration,
+    pub p50: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+}
+
+impl LatencyStats {
+    /// stats of the latencies, which have to be sorted
+    pub(crate) fn new(sorted: &[Duration]) -> Self {
+        let Some(&max) = sorted.last() else {
+            return Self::default();
+        };
+        let percentile = |p: usize| sorted[(sorted.len() - 1) * p / 100];
+        Self {
+            mean: sorted.iter().sum::<Duration>() / sorted.len() as u32,
+            p50: percentile(50),
+            p99: percentile(99),
+            max,
+        }
+    }
+}
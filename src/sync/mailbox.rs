@@ -0,0 +1,86 @@
+use std::{any::Any, fmt, sync::Arc};
+
+use crate::MemberID;
+
+/// a message one member sent another through the Tick Manager, see TickMember::send_to
+pub struct Message {
+    /// the member that sent the message
+    pub from: MemberID,
+    /// the main frame that was current when the message was sent
+    pub sent_in: u64,
+    payload: Box<dyn Any + Send>,
+}
+
+impl Message {
+    pub(crate) fn new(from: MemberID, sent_in: u64, payload: Box<dyn Any + Send>) -> Self {
+        Self {
+            from,
+            sent_in,
+            payload,
+        }
+    }
+
+    /// whether the payload is a T
+    pub fn is<T: Any>(&self) -> bool {
+        self.payload.is::<T>()
+    }
+
+    /// the payload as a T, or the message back if it is something else
+    pub fn downcast<T: Any>(self) -> Result<T, Self> {
+        match self.payload.downcast::<T>() {
+            Ok(payload) => Ok(*payload),
+            Err(payload) => Err(Self { payload, ..self }),
+        }
+    }
+}
+
+impl fmt::Debug for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Message")
+            .field("from", &self.from)
+            .field("sent_in", &self.sent_in)
+            .finish_non_exhaustive()
+    }
+}
+
+/// a message published on a topic, every subscriber gets the same payload, see TickMember::publish
+#[derive(Clone)]
+pub struct TopicMessage {
+    pub topic: Arc<str>,
+    /// the member that published the message
+    pub from: MemberID,
+    /// the main frame the message was published in
+    pub sent_in: u64,
+    payload: Arc<dyn Any + Send + Sync>,
+}
+
+impl TopicMessage {
+    pub(crate) fn new(
+        topic: Arc<str>,
+        from: MemberID,
+        sent_in: u64,
+        payload: Arc<dyn Any + Send + Sync>,
+    ) -> Self {
+        Self {
+            topic,
+            from,
+            sent_in,
+            payload,
+        }
+    }
+
+    /// the payload if it is a T
+    pub fn get<T: Any>(&self) -> Option<&T> {
+        self.payload.downcast_ref::<T>()
+    }
+}
+
+impl fmt::Debug for TopicMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TopicMessage")
+            .field("topic", &self.topic)
+            .field("from", &self.from)
+            .field("sent_in", &self.sent_in)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,129 @@
+use std::{any::Any, marker::PhantomData, sync::Arc, time::Duration};
+
+use crate::{
+    MemberConfig, MemberID, Role, Simulation, SpeedFactor, TickError, TickManagerHandle,
+    TickMember, WakeStrategy,
+};
+
+/// configures a TickMember before it is registered, see TickMember::builder
+#[derive(Debug, Clone)]
+pub struct TickMemberBuilder<R: Role = Simulation> {
+    manager_handle: TickManagerHandle,
+    config: MemberConfig,
+    role: PhantomData<R>,
+}
+
+impl TickMemberBuilder {
+    pub fn new(manager_handle: TickManagerHandle) -> Self {
+        Self {
+            manager_handle,
+            config: MemberConfig::default(),
+            role: PhantomData,
+        }
+    }
+}
+
+impl<R: Role> TickMemberBuilder<R> {
+    /// how many main frames pass between ticks of the member, defaults to 1
+    pub fn speed_factor(mut self, speed_factor: SpeedFactor) -> Self {
+        self.config.speed_factor = speed_factor;
+        self
+    }
+
+    /// shifts the frames the member is due on, see MemberConfig::offset.
+    /// the offset is kept even if the Tick Manager balances offsets
+    pub fn offset(mut self, offset: u64) -> Self {
+        self.config.offset = offset;
+        self.config.auto_offset = false;
+        self
+    }
+
+    /// a name to tell the member apart in logs and introspection
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.config.name = Some(name.into());
+        self
+    }
+
+    pub fn group(mut self, group: impl Into<String>) -> Self {
+        self.config.group = Some(group.into());
+        self
+    }
+
+    /// wakes the member before the ones with a lower priority, see MemberConfig::priority
+    pub fn priority(mut self, priority: i32) -> Self {
+        self.config.priority = priority;
+        self
+    }
+
+    /// ticks the member in a frame only after `member` finished its tick of the frame,
+    /// see MemberConfig::after
+    pub fn after(mut self, member: MemberID) -> Self {
+        self.config.after.push(member);
+        self
+    }
+
+    /// ticks the member in the given phase of every frame, after all members of the earlier
+    /// phases finished, see MemberConfig::phase
+    pub fn phase(mut self, phase: u32) -> Self {
+        self.config.phase = phase;
+        self
+    }
+
+    /// attaches state to the member, see MemberConfig::context
+    pub fn context<T: Any + Send + Sync>(mut self, context: Arc<T>) -> Self {
+        self.config.context = Some(context);
+        self
+    }
+
+    /// counts the member towards the quota of the tenant, see TickManagerHandle::set_tenant_quota
+    pub fn tenant(mut self, tenant: impl Into<String>) -> Self {
+        self.config.tenant = Some(tenant.into());
+        self
+    }
+
+    /// let the work of every tick span the frames until the next due one,
+    /// see MemberConfig::spread_work
+    pub fn spread_work(mut self, enabled: bool) -> Self {
+        self.config.spread_work = enabled;
+        self
+    }
+
+    /// makes this a background member with the given time slice per tick,
+    /// see MemberConfig::budget and TickMember::run_sliced
+    pub fn budget(mut self, budget: Duration) -> Self {
+        self.config.budget = Some(budget);
+        self
+    }
+
+    /// receive the latest frame on registration, see TickMember::bootstrap_info
+    pub fn bootstrap(mut self, enabled: bool) -> Self {
+        self.config.bootstrap = enabled;
+        self
+    }
+
+    /// how the member waits for its ticks, see WakeStrategy
+    pub fn wake(mut self, wake: WakeStrategy) -> Self {
+        self.config.wake = wake;
+        self
+    }
+
+    /// registers the member with another role, e.g. `.role::<Observer>()`
+    pub fn role<R2: Role>(self) -> TickMemberBuilder<R2> {
+        TickMemberBuilder {
+            manager_handle: self.manager_handle,
+            config: self.config,
+            role: PhantomData,
+        }
+    }
+
+    /// registers the member with the Tick Manager
+    pub fn build(self) -> Result<TickMember<R>, TickError> {
+        TickMember::try_with_role(self.manager_handle, self.config)
+    }
+}
+
+impl TickMember {
+    pub fn builder(manager_handle: TickManagerHandle) -> TickMemberBuilder {
+        TickMemberBuilder::new(manager_handle)
+    }
+}
@@ -0,0 +1,56 @@
+//! the Tick Manager thread and the blocking member API
+
+pub mod calibrate;
+pub mod callback;
+pub mod command_buffer;
+pub mod error;
+pub mod fence;
+pub mod frame_limiter;
+pub mod harness;
+pub mod mailbox;
+pub mod member_builder;
+pub mod rate_limiter;
+pub mod retry;
+pub mod role;
+#[cfg(feature = "scripting")]
+pub mod scripting;
+#[cfg(all(feature = "shm", unix))]
+pub mod shm;
+pub mod supervisor;
+pub mod testkit;
+pub mod tick_hook;
+pub mod tick_queue;
+pub mod tick_stream;
+#[cfg(feature = "watch")]
+pub mod tick_watch;
+pub mod tickmanager;
+pub mod timers;
+pub mod tween;
+pub mod wake;
+pub use calibrate::*;
+pub use callback::*;
+pub use command_buffer::*;
+pub use error::*;
+pub use fence::*;
+pub use frame_limiter::*;
+pub use harness::*;
+pub use mailbox::*;
+pub use member_builder::*;
+pub use rate_limiter::*;
+pub use retry::*;
+pub use role::*;
+#[cfg(feature = "scripting")]
+pub use scripting::*;
+#[cfg(all(feature = "shm", unix))]
+pub use shm::*;
+pub use supervisor::*;
+pub use testkit::*;
+pub use tick_hook::*;
+pub use tick_queue::*;
+pub use tick_stream::*;
+#[cfg(feature = "watch")]
+pub use tick_watch::*;
+pub use tickmanager::*;
+pub use timers::*;
+pub use tween::*;
+pub use wake::*;
@@ -0,0 +1,89 @@
+use flume::Receiver;
+use std::sync::{Arc, Mutex};
+
+use crate::{TickClock, TickCommand, TickInfo, TickManagerHandle};
+
+/// hands out a fixed amount of permits that are replenished every few main frames
+#[derive(Debug)]
+pub struct RateLimiter {
+    clock: Arc<TickClock>,
+    /// used to block until the next frame
+    receiver: Receiver<TickInfo>,
+    state: Mutex<LimiterState>,
+    permits: usize,
+    refill_every: u64,
+}
+
+#[derive(Debug)]
+struct LimiterState {
+    available: usize,
+    /// main frame of the last refill, None until the first frame was observed
+    last_refill: Option<u64>,
+}
+
+impl RateLimiter {
+    /// creates a limiter with `permits` permits, refilled every `refill_every` main frames.
+    /// fails if the Tick Manager has shut down
+    pub fn new(
+        manager_handle: &TickManagerHandle,
+        permits: usize,
+        refill_every: u64,
+    ) -> Result<Self, flume::SendError<TickCommand>> {
+        let (sender, receiver) = flume::bounded(1);
+        manager_handle.send(TickCommand::Observe(sender))?;
+        Ok(Self {
+            clock: manager_handle.clock(),
+            receiver,
+            state: Mutex::new(LimiterState {
+                available: permits,
+                last_refill: None,
+            }),
+            permits,
+            refill_every: refill_every.max(1),
+        })
+    }
+
+    /// takes a permit if one is available, never blocks
+    pub fn try_acquire(&self) -> bool {
+        self.refill(self.clock.tick_number());
+        let mut state = self.state.lock().unwrap();
+        if state.available > 0 {
+            state.available -= 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// blocks until a permit is available.
+    /// returns false if the Tick Manager has shut down and no permit is left
+    pub fn acquire(&self) -> bool {
+        loop {
+            if self.try_acquire() {
+                return true;
+            }
+            if self.receiver.recv().is_err() {
+                return false;
+            }
+        }
+    }
+
+    /// permits currently left until the next refill
+    pub fn available(&self) -> usize {
+        self.state.lock().unwrap().available
+    }
+
+    fn refill(&self, tick_number: u64) {
+        let mut state = self.state.lock().unwrap();
+        // the refill is based on the frame number instead of counting frames,
+        // nobody has to be acquiring while frames pass
+        let due = match state.last_refill {
+            Some(last) => tick_number.saturating_sub(last) >= self.refill_every,
+            None => true,
+        };
+        if due {
+            state.available = self.permits;
+            state.last_refill = Some(tick_number);
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use flume::Sender;
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicU32, Ordering},
+    },
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{ClockDomain, Due, ScheduleHandle, frames_until};
+
+/// how retries are spaced out
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// delay before the first retry
+    pub initial_delay: Duration,
+    /// factor the delay grows by after every attempt
+    pub multiplier: f64,
+    pub max_delay: Duration,
+    /// None retries until cancelled
+    pub max_attempts: Option<u32>,
+    /// random spread applied to every delay, 0.25 means +-25%
+    pub jitter: f64,
+    /// Sim retries wait while no frames start, Wall retries don't
+    pub domain: ClockDomain,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_millis(100),
+            multiplier: 2.0,
+            max_delay: Duration::from_secs(30),
+            max_attempts: Some(5),
+            jitter: 0.1,
+            domain: ClockDomain::Sim,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// delay before the given attempt (1-based) without jitter
+    pub fn base_delay(&self, attempt: u32) -> Duration {
+        let factor = self
+            .multiplier
+            .max(1.0)
+            .powi(attempt.saturating_sub(1).min(i32::MAX as u32) as i32);
+        // late attempts of an unlimited retry grow past what a Duration holds
+        Duration::try_from_secs_f64(self.initial_delay.as_secs_f64() * factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+}
+
+/// sent to the task sender every time a retry is due
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryAttempt {
+    /// 1 for the first retry
+    pub attempt: u32,
+    /// the delay that was waited before this attempt
+    pub delay: Duration,
+}
+
+/// shared between a RetryHandle and the manager thread
+#[derive(Debug, Default)]
+pub struct RetryState {
+    attempts: AtomicU32,
+}
+
+/// controls a retry schedule running on the Tick Manager
+#[derive(Debug, Clone)]
+pub struct RetryHandle {
+    schedule: ScheduleHandle,
+    state: Arc<RetryState>,
+}
+
+impl RetryHandle {
+    pub(crate) fn new(schedule: ScheduleHandle, state: Arc<RetryState>) -> Self {
+        Self { schedule, state }
+    }
+
+    /// stops all further attempts, e.g. once the task succeeded
+    pub fn cancel(&self) {
+        self.schedule.cancel();
+    }
+
+    /// whether more attempts will be sent
+    pub fn is_pending(&self) -> bool {
+        self.schedule.is_pending()
+    }
+
+    /// sends the next attempt on the given amount of main frames from now instead,
+    /// the backoff continues from there. false if no more attempts will be sent
+    pub fn reschedule(&self, frames: u64) -> bool {
+        self.schedule.reschedule(frames)
+    }
+
+    /// amount of attempts sent so far
+    pub fn attempts(&self) -> u32 {
+        self.state.attempts.load(Ordering::Acquire)
+    }
+}
+
+/// a retry schedule as tracked by the manager thread
+pub(crate) struct ActiveRetry {
+    policy: RetryPolicy,
+    sender: Sender<RetryAttempt>,
+    state: Arc<RetryState>,
+    next_attempt: u32,
+    delay: Duration,
+    due_at: Instant,
+    rng: u64,
+}
+
+impl ActiveRetry {
+    pub(crate) fn new(
+        policy: RetryPolicy,
+        sender: Sender<RetryAttempt>,
+        state: Arc<RetryState>,
+    ) -> Self {
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or_default();
+        let mut retry = Self {
+            policy,
+            sender,
+            state,
+            next_attempt: 1,
+            delay: Duration::ZERO,
+            due_at: Instant::now(),
+            rng: seed | 1,
+        };
+        retry.schedule(Instant::now());
+        retry
+    }
+
+    /// sends the attempt if it is due, returns false once the schedule is over
+    pub(crate) fn poll(&mut self, now: Instant) -> bool {
+        if now < self.due_at {
+            return true;
+        }
+        let attempt = RetryAttempt {
+            attempt: self.next_attempt,
+            delay: self.delay,
+        };
+        if self.sender.send(attempt).is_err() {
+            return false;
+        }
+        self.state.attempts.fetch_add(1, Ordering::AcqRel);
+
+        if self
+            .policy
+            .max_attempts
+            .is_some_and(|max| self.next_attempt >= max)
+        {
+            return false;
+        }
+        self.next_attempt += 1;
+        self.schedule(now);
+        true
+    }
+
+    /// when the next attempt is sent
+    pub(crate) fn due(&self, tick_number: u64, frame_period: Duration) -> Due {
+        match self.policy.domain {
+            ClockDomain::Sim => Due::Frame(frames_until(tick_number, frame_period, self.due_at)),
+            ClockDomain::Wall => Due::At(self.due_at),
+        }
+    }
+
+    pub(crate) fn domain(&self) -> ClockDomain {
+        self.policy.domain
+    }
+
+    /// makes the next attempt due right away, used when the schedule was moved
+    pub(crate) fn due_now(&mut self, now: Instant) {
+        self.due_at = now;
+    }
+
+    fn schedule(&mut self, now: Instant) {
+        let base = self.policy.base_delay(self.next_attempt);
+        let jitter = self.policy.jitter.clamp(0.0, 1.0);
+        // xorshift, good enough to spread out retries
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        let unit = (self.rng >> 11) as f64 / (1u64 << 53) as f64;
+        self.delay = base.mul_f64(1.0 + jitter * (unit * 2.0 - 1.0));
+        self.due_at = now + self.delay;
+    }
+}
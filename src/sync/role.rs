@@ -0,0 +1,44 @@
+use crate::MemberKind;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// decides at the type level how a TickMember takes part in main frames
+pub trait Role: sealed::Sealed + Clone + std::fmt::Debug + Send + Sync + 'static {
+    const KIND: MemberKind;
+}
+
+/// roles that report their state, frames can wait for them
+pub trait Participant: Role {}
+
+/// takes part in the barrier, frames wait until it finished its tick
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Simulation;
+
+/// frames don't wait for it, frames that are due while it's still working are skipped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Render;
+
+/// receives every due tick without reporting its state, it can never hold back a frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Observer;
+
+impl sealed::Sealed for Simulation {}
+impl sealed::Sealed for Render {}
+impl sealed::Sealed for Observer {}
+
+impl Role for Simulation {
+    const KIND: MemberKind = MemberKind::Participant;
+}
+
+impl Role for Render {
+    const KIND: MemberKind = MemberKind::FrameDropping;
+}
+
+impl Role for Observer {
+    const KIND: MemberKind = MemberKind::Observer;
+}
+
+impl Participant for Simulation {}
+impl Participant for Render {}
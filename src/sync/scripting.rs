@@ -0,0 +1,87 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    FallibleTickCallback, MemberConfig, MemberID, MemberToken, SpeedFactor, TickCommand, TickError,
+    TickManagerHandle,
+};
+
+/// members addressed by their plain id, for a scripting layer to bind its register,
+/// unregister and set-factor functions to. no script engine is bundled, the engine's bindings
+/// call these and run the script from the callbacks.
+/// scripts can hold the ids as integers, the host keeps their MemberTokens alive until a
+/// script unregisters them or the host is dropped.
+/// errors of the tick callbacks are reported as TickEvent::MemberFailed instead of unwinding,
+/// give the host a TickManagerHandle::restricted handle to keep scripts in a namespace
+#[derive(Debug, Clone)]
+pub struct MemberHost {
+    handle: TickManagerHandle,
+    members: Arc<Mutex<HashMap<MemberID, MemberToken>>>,
+}
+
+impl MemberHost {
+    pub fn new(handle: TickManagerHandle) -> Self {
+        Self {
+            handle,
+            members: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// registers a member running `callback` on every tick, returns its id for the script.
+    /// the callback usually calls into the script engine and turns its error into a message
+    pub fn register(
+        &self,
+        speed_factor: SpeedFactor,
+        callback: FallibleTickCallback,
+    ) -> Result<MemberID, TickError> {
+        self.register_with(
+            MemberConfig {
+                speed_factor,
+                ..Default::default()
+            },
+            callback,
+        )
+    }
+
+    /// like register, with the whole MemberConfig
+    pub fn register_with(
+        &self,
+        config: MemberConfig,
+        callback: FallibleTickCallback,
+    ) -> Result<MemberID, TickError> {
+        let token = self.handle.try_register_fallible(config, callback)?;
+        let id = token.id();
+        self.members.lock().unwrap().insert(id, token);
+        Ok(id)
+    }
+
+    /// removes a member registered by this host, false for any other id.
+    /// a callback may unregister its own member, it doesn't get another tick then
+    pub fn unregister(&self, id: MemberID) -> bool {
+        let token = self.members.lock().unwrap().remove(&id);
+        match token {
+            Some(token) => {
+                token.unregister();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// changes the speed factor of a member registered by this host, false for any other id
+    pub fn set_factor(&self, id: MemberID, speed_factor: SpeedFactor) -> bool {
+        if !self.members.lock().unwrap().contains_key(&id) {
+            return false;
+        }
+        self.handle
+            .send(TickCommand::SetSpeedFactor(id, speed_factor))
+            .is_ok()
+    }
+
+    /// the ids of the members registered by this host, in no particular order
+    pub fn members(&self) -> Vec<MemberID> {
+        self.members.lock().unwrap().keys().copied().collect()
+    }
+}
@@ -0,0 +1,246 @@
+use std::{
+    fs::OpenOptions,
+    io,
+    os::fd::AsRawFd,
+    path::Path,
+    ptr,
+    sync::atomic::{AtomicU64, Ordering},
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{Speed, TickInfo};
+
+/// "TICKSHM1", marks a file written by a ShmPublisher
+const MAGIC: u64 = u64::from_le_bytes(*b"TICKSHM1");
+
+/// the layout of the shared file. the sequence is odd while the publisher writes,
+/// readers retry until they read the same even sequence before and after the fields
+#[repr(C)]
+struct SharedLayout {
+    magic: AtomicU64,
+    sequence: AtomicU64,
+    tick_number: AtomicU64,
+    sim_time_nanos: AtomicU64,
+    wall_time_nanos: AtomicU64,
+    started_at_unix_nanos: AtomicU64,
+    period_nanos: AtomicU64,
+}
+
+const SIZE: usize = size_of::<SharedLayout>();
+
+/// a shared mapping of a SharedLayout file
+#[derive(Debug)]
+struct Mapping {
+    layout: *mut SharedLayout,
+}
+
+// SAFETY: the mapping is only accessed through the atomics of SharedLayout
+unsafe impl Send for Mapping {}
+unsafe impl Sync for Mapping {}
+
+impl Mapping {
+    fn open(path: &Path, write: bool) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(write)
+            .create(write)
+            .truncate(false)
+            .open(path)?;
+        if write {
+            file.set_len(SIZE as u64)?;
+        } else if file.metadata()?.len() < SIZE as u64 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "not a tick publication",
+            ));
+        }
+        let prot = if write {
+            libc::PROT_READ | libc::PROT_WRITE
+        } else {
+            libc::PROT_READ
+        };
+        // SAFETY: maps SIZE bytes of a file that is at least that long, the file may be closed
+        // afterwards. the mapping is page aligned, which is enough for the atomics
+        let address = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                SIZE,
+                prot,
+                libc::MAP_SHARED,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if address == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Self {
+            layout: address.cast(),
+        })
+    }
+
+    fn layout(&self) -> &SharedLayout {
+        // SAFETY: mapped until drop, only accessed through atomics
+        unsafe { &*self.layout }
+    }
+}
+
+impl Drop for Mapping {
+    fn drop(&mut self) {
+        // SAFETY: unmaps what open mapped, no reference to it outlives self
+        unsafe {
+            libc::munmap(self.layout.cast(), SIZE);
+        }
+    }
+}
+
+/// writes the frames of a Tick Manager into a shared file, e.g. in /dev/shm, so sibling
+/// processes can pace themselves off it with a ShmReader, see TickManagerBuilder::publish_to_shm.
+/// the file is left in place when the publisher is dropped
+#[derive(Debug)]
+pub struct ShmPublisher {
+    mapping: Mapping,
+}
+
+impl ShmPublisher {
+    /// creates the file or takes over an existing one
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mapping = Mapping::open(path.as_ref(), true)?;
+        let layout = mapping.layout();
+        layout.sequence.store(0, Ordering::Relaxed);
+        layout.tick_number.store(0, Ordering::Relaxed);
+        layout.magic.store(MAGIC, Ordering::Release);
+        Ok(Self { mapping })
+    }
+
+    /// writes the frame, called by the Tick Manager at the start of every main frame.
+    /// `now` is the time of the Tick Manager's Clock
+    pub(crate) fn publish(&self, info: &TickInfo, speed: &Speed, now: Instant) {
+        let layout = self.mapping.layout();
+        // the frame started at scheduled_at at the latest, in wall clock time
+        let late = now.saturating_duration_since(info.scheduled_at);
+        let started_at = SystemTime::now()
+            .checked_sub(late)
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .unwrap_or_default();
+
+        let sequence = layout.sequence.fetch_add(1, Ordering::Relaxed);
+        std::sync::atomic::fence(Ordering::Release);
+        layout
+            .tick_number
+            .store(info.tick_number, Ordering::Relaxed);
+        let nanos = |d: Duration| d.as_nanos() as u64;
+        layout
+            .sim_time_nanos
+            .store(nanos(info.sim_time), Ordering::Relaxed);
+        layout
+            .wall_time_nanos
+            .store(nanos(info.wall_time), Ordering::Relaxed);
+        layout
+            .started_at_unix_nanos
+            .store(nanos(started_at), Ordering::Relaxed);
+        layout
+            .period_nanos
+            .store(nanos(speed.get_duration()), Ordering::Relaxed);
+        layout.sequence.store(sequence + 2, Ordering::Release);
+    }
+}
+
+/// a main frame as read from a ShmPublisher
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SharedFrame {
+    pub tick_number: u64,
+    pub sim_time: Duration,
+    pub wall_time: Duration,
+    /// when the frame was due, in wall clock time since the unix epoch
+    pub started_at: Duration,
+    /// the frame period of the publishing Tick Manager
+    pub period: Duration,
+}
+
+impl SharedFrame {
+    /// when the frame after this one is due, in wall clock time since the unix epoch
+    pub fn next_due(&self) -> Duration {
+        self.started_at + self.period
+    }
+}
+
+/// reads the frames another process publishes with a ShmPublisher
+#[derive(Debug)]
+pub struct ShmReader {
+    mapping: Mapping,
+}
+
+impl ShmReader {
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mapping = Mapping::open(path.as_ref(), false)?;
+        if mapping.layout().magic.load(Ordering::Acquire) != MAGIC {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "not a tick publication",
+            ));
+        }
+        Ok(Self { mapping })
+    }
+
+    /// the latest published frame, None before the first one
+    pub fn latest(&self) -> Option<SharedFrame> {
+        let layout = self.mapping.layout();
+        loop {
+            let before = layout.sequence.load(Ordering::Acquire);
+            if before % 2 == 1 {
+                std::hint::spin_loop();
+                continue;
+            }
+            let nanos = |value: &AtomicU64| Duration::from_nanos(value.load(Ordering::Relaxed));
+            let frame = SharedFrame {
+                tick_number: layout.tick_number.load(Ordering::Relaxed),
+                sim_time: nanos(&layout.sim_time_nanos),
+                wall_time: nanos(&layout.wall_time_nanos),
+                started_at: nanos(&layout.started_at_unix_nanos),
+                period: nanos(&layout.period_nanos),
+            };
+            std::sync::atomic::fence(Ordering::Acquire);
+            if layout.sequence.load(Ordering::Relaxed) == before {
+                return (frame.tick_number > 0).then_some(frame);
+            }
+        }
+    }
+
+    /// sleeps until frame `frame` was published, None if it wasn't within the timeout.
+    /// sleeps until the frame is expected and polls from there
+    pub fn wait_for_frame(&self, frame: u64, timeout: Duration) -> Option<SharedFrame> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let latest = self.latest();
+            if let Some(latest) = latest.filter(|latest| latest.tick_number >= frame) {
+                return Some(latest);
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return None;
+            }
+            let expected_in = latest
+                .map(|latest| {
+                    let frames_left = (frame - latest.tick_number).min(u32::MAX as u64) as u32;
+                    let due = latest
+                        .started_at
+                        .saturating_add(latest.period.saturating_mul(frames_left));
+                    let unix_now = SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .unwrap_or_default();
+                    due.saturating_sub(unix_now)
+                })
+                .unwrap_or_default();
+            thread::sleep(
+                expected_in
+                    .max(POLL_INTERVAL)
+                    .min(deadline.saturating_duration_since(now)),
+            );
+        }
+    }
+}
+
+/// how often wait_for_frame checks for a frame once it is expected
+const POLL_INTERVAL: Duration = Duration::from_micros(200);
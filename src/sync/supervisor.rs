@@ -0,0 +1,113 @@
+use std::{
+    any::Any,
+    sync::Arc,
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use crate::{HookID, MemberConfig, TickCommand, TickManagerHandle, TickMember};
+
+/// what happens after the worker of a supervised member panicked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestartPolicy {
+    /// respawn the worker after `backoff`, at most `max` times, then remove the member
+    Restart { max: u32, backoff: Duration },
+    /// unregister the member
+    Remove,
+    /// only report the panic and respawn the worker after IGNORE_BACKOFF, without limit
+    Ignore,
+}
+
+/// how long an ignored panic waits before the worker is respawned, so a worker that panics
+/// right away doesn't spin up threads and panic events in a hot loop
+pub const IGNORE_BACKOFF: Duration = Duration::from_millis(100);
+
+/// a member whose worker thread is respawned according to its RestartPolicy
+#[derive(Debug)]
+pub struct SupervisedMember {
+    pub id: HookID,
+    supervisor: JoinHandle<()>,
+}
+
+impl SupervisedMember {
+    /// blocks until the worker returned normally or the member was removed
+    pub fn join(self) {
+        let _ = self.supervisor.join();
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.supervisor.is_finished()
+    }
+}
+
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+impl TickManagerHandle {
+    /// registers a member and runs `worker` with it on its own thread.
+    /// when the worker panics, the panic is reported as TickEvent::MemberPanicked
+    /// and the worker is handled according to `policy`.
+    /// `worker` is the factory every respawn calls again with the same TickMember: it keeps its
+    /// id, registration and unread messages, and the tick it panicked in counts as finished
+    pub fn supervise<F>(
+        &self,
+        config: MemberConfig,
+        policy: RestartPolicy,
+        worker: F,
+    ) -> SupervisedMember
+    where
+        F: Fn(&TickMember) + Send + Sync + 'static,
+    {
+        let member = Arc::new(TickMember::with_config(self.clone(), config));
+        let id = member.id;
+        let worker = Arc::new(worker);
+        let manager_handle = self.clone();
+
+        let supervisor = thread::spawn(move || {
+            let mut restarts = 0;
+            loop {
+                let result = {
+                    let member = member.clone();
+                    let worker = worker.clone();
+                    thread::spawn(move || worker(&member)).join()
+                };
+                let Err(payload) = result else {
+                    return;
+                };
+
+                let remove = match policy {
+                    RestartPolicy::Restart { max, .. } => restarts >= max,
+                    RestartPolicy::Remove => true,
+                    RestartPolicy::Ignore => false,
+                };
+                let _ = manager_handle.send(TickCommand::ReportPanic(
+                    id,
+                    panic_message(payload.as_ref()),
+                    remove,
+                ));
+                if remove {
+                    return;
+                }
+
+                match policy {
+                    RestartPolicy::Restart { backoff, .. } => {
+                        restarts += 1;
+                        thread::sleep(backoff);
+                        let _ = manager_handle.send(TickCommand::ReportRestart(id, restarts));
+                    }
+                    RestartPolicy::Ignore => thread::sleep(IGNORE_BACKOFF),
+                    RestartPolicy::Remove => {}
+                }
+            }
+        });
+
+        SupervisedMember { id, supervisor }
+    }
+}
@@ -0,0 +1,159 @@
+use std::{
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread::{self, JoinHandle},
+};
+
+use crate::{MemberConfig, MemberDescriptor, MemberID, SpeedFactor, TickManagerHandle, TickMember};
+
+/// spawns members that count their ticks on their own threads, to write integration tests
+/// against a Tick Manager configured like the real one.
+/// the members unregister when the kit is dropped
+#[derive(Debug)]
+pub struct TestKit {
+    handle: TickManagerHandle,
+    members: Vec<CountingMember>,
+    stop: Arc<AtomicBool>,
+}
+
+#[derive(Debug)]
+struct CountingMember {
+    descriptor: MemberDescriptor,
+    /// the frames the member got a tick on
+    ticks: Arc<Mutex<Vec<u64>>>,
+    worker: Option<JoinHandle<()>>,
+}
+
+/// the main frames of a TestKit::run_ticks call, from excluded to `to` included
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TestRun {
+    pub from: u64,
+    pub to: u64,
+}
+
+impl TestRun {
+    pub fn frames(&self) -> u64 {
+        self.to - self.from
+    }
+
+    /// how many ticks a member with the speed factor should get during the run
+    pub fn expected_ticks(&self, speed_factor: SpeedFactor) -> u64 {
+        self.frames() / speed_factor.max(1) as u64
+    }
+
+    fn contains(&self, frame: u64) -> bool {
+        frame > self.from && frame <= self.to
+    }
+}
+
+impl TestKit {
+    pub fn new(handle: TickManagerHandle) -> Self {
+        Self {
+            handle,
+            members: Vec::new(),
+            stop: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// spawns `count` counting members with the speed factor, returns their ids
+    pub fn spawn_counting(&mut self, count: usize, speed_factor: SpeedFactor) -> Vec<MemberID> {
+        (0..count)
+            .map(|_| {
+                self.spawn_counting_with(MemberConfig {
+                    speed_factor,
+                    ..Default::default()
+                })
+            })
+            .collect()
+    }
+
+    /// spawns a single counting member registered with the config
+    pub fn spawn_counting_with(&mut self, config: MemberConfig) -> MemberID {
+        let member = TickMember::with_config(self.handle.clone(), config);
+        let descriptor = member.descriptor();
+        let ticks = Arc::new(Mutex::new(Vec::new()));
+        let worker = {
+            let ticks = ticks.clone();
+            let stop = self.stop.clone();
+            thread::spawn(move || count_ticks(member, &ticks, &stop))
+        };
+        self.members.push(CountingMember {
+            descriptor: descriptor.clone(),
+            ticks,
+            worker: Some(worker),
+        });
+        descriptor.id
+    }
+
+    /// blocks until `frames` more main frames started.
+    /// waits for one frame past the run, so the members recorded the ticks of its last frame
+    pub fn run_ticks(&self, frames: u64) -> TestRun {
+        let from = self.handle.clock().tick_number();
+        let to = from + frames;
+        self.handle.wait_until(move |info| info.tick_number > to);
+        TestRun { from, to }
+    }
+
+    /// the ticks the member got during the run, 0 for ids the kit didn't spawn
+    pub fn count(&self, id: MemberID, run: &TestRun) -> u64 {
+        self.members
+            .iter()
+            .find(|m| m.descriptor.id == id)
+            .map_or(0, |m| m.count(run))
+    }
+
+    /// every member with the ticks it got during the run, in spawn order
+    pub fn counts(&self, run: &TestRun) -> Vec<(MemberDescriptor, u64)> {
+        self.members
+            .iter()
+            .map(|m| (m.descriptor.clone(), m.count(run)))
+            .collect()
+    }
+
+    /// panics unless every member got TestRun::expected_ticks for its speed factor,
+    /// give or take `tolerance` ticks for frames that were skipped or started while spawning
+    pub fn assert_counts(&self, run: &TestRun, tolerance: u64) {
+        for (descriptor, count) in self.counts(run) {
+            let expected = run.expected_ticks(descriptor.factor);
+            assert!(
+                count.abs_diff(expected) <= tolerance,
+                "{} got {} ticks in frames {}..={}, expected {} ± {}",
+                descriptor,
+                count,
+                run.from + 1,
+                run.to,
+                expected,
+                tolerance
+            );
+        }
+    }
+}
+
+impl CountingMember {
+    fn count(&self, run: &TestRun) -> u64 {
+        let ticks = self.ticks.lock().unwrap();
+        ticks.iter().filter(|&&frame| run.contains(frame)).count() as u64
+    }
+}
+
+fn count_ticks(member: TickMember, ticks: &Mutex<Vec<u64>>, stop: &AtomicBool) {
+    while !stop.load(Ordering::Acquire) {
+        let Some(info) = member.wait_for_tick() else {
+            break;
+        };
+        ticks.lock().unwrap().push(info.tick_number);
+    }
+}
+
+impl Drop for TestKit {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Release);
+        for member in &mut self.members {
+            if let Some(worker) = member.worker.take() {
+                let _ = worker.join();
+            }
+        }
+    }
+}
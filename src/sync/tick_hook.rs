@@ -0,0 +1,733 @@
+use flume::{Receiver, RecvTimeoutError, TryRecvError};
+use std::{
+    any::Any,
+    marker::PhantomData,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread::{self, ThreadId},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    HookID, MemberConfig, MemberDescriptor, MemberID, MemberKind, MemberState, MemberTicks,
+    Message, Participant, Role, Simulation, SpeedFactor, StateCell, TickCommand, TickError,
+    TickInfo, TickManagerHandle, TickStateReply, TopicMessage, WakeSlot, WakeStrategy,
+    panic_message,
+};
+
+/// what catch_ticks does after the tick handler panicked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PanicAction {
+    /// keep running the handler on the next tick
+    Continue,
+    /// unregister the member and return
+    Unregister,
+}
+
+#[derive(Debug, Clone)]
+pub struct TickMember<R: Role = Simulation> {
+    pub id: usize,
+    /// replaced when the Tick Manager hands its members over to a standby
+    manager_handle: Arc<Mutex<TickManagerHandle>>,
+    receiver: Receiver<TickStateReply>,
+    /// shared with the Tick Manager, setting it needs no command
+    state_cell: Arc<StateCell>,
+    /// set once the Tick Manager removed this member or shut down
+    unregistered: Arc<AtomicBool>,
+    /// kept to register with another Tick Manager on migrate
+    config: Arc<Mutex<MemberConfig>>,
+    bootstrap: Option<TickInfo>,
+    active_from: u64,
+    latest_tick: Arc<Mutex<Option<TickInfo>>>,
+    /// end of the time slice of a background member
+    slice_end: Arc<Mutex<Option<Instant>>>,
+    /// messages that arrived with the ticks so far
+    inbox: Arc<Mutex<Vec<Message>>>,
+    topic_inbox: Arc<Mutex<Vec<TopicMessage>>>,
+    /// set for WakeStrategy::Park, the Tick Manager puts the plain ticks here
+    wake_slot: Option<Arc<WakeSlot>>,
+    /// the thread the Tick Manager unparks, None until the first wait
+    parked_on: Arc<Mutex<Option<ThreadId>>>,
+    role: PhantomData<R>,
+}
+
+impl TickMember<Simulation> {
+    /// adds a new tick member to the Tick Manager
+    pub fn new(manager_handle: TickManagerHandle, speed_factor: usize) -> Self {
+        Self::with_config(
+            manager_handle,
+            MemberConfig {
+                speed_factor,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// like new, but returns an error instead of panicking if the Tick Manager is gone
+    /// or doesn't reply in time
+    pub fn try_new(
+        manager_handle: TickManagerHandle,
+        speed_factor: usize,
+    ) -> Result<Self, TickError> {
+        Self::try_with_config(
+            manager_handle,
+            MemberConfig {
+                speed_factor,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// adds a new tick member to the Tick Manager as part of a group
+    pub fn new_in_group(
+        manager_handle: TickManagerHandle,
+        speed_factor: usize,
+        group: &str,
+    ) -> Self {
+        Self::with_config(
+            manager_handle,
+            MemberConfig {
+                speed_factor,
+                group: Some(group.to_string()),
+                ..Default::default()
+            },
+        )
+    }
+
+    /// adds a new tick member to the Tick Manager with the given settings
+    pub fn with_config(manager_handle: TickManagerHandle, config: MemberConfig) -> Self {
+        Self::with_role(manager_handle, config)
+    }
+
+    /// like with_config, but returns an error instead of panicking
+    pub fn try_with_config(
+        manager_handle: TickManagerHandle,
+        config: MemberConfig,
+    ) -> Result<Self, TickError> {
+        Self::try_with_role(manager_handle, config)
+    }
+}
+
+impl<R: Role> TickMember<R> {
+    /// adds a new tick member with the role R, e.g. `TickMember::<Observer>::with_role`.
+    /// the kind in the config is replaced by the one of the role
+    pub fn with_role(manager_handle: TickManagerHandle, config: MemberConfig) -> Self {
+        Self::try_with_role(manager_handle, config)
+            .unwrap_or_else(|e| panic!("Could not register the member: {}", e))
+    }
+
+    /// like with_role, but returns an error instead of panicking if the Tick Manager is gone
+    /// or doesn't reply in time
+    pub fn try_with_role(
+        manager_handle: TickManagerHandle,
+        mut config: MemberConfig,
+    ) -> Result<Self, TickError> {
+        config.kind = R::KIND;
+        let registration = register(&manager_handle, &config)?;
+        Ok(Self::registered(manager_handle, config, registration))
+    }
+
+    fn registered(
+        manager_handle: TickManagerHandle,
+        config: MemberConfig,
+        registration: Registration,
+    ) -> Self {
+        let Registration {
+            id,
+            receiver,
+            state_cell,
+            bootstrap,
+            active_from,
+        } = registration;
+        let wake_slot = (config.wake == WakeStrategy::Park).then(Arc::default);
+        Self {
+            id,
+            manager_handle: Arc::new(Mutex::new(manager_handle)),
+            receiver,
+            state_cell,
+            unregistered: Arc::new(AtomicBool::new(false)),
+            config: Arc::new(Mutex::new(config)),
+            bootstrap,
+            active_from,
+            latest_tick: Arc::new(Mutex::new(None)),
+            slice_end: Arc::new(Mutex::new(None)),
+            inbox: Arc::new(Mutex::new(Vec::new())),
+            topic_inbox: Arc::new(Mutex::new(Vec::new())),
+            wake_slot,
+            parked_on: Arc::new(Mutex::new(None)),
+            role: PhantomData,
+        }
+    }
+
+    /// moves this member to another Tick Manager with the same settings.
+    /// it is registered there before leaving the current one, so the next wait uses the new manager.
+    /// the member gets a new id from the new manager.
+    /// if the new manager doesn't take it, the member stays registered with the current one
+    pub fn migrate(&mut self, to: &TickManagerHandle) -> Result<(), TickError> {
+        let config = self.config.lock().unwrap().clone();
+        let registration = register(to, &config)?;
+        let _ = self.manager_handle().send(TickCommand::Unregister(self.id));
+        self.id = registration.id;
+        self.bootstrap = registration.bootstrap;
+        self.active_from = registration.active_from;
+        self.receiver = registration.receiver;
+        self.state_cell = registration.state_cell;
+        self.manager_handle = Arc::new(Mutex::new(to.clone()));
+        self.unregistered = Arc::new(AtomicBool::new(false));
+        self.parked_on = Arc::new(Mutex::new(None));
+        Ok(())
+    }
+
+    /// the latest frame when this member registered, only set if MemberConfig::bootstrap was set
+    /// and the Tick Manager had already started a frame
+    pub fn bootstrap_info(&self) -> Option<TickInfo> {
+        self.bootstrap
+    }
+
+    /// the name the member was registered with
+    pub fn name(&self) -> Option<String> {
+        self.config.lock().unwrap().name.clone()
+    }
+
+    /// the context the member was registered with, None without one or if it isn't a T
+    pub fn context<T: Any + Send + Sync>(&self) -> Option<Arc<T>> {
+        let context = self.config.lock().unwrap().context.clone()?;
+        context.downcast().ok()
+    }
+
+    /// identifies this member in log lines, like the descriptors the Tick Manager puts in its events
+    pub fn descriptor(&self) -> MemberDescriptor {
+        let config = self.config.lock().unwrap();
+        MemberDescriptor {
+            id: self.id,
+            name: config.name.clone(),
+            factor: config.speed_factor,
+            group: config.group.clone(),
+        }
+    }
+
+    /// the first main frame this member takes part in
+    pub fn active_from(&self) -> u64 {
+        self.active_from
+    }
+
+    /// false once the Tick Manager removed this member, e.g. through unregister_group
+    pub fn is_registered(&self) -> bool {
+        !self.unregistered.load(Ordering::Acquire)
+    }
+
+    /// changes how many main frames pass between ticks of this member
+    pub fn set_speed_factor(&self, speed_factor: SpeedFactor) {
+        self.config.lock().unwrap().speed_factor = speed_factor;
+        let _ = self
+            .manager_handle()
+            .send(TickCommand::SetSpeedFactor(self.id, speed_factor));
+    }
+
+    /// moves this member to another phase within its speed factor, to stagger it against
+    /// members with the same factor. with factor 4 and offset 2 it ticks on frames 2, 6, 10..
+    pub fn set_offset(&self, offset: u64) {
+        {
+            let mut config = self.config.lock().unwrap();
+            config.offset = offset;
+            config.auto_offset = false;
+        }
+        let _ = self
+            .manager_handle()
+            .send(TickCommand::SetOffset(self.id, offset));
+    }
+
+    /// tells the Tick Manager that the current tick runs until the next due frame of this member,
+    /// like MemberConfig::spread_work for a single tick. it isn't counted towards the frame
+    /// utilization and a TickEvent::LeaseOverrun is emitted if it's still running then.
+    /// returns that frame, or None if the member isn't mid-tick
+    pub fn lease_until_next_due(&self) -> Option<u64> {
+        let (sender, receiver) = flume::bounded(1);
+        self.manager_handle()
+            .send(TickCommand::Lease(self.id, sender))
+            .ok()?;
+        receiver.recv().ok()?
+    }
+
+    /// waits for the next tick, will only continue if all members are in the Finished state.
+    /// returns the frame of the tick, or None right away once the member is no longer registered
+    pub fn wait_for_tick(&self) -> Option<TickInfo> {
+        if !self.is_registered() {
+            return None;
+        }
+        self.report_finished();
+        while !self.on_wait_reply(self.next_reply(Some(Instant::now() + Duration::from_secs(1)))) {}
+        self.received_tick()
+    }
+
+    /// like wait_for_tick, but gives up after the timeout with TickError::Timeout.
+    /// the member stays Finished, so a later wait picks up the tick that didn't arrive in time.
+    /// fails with TickError::Unregistered once the member is no longer registered
+    pub fn wait_for_tick_timeout(&self, timeout: Duration) -> Result<TickInfo, TickError> {
+        self.wait_for_tick_until(Instant::now() + timeout)
+    }
+
+    /// reports the current tick as done and returns the next one if it already arrived,
+    /// never blocks. None if there is no tick yet or the member is no longer registered
+    pub fn try_wait_for_tick(&self) -> Option<TickInfo> {
+        self.wait_for_tick_until(Instant::now()).ok()
+    }
+
+    fn wait_for_tick_until(&self, deadline: Instant) -> Result<TickInfo, TickError> {
+        if !self.is_registered() {
+            return Err(TickError::Unregistered);
+        }
+        self.report_finished();
+        loop {
+            match self.next_reply(Some(deadline)) {
+                Err(RecvTimeoutError::Timeout) => return Err(TickError::Timeout),
+                reply => {
+                    if self.on_wait_reply(reply) {
+                        return self.received_tick().ok_or(TickError::Unregistered);
+                    }
+                }
+            }
+        }
+    }
+
+    /// the tick a finished wait returned with, None if it ended because the member was removed
+    pub(crate) fn received_tick(&self) -> Option<TickInfo> {
+        if self.is_registered() {
+            self.tick_info()
+        } else {
+            None
+        }
+    }
+
+    /// handles a reply while waiting for the next tick, true once the wait is over
+    pub(crate) fn on_wait_reply(&self, reply: Result<TickStateReply, RecvTimeoutError>) -> bool {
+        match reply {
+            Ok(TickStateReply::Tick(info)) => {
+                self.received(info, None);
+                true
+            }
+            Ok(TickStateReply::Slice(info, budget)) => {
+                self.received(info, Some(budget));
+                true
+            }
+            Ok(TickStateReply::SwitchedTo(handle)) => {
+                self.switch_to(handle);
+                false
+            }
+            Ok(TickStateReply::Mail(message)) => {
+                self.inbox.lock().unwrap().push(message);
+                false
+            }
+            Ok(TickStateReply::Topic(message)) => {
+                self.topic_inbox.lock().unwrap().push(message);
+                false
+            }
+            Ok(TickStateReply::Unregistered | TickStateReply::Cancelled)
+            | Err(RecvTimeoutError::Disconnected) => {
+                self.unregistered.store(true, Ordering::Release);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// sleeps through all frames before `frame`, the manager doesn't dispatch or wait on
+    /// this member until then. returns on the first frame at or after `frame` the member is due
+    pub fn wait_until_tick(&self, frame: u64) -> Option<TickInfo> {
+        let _ = self
+            .manager_handle()
+            .send(TickCommand::SleepUntil(self.id, frame));
+        self.wait_for_tick()
+    }
+
+    /// lets the next `ticks` ticks of this member pass without waking it, then waits for the one after
+    pub fn skip_ticks(&self, ticks: u64) -> Option<TickInfo> {
+        let _ = self
+            .manager_handle()
+            .send(TickCommand::SkipTicks(self.id, ticks));
+        self.wait_for_tick()
+    }
+
+    /// the ticks of this member as a TickStream
+    pub fn ticks(&self) -> MemberTicks<'_, R> {
+        MemberTicks::new(self)
+    }
+
+    /// observers don't report their state, the Tick Manager ignores it
+    pub(crate) fn report_finished(&self) {
+        self.park_here();
+        if R::KIND != MemberKind::Observer {
+            self.state_cell.set(MemberState::Finished);
+        }
+    }
+
+    /// time left of the slice granted with the current tick, None unless this is a background
+    /// member, see MemberConfig::budget
+    pub fn budget_remaining(&self) -> Option<Duration> {
+        self.slice_end
+            .lock()
+            .unwrap()
+            .map(|end| end.saturating_duration_since(Instant::now()))
+    }
+
+    /// whether the slice granted with the current tick is used up, always false for
+    /// members without a budget
+    pub fn is_budget_exhausted(&self) -> bool {
+        self.budget_remaining().is_some_and(|left| left.is_zero())
+    }
+
+    /// sends the payload to another member of the same Tick Manager, it arrives together with the
+    /// next tick of that member. messages to members that are gone are dropped
+    pub fn send_to(
+        &self,
+        member_id: MemberID,
+        payload: impl Any + Send,
+    ) -> Result<(), flume::SendError<TickCommand>> {
+        self.manager_handle()
+            .send(TickCommand::SendTo(self.id, member_id, Box::new(payload)))
+    }
+
+    /// takes the messages that arrived with the ticks so far, in the order they were sent
+    pub fn messages(&self) -> Vec<Message> {
+        std::mem::take(&mut *self.inbox.lock().unwrap())
+    }
+
+    /// receive the messages published on the topic from now on, together with the next tick
+    /// after the frame they were published in
+    pub fn subscribe(&self, topic: &str) -> Result<(), flume::SendError<TickCommand>> {
+        self.manager_handle()
+            .send(TickCommand::Subscribe(self.id, topic.to_string()))
+    }
+
+    pub fn unsubscribe(&self, topic: &str) -> Result<(), flume::SendError<TickCommand>> {
+        self.manager_handle()
+            .send(TickCommand::Unsubscribe(self.id, topic.to_string()))
+    }
+
+    /// sends the payload to every subscriber of the topic, including this member if it subscribed.
+    /// it is visible to them with their next tick, never within the current frame
+    pub fn publish(
+        &self,
+        topic: &str,
+        payload: impl Any + Send + Sync,
+    ) -> Result<(), flume::SendError<TickCommand>> {
+        self.manager_handle().send(TickCommand::Publish(
+            self.id,
+            topic.to_string(),
+            Arc::new(payload),
+        ))
+    }
+
+    /// takes the topic messages that arrived with the ticks so far, in the order they were published
+    pub fn topic_messages(&self) -> Vec<TopicMessage> {
+        std::mem::take(&mut *self.topic_inbox.lock().unwrap())
+    }
+
+    /// the frame of the latest tick this member received
+    pub fn tick_info(&self) -> Option<TickInfo> {
+        *self.latest_tick.lock().unwrap()
+    }
+
+    fn received(&self, info: TickInfo, budget: Option<Duration>) {
+        *self.latest_tick.lock().unwrap() = Some(info);
+        if R::KIND != MemberKind::Observer {
+            self.state_cell.took(info.tick_number);
+        }
+        if let Some(budget) = budget {
+            *self.slice_end.lock().unwrap() = Some(Instant::now() + budget);
+        }
+    }
+
+    /// tells the Tick Manager which thread to unpark, if this member parks and waits on a
+    /// different thread than before
+    /// reports Finished without parking, so the ticks come over the channel even for
+    /// WakeStrategy::Park. for waits that don't block a thread, returns the channel they come over
+    #[cfg(feature = "async")]
+    pub(crate) fn report_finished_unparked(&self) -> &Receiver<TickStateReply> {
+        if self.wake_slot.is_some() && self.parked_on.lock().unwrap().take().is_some() {
+            let _ = self.manager_handle().send(TickCommand::Unpark(self.id));
+        }
+        if R::KIND != MemberKind::Observer {
+            self.state_cell.set(MemberState::Finished);
+        }
+        &self.receiver
+    }
+
+    fn park_here(&self) {
+        let Some(slot) = &self.wake_slot else {
+            return;
+        };
+        let mut parked_on = self.parked_on.lock().unwrap();
+        let current = thread::current();
+        if *parked_on != Some(current.id()) {
+            *parked_on = Some(current.id());
+            let _ = self
+                .manager_handle()
+                .send(TickCommand::Park(self.id, current, slot.clone()));
+        }
+    }
+
+    /// the next reply until the deadline, a tick in the wake slot of a parking member counts
+    /// as a Tick reply
+    fn next_reply(&self, deadline: Option<Instant>) -> Result<TickStateReply, RecvTimeoutError> {
+        let Some(slot) = &self.wake_slot else {
+            return match deadline {
+                Some(deadline) => self.receiver.recv_deadline(deadline),
+                None => self
+                    .receiver
+                    .recv()
+                    .map_err(|_| RecvTimeoutError::Disconnected),
+            };
+        };
+        loop {
+            let seen = slot.sequence();
+            // mail is sent before the tick is put in the slot, so it is handed out first
+            let tick = slot.take();
+            match self.receiver.try_recv() {
+                Ok(reply) => {
+                    if let Some(info) = tick {
+                        slot.restore(info);
+                    }
+                    return Ok(reply);
+                }
+                Err(TryRecvError::Disconnected) => return Err(RecvTimeoutError::Disconnected),
+                Err(TryRecvError::Empty) => {}
+            }
+            if let Some(info) = tick {
+                return Ok(TickStateReply::Tick(info));
+            }
+            // waiting is only cut short to notice a Tick Manager that shut down
+            let mut timeout = Duration::from_millis(100);
+            if let Some(deadline) = deadline {
+                let left = deadline.saturating_duration_since(Instant::now());
+                if left.is_zero() {
+                    return Err(RecvTimeoutError::Timeout);
+                }
+                timeout = timeout.min(left);
+            }
+            slot.wait(seen, timeout);
+        }
+    }
+
+    fn manager_handle(&self) -> TickManagerHandle {
+        self.manager_handle.lock().unwrap().clone()
+    }
+
+    /// the Tick Manager handed this member over, it keeps its id
+    fn switch_to(&self, handle: TickManagerHandle) {
+        *self.manager_handle.lock().unwrap() = handle;
+        *self.parked_on.lock().unwrap() = None;
+        self.park_here();
+    }
+
+    /// waits for a Tick reply without reporting Finished first
+    pub(crate) fn recv_tick(&self, timeout: Option<Duration>) -> Result<(), RecvTimeoutError> {
+        let deadline = timeout.map(|t| Instant::now() + t);
+        loop {
+            match self.next_reply(deadline) {
+                Ok(TickStateReply::Tick(info)) => {
+                    self.received(info, None);
+                    return Ok(());
+                }
+                Ok(TickStateReply::Slice(info, budget)) => {
+                    self.received(info, Some(budget));
+                    return Ok(());
+                }
+                Ok(TickStateReply::SwitchedTo(handle)) => self.switch_to(handle),
+                Ok(TickStateReply::Mail(message)) => self.inbox.lock().unwrap().push(message),
+                Ok(TickStateReply::Topic(message)) => {
+                    self.topic_inbox.lock().unwrap().push(message)
+                }
+                Ok(TickStateReply::Unregistered | TickStateReply::Cancelled)
+                | Err(RecvTimeoutError::Disconnected) => {
+                    self.unregistered.store(true, Ordering::Release);
+                    return Err(RecvTimeoutError::Disconnected);
+                }
+                Ok(_) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+impl<R: Participant> TickMember<R> {
+    /// sets the state of the Tick Member. no command is sent, the Tick Manager sees the new
+    /// state before it checks whether the next frame can start
+    pub fn set_state(&self, state: MemberState) {
+        self.state_cell.set(state);
+    }
+
+    /// acknowledges the current tick without waiting for the next one, which lets a
+    /// TickManagerBuilder::lockstep manager start its next frame. same as setting Finished
+    pub fn ack(&self) {
+        self.state_cell.set(MemberState::Finished);
+    }
+
+    /// the state as the Tick Manager will see it at the next barrier check
+    pub fn state(&self) -> MemberState {
+        self.state_cell.get()
+    }
+
+    /// reserves the main frame with the given tick number exclusively for this member.
+    /// during that frame only this member is dispatched and waited on, the others skip it.
+    /// returns false if the frame already started or is reserved by another member
+    pub fn reserve_exclusive(&self, frame: u64) -> bool {
+        let (sender, receiver) = flume::bounded(1);
+        if self
+            .manager_handle()
+            .send(TickCommand::ReserveExclusive(self.id, frame, sender))
+            .is_err()
+        {
+            return false;
+        }
+        receiver.recv().unwrap_or(false)
+    }
+
+    /// runs `handler` once per tick until it returns false or the member is unregistered.
+    /// panics inside the handler are caught and reported as TickEvent::MemberPanicked,
+    /// the member is marked Finished so the other members aren't held back
+    pub fn catch_ticks(&self, on_panic: PanicAction, mut handler: impl FnMut() -> bool) {
+        loop {
+            self.wait_for_tick();
+            if !self.is_registered() {
+                return;
+            }
+            match panic::catch_unwind(AssertUnwindSafe(&mut handler)) {
+                Ok(true) => continue,
+                Ok(false) => return,
+                Err(payload) => {
+                    let message = panic_message(payload.as_ref());
+                    let unregister = on_panic == PanicAction::Unregister;
+                    let _ = self
+                        .manager_handle()
+                        .send(TickCommand::ReportPanic(self.id, message, unregister));
+                    if unregister {
+                        self.unregistered.store(true, Ordering::Release);
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    /// runs `work` in small steps, as often as fits into the slice of each tick, then yields
+    /// until the next one. returns once `work` returns false or the member is unregistered.
+    /// members without a budget run a single step per tick
+    pub fn run_sliced(&self, mut work: impl FnMut() -> bool) {
+        loop {
+            self.wait_for_tick();
+            if !self.is_registered() {
+                return;
+            }
+            loop {
+                if !work() {
+                    return;
+                }
+                if self.budget_remaining().is_none_or(|left| left.is_zero()) {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+struct Registration {
+    id: HookID,
+    receiver: Receiver<TickStateReply>,
+    state_cell: Arc<StateCell>,
+    bootstrap: Option<TickInfo>,
+    active_from: u64,
+}
+
+/// registers a new member and waits for its id and bootstrap frame
+fn register(
+    manager_handle: &TickManagerHandle,
+    config: &MemberConfig,
+) -> Result<Registration, TickError> {
+    let (sender, receiver) = flume::bounded(10);
+    manager_handle.send(TickCommand::Register(sender, Box::new(config.clone())))?;
+    await_registration(receiver, config)
+}
+
+/// waits for the replies to a Register command
+fn await_registration(
+    receiver: Receiver<TickStateReply>,
+    config: &MemberConfig,
+) -> Result<Registration, TickError> {
+    let (id, active_from, state_cell) = match expect_reply(&receiver)? {
+        TickStateReply::SelfID(id, active_from, state_cell) => (id, active_from, state_cell),
+        TickStateReply::Rejected(error) => return Err(error),
+        unexpected => {
+            return Err(TickError::UnexpectedReply(format!("{:?}", unexpected)));
+        }
+    };
+    let bootstrap = if config.bootstrap {
+        match expect_reply(&receiver)? {
+            TickStateReply::Bootstrap(info) => info,
+            unexpected => {
+                return Err(TickError::UnexpectedReply(format!("{:?}", unexpected)));
+            }
+        }
+    } else {
+        None
+    };
+    Ok(Registration {
+        id,
+        receiver,
+        state_cell,
+        bootstrap,
+        active_from,
+    })
+}
+
+/// a member registered through a CommandBuffer, it exists once the buffer was applied
+#[derive(Debug)]
+pub struct PendingMember<R: Role = Simulation> {
+    manager_handle: TickManagerHandle,
+    config: MemberConfig,
+    receiver: Receiver<TickStateReply>,
+    role: PhantomData<R>,
+}
+
+impl<R: Role> PendingMember<R> {
+    pub(crate) fn new(
+        manager_handle: TickManagerHandle,
+        config: MemberConfig,
+        receiver: Receiver<TickStateReply>,
+    ) -> Self {
+        Self {
+            manager_handle,
+            config,
+            receiver,
+            role: PhantomData,
+        }
+    }
+
+    /// waits until the buffer was applied at the start of the next frame, times out like a
+    /// direct registration if that takes longer than a second
+    pub fn wait(self) -> Result<TickMember<R>, TickError> {
+        let registration = await_registration(self.receiver, &self.config)?;
+        Ok(TickMember::registered(
+            self.manager_handle,
+            self.config,
+            registration,
+        ))
+    }
+}
+
+fn expect_reply(
+    receiver: &Receiver<TickStateReply>,
+) -> Result<TickStateReply, flume::RecvTimeoutError> {
+    // TODO: check if lower times work reliably
+    receiver.recv_timeout(Duration::from_secs(1))
+}
+
+impl<R: Role> Drop for TickMember<R> {
+    fn drop(&mut self) {
+        // Don't panic if the manager is already gone
+        let _ = self.manager_handle().send(TickCommand::Unregister(self.id));
+    }
+}
@@ -0,0 +1,104 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use crate::{TickClock, TickManagerHandle};
+
+/// jobs handed from producers to members one main frame later.
+/// producers push at any time, the jobs pushed during a frame are sealed into the batch once the
+/// next frame starts, and members only pop from the sealed batch. so work submitted during frame
+/// n is processed from frame n + 1 on, no matter when the members run.
+/// sealed jobs that weren't popped stay in the batch, ahead of the next sealed ones
+pub struct TickQueue<T> {
+    state: Arc<Mutex<QueueState<T>>>,
+    clock: Arc<TickClock>,
+}
+
+struct QueueState<T> {
+    sealed: VecDeque<T>,
+    pending: VecDeque<T>,
+    /// the frame the pending jobs were pushed during
+    pending_frame: u64,
+}
+
+impl<T> QueueState<T> {
+    /// seals the pending jobs once a later frame started
+    fn seal(&mut self, tick_number: u64) {
+        if tick_number > self.pending_frame {
+            self.sealed.append(&mut self.pending);
+            self.pending_frame = tick_number;
+        }
+    }
+}
+
+impl<T> TickQueue<T> {
+    /// a queue sealed by the frames of the Tick Manager
+    pub fn new(manager_handle: &TickManagerHandle) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(QueueState {
+                sealed: VecDeque::new(),
+                pending: VecDeque::new(),
+                pending_frame: 0,
+            })),
+            clock: manager_handle.clock(),
+        }
+    }
+
+    /// locks the state with every frame that started since sealed
+    fn state(&self) -> std::sync::MutexGuard<'_, QueueState<T>> {
+        let mut state = self.state.lock().unwrap();
+        // read while locked, so a push is either before or after the frame boundary for everyone
+        state.seal(self.clock.tick_number());
+        state
+    }
+
+    /// adds a job to the batch of the current frame
+    pub fn push(&self, job: T) {
+        self.state().pending.push_back(job);
+    }
+
+    /// the oldest sealed job
+    pub fn pop_front(&self) -> Option<T> {
+        self.state().sealed.pop_front()
+    }
+
+    /// the newest sealed job
+    pub fn pop_back(&self) -> Option<T> {
+        self.state().sealed.pop_back()
+    }
+
+    /// takes all sealed jobs, oldest first
+    pub fn drain_sealed(&self) -> Vec<T> {
+        self.state().sealed.drain(..).collect()
+    }
+
+    /// jobs members can pop right now
+    pub fn sealed_len(&self) -> usize {
+        self.state().sealed.len()
+    }
+
+    /// jobs pushed during the current frame, sealed once the next one starts
+    pub fn pending_len(&self) -> usize {
+        self.state().pending.len()
+    }
+}
+
+impl<T> Clone for TickQueue<T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            clock: self.clock.clone(),
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for TickQueue<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.state();
+        f.debug_struct("TickQueue")
+            .field("sealed", &state.sealed.len())
+            .field("pending", &state.pending.len())
+            .finish()
+    }
+}
@@ -0,0 +1,244 @@
+use flume::{Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+use crate::{Role, Simulation, TickMember};
+
+/// result of waiting on a TickStream
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StreamItem<T> {
+    Item(T),
+    TimedOut,
+    /// the Tick Manager has shut down, no more items will arrive
+    Closed,
+}
+
+/// a blocking stream of ticks that can be adapted with combinators
+pub trait TickStream {
+    type Item;
+
+    /// waits for the next item, for at most `timeout` if one is given
+    fn next_timeout(&mut self, timeout: Option<Duration>) -> StreamItem<Self::Item>;
+
+    /// only passes every nth item, the skipped ones are consumed right away
+    fn throttle(self, n: usize) -> Throttle<Self>
+    where
+        Self: Sized,
+    {
+        Throttle {
+            inner: self,
+            n: n.max(1),
+            skipped: 0,
+        }
+    }
+
+    /// holds back items until the stream has been idle for `idle`, then yields the last one
+    fn debounce_until_idle(self, idle: Duration) -> DebounceUntilIdle<Self>
+    where
+        Self: Sized,
+    {
+        DebounceUntilIdle {
+            inner: self,
+            idle,
+            pending: None,
+            closed: false,
+        }
+    }
+
+    /// pairs every item with the latest value received on `receiver` so far
+    fn sample_latest<T: Clone>(self, receiver: Receiver<T>) -> SampleLatest<Self, T>
+    where
+        Self: Sized,
+    {
+        SampleLatest {
+            inner: self,
+            receiver,
+            latest: None,
+        }
+    }
+}
+
+fn remaining(deadline: Option<Instant>) -> Option<Duration> {
+    deadline.map(|d| d.saturating_duration_since(Instant::now()))
+}
+
+/// blocks on the stream until an item arrives or it is closed
+fn next_blocking<S: TickStream>(stream: &mut S) -> Option<S::Item> {
+    loop {
+        match stream.next_timeout(None) {
+            StreamItem::Item(item) => return Some(item),
+            StreamItem::TimedOut => continue,
+            StreamItem::Closed => return None,
+        }
+    }
+}
+
+/// the ticks of a single TickMember, yields the number of ticks received through this stream
+#[derive(Debug)]
+pub struct MemberTicks<'a, R: Role = Simulation> {
+    member: &'a TickMember<R>,
+    received: u64,
+    /// whether Finished was already reported for the tick we are waiting on
+    awaiting: bool,
+}
+
+impl<'a, R: Role> MemberTicks<'a, R> {
+    pub(crate) fn new(member: &'a TickMember<R>) -> Self {
+        Self {
+            member,
+            received: 0,
+            awaiting: false,
+        }
+    }
+}
+
+impl<R: Role> TickStream for MemberTicks<'_, R> {
+    type Item = u64;
+
+    fn next_timeout(&mut self, timeout: Option<Duration>) -> StreamItem<u64> {
+        if !self.awaiting {
+            self.member.report_finished();
+            self.awaiting = true;
+        }
+        match self.member.recv_tick(timeout) {
+            Ok(()) => {
+                self.awaiting = false;
+                self.received += 1;
+                StreamItem::Item(self.received)
+            }
+            Err(RecvTimeoutError::Timeout) => StreamItem::TimedOut,
+            Err(RecvTimeoutError::Disconnected) => StreamItem::Closed,
+        }
+    }
+}
+
+impl<R: Role> Iterator for MemberTicks<'_, R> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        next_blocking(self)
+    }
+}
+
+#[derive(Debug)]
+pub struct Throttle<S> {
+    inner: S,
+    n: usize,
+    skipped: usize,
+}
+
+impl<S: TickStream> TickStream for Throttle<S> {
+    type Item = S::Item;
+
+    fn next_timeout(&mut self, timeout: Option<Duration>) -> StreamItem<S::Item> {
+        let deadline = timeout.map(|t| Instant::now() + t);
+        loop {
+            match self.inner.next_timeout(remaining(deadline)) {
+                StreamItem::Item(item) => {
+                    self.skipped += 1;
+                    if self.skipped == self.n {
+                        self.skipped = 0;
+                        return StreamItem::Item(item);
+                    }
+                }
+                other => return other,
+            }
+        }
+    }
+}
+
+impl<S: TickStream> Iterator for Throttle<S> {
+    type Item = S::Item;
+
+    fn next(&mut self) -> Option<S::Item> {
+        next_blocking(self)
+    }
+}
+
+#[derive(Debug)]
+pub struct DebounceUntilIdle<S: TickStream> {
+    inner: S,
+    idle: Duration,
+    pending: Option<S::Item>,
+    closed: bool,
+}
+
+impl<S: TickStream> TickStream for DebounceUntilIdle<S> {
+    type Item = S::Item;
+
+    fn next_timeout(&mut self, timeout: Option<Duration>) -> StreamItem<S::Item> {
+        if self.closed {
+            return match self.pending.take() {
+                Some(item) => StreamItem::Item(item),
+                None => StreamItem::Closed,
+            };
+        }
+        let deadline = timeout.map(|t| Instant::now() + t);
+        loop {
+            let outer = remaining(deadline);
+            if self.pending.is_none() {
+                match self.inner.next_timeout(outer) {
+                    StreamItem::Item(item) => self.pending = Some(item),
+                    other => return other,
+                }
+                continue;
+            }
+
+            // wait for the quiet period, but never past the callers deadline
+            let wait = match outer {
+                Some(outer) if outer < self.idle => outer,
+                _ => self.idle,
+            };
+            match self.inner.next_timeout(Some(wait)) {
+                StreamItem::Item(item) => self.pending = Some(item),
+                StreamItem::TimedOut if wait == self.idle => {
+                    return StreamItem::Item(self.pending.take().unwrap());
+                }
+                StreamItem::TimedOut => return StreamItem::TimedOut,
+                StreamItem::Closed => {
+                    self.closed = true;
+                    return StreamItem::Item(self.pending.take().unwrap());
+                }
+            }
+        }
+    }
+}
+
+impl<S: TickStream> Iterator for DebounceUntilIdle<S> {
+    type Item = S::Item;
+
+    fn next(&mut self) -> Option<S::Item> {
+        next_blocking(self)
+    }
+}
+
+#[derive(Debug)]
+pub struct SampleLatest<S, T> {
+    inner: S,
+    receiver: Receiver<T>,
+    latest: Option<T>,
+}
+
+impl<S: TickStream, T: Clone> TickStream for SampleLatest<S, T> {
+    type Item = (S::Item, Option<T>);
+
+    fn next_timeout(&mut self, timeout: Option<Duration>) -> StreamItem<Self::Item> {
+        match self.inner.next_timeout(timeout) {
+            StreamItem::Item(item) => {
+                if let Some(value) = self.receiver.try_iter().last() {
+                    self.latest = Some(value);
+                }
+                StreamItem::Item((item, self.latest.clone()))
+            }
+            StreamItem::TimedOut => StreamItem::TimedOut,
+            StreamItem::Closed => StreamItem::Closed,
+        }
+    }
+}
+
+impl<S: TickStream, T: Clone> Iterator for SampleLatest<S, T> {
+    type Item = (S::Item, Option<T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_blocking(self)
+    }
+}
@@ -0,0 +1,66 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::{TickClock, TickError, TickInfo, TickManagerHandle};
+
+/// reads the latest main frame and waits for newer ones without being a member,
+/// for readers like UI threads that only need the freshest state. cloned watches are independent
+#[derive(Debug, Clone)]
+pub struct TickWatch {
+    pub(crate) clock: Arc<TickClock>,
+    /// the frame number last returned by changed
+    pub(crate) seen: u64,
+}
+
+impl TickWatch {
+    /// the latest main frame, None before the first one
+    pub fn latest(&self) -> Option<TickInfo> {
+        self.clock.latest()
+    }
+
+    /// true if a frame started since changed last returned
+    pub fn has_changed(&self) -> bool {
+        self.clock.tick_number() > self.seen
+    }
+
+    /// blocks until a frame newer than the one last seen started and returns the latest frame.
+    /// frames in between are skipped. TickError::ManagerGone once the Tick Manager shut down
+    /// without a newer frame
+    pub fn changed(&mut self) -> Result<TickInfo, TickError> {
+        let reached = self.clock.wait_for_frame(self.seen + 1);
+        self.seen_after(reached)
+    }
+
+    /// like changed, None if no newer frame started within the timeout or the Tick Manager
+    /// shut down
+    pub fn changed_timeout(&mut self, timeout: Duration) -> Option<TickInfo> {
+        self.clock.wait_for_frame_timeout(self.seen + 1, timeout)?;
+        Some(self.mark_seen())
+    }
+
+    /// the newer frame `reached` by waiting, if any
+    pub(crate) fn seen_after(&mut self, reached: u64) -> Result<TickInfo, TickError> {
+        if reached > self.seen {
+            Ok(self.mark_seen())
+        } else {
+            Err(TickError::ManagerGone)
+        }
+    }
+
+    fn mark_seen(&mut self) -> TickInfo {
+        let info = self
+            .clock
+            .latest()
+            .expect("a frame was published before the clock moved on");
+        self.seen = info.tick_number;
+        info
+    }
+}
+
+impl TickManagerHandle {
+    /// a watch on the latest main frame, it counts the current one as seen
+    pub fn watch(&self) -> TickWatch {
+        let clock = self.clock();
+        let seen = clock.latest().map_or(0, |info| info.tick_number);
+        TickWatch { clock, seen }
+    }
+}
@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+
+use crate::TickEvent;
+
+/// reports collections of the Tick Manager that keep growing, see
+/// TickManagerHandle::set_leak_detection
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeakPolicy {
+    /// main frames between two samples of the MemoryStats
+    pub sample_every: u64,
+    /// a collection is reported once it grew in this many samples in a row
+    pub growth_samples: u32,
+    /// collections smaller than this are never reported
+    pub min_size: usize,
+}
+
+impl Default for LeakPolicy {
+    fn default() -> Self {
+        Self {
+            sample_every: 60,
+            growth_samples: 10,
+            min_size: 64,
+        }
+    }
+}
+
+/// the sizes of everything the Tick Manager keeps around, see TickManagerHandle::memory_stats
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryStats {
+    pub members: usize,
+    pub pending_topology: usize,
+    pub observers: usize,
+    pub event_subscribers: usize,
+    pub tweens: usize,
+    /// timers keyed by frame number
+    pub frame_timers: usize,
+    /// timers keyed by wall clock time
+    pub wall_timers: usize,
+    pub schedules: usize,
+    pub reservations: usize,
+    pub sleeping: usize,
+    pub deferred: usize,
+    /// mail and topic messages waiting for the next tick of their receiver
+    pub mailed: usize,
+    pub topics: usize,
+    /// commands of submitted CommandBuffers
+    pub buffered_commands: usize,
+    pub frame_conditions: usize,
+    /// commands sent to the Tick Manager and not handled yet
+    pub queued_commands: usize,
+    /// replies sent to the members and not received yet
+    pub queued_replies: usize,
+}
+
+impl MemoryStats {
+    /// every collection by name
+    pub fn entries(&self) -> [(&'static str, usize); 17] {
+        [
+            ("members", self.members),
+            ("pending_topology", self.pending_topology),
+            ("observers", self.observers),
+            ("event_subscribers", self.event_subscribers),
+            ("tweens", self.tweens),
+            ("frame_timers", self.frame_timers),
+            ("wall_timers", self.wall_timers),
+            ("schedules", self.schedules),
+            ("reservations", self.reservations),
+            ("sleeping", self.sleeping),
+            ("deferred", self.deferred),
+            ("mailed", self.mailed),
+            ("topics", self.topics),
+            ("buffered_commands", self.buffered_commands),
+            ("frame_conditions", self.frame_conditions),
+            ("queued_commands", self.queued_commands),
+            ("queued_replies", self.queued_replies),
+        ]
+    }
+
+    /// all entries together
+    pub fn total(&self) -> usize {
+        self.entries().iter().map(|(_, size)| size).sum()
+    }
+}
+
+/// how long every collection has been growing, owned by the manager thread
+#[derive(Debug, Default)]
+pub(crate) struct LeakDetector {
+    policy: Option<LeakPolicy>,
+    previous: Option<MemoryStats>,
+    /// samples in a row the collection grew in
+    streaks: HashMap<&'static str, u32>,
+}
+
+impl LeakDetector {
+    pub(crate) fn set_policy(&mut self, policy: Option<LeakPolicy>) {
+        self.previous = None;
+        self.streaks.clear();
+        self.policy = policy;
+    }
+
+    /// whether the stats should be sampled on this frame
+    pub(crate) fn is_sampling(&self, frame: u64) -> bool {
+        self.policy
+            .as_ref()
+            .is_some_and(|policy| frame.is_multiple_of(policy.sample_every.max(1)))
+    }
+
+    /// compares the sample with the previous one, returns an event for every collection that
+    /// has now grown for LeakPolicy::growth_samples samples in a row
+    pub(crate) fn sample(&mut self, stats: MemoryStats) -> Vec<TickEvent> {
+        let Some(policy) = &self.policy else {
+            return Vec::new();
+        };
+        let mut events = Vec::new();
+        if let Some(previous) = &self.previous {
+            for ((collection, size), (_, before)) in
+                stats.entries().into_iter().zip(previous.entries())
+            {
+                let streak = self.streaks.entry(collection).or_default();
+                if size <= before {
+                    *streak = 0;
+                    continue;
+                }
+                *streak += 1;
+                // reported again after every further run of growth_samples
+                if size >= policy.min_size && streak.is_multiple_of(policy.growth_samples.max(1)) {
+                    events.push(TickEvent::UnboundedGrowth { collection, size });
+                }
+            }
+        }
+        self.previous = Some(stats);
+        events
+    }
+}
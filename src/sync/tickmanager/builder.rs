@@ -0,0 +1,183 @@
+use std::sync::Arc;
+
+#[cfg(all(feature = "shm", unix))]
+use crate::ShmPublisher;
+use crate::{
+    Clock, DropPolicy, HiddenPolicy, MonotonicClock, OrderedDispatch, Speed, TickManager,
+    TickManagerHandle,
+};
+
+/// configures a TickManager before its thread is started
+#[derive(Debug, Clone)]
+pub struct TickManagerBuilder {
+    pub(crate) speed: Speed,
+    pub(crate) thread_name: Option<String>,
+    pub(crate) join_at_frame_boundary: bool,
+    pub(crate) batch_topology_changes: bool,
+    pub(crate) balance_offsets: bool,
+    pub(crate) group_barriers: bool,
+    pub(crate) free_run: bool,
+    pub(crate) lockstep: bool,
+    pub(crate) pipeline_depth: Option<u64>,
+    pub(crate) seed: Option<u64>,
+    pub(crate) ordered_dispatch: Option<OrderedDispatch>,
+    pub(crate) fixed_point_time: bool,
+    pub(crate) max_catch_up: Option<u64>,
+    pub(crate) drop_policy: DropPolicy,
+    pub(crate) when_all_hidden: HiddenPolicy,
+    pub(crate) time: Arc<dyn Clock>,
+    #[cfg(all(feature = "shm", unix))]
+    pub(crate) shm: Option<Arc<ShmPublisher>>,
+}
+
+impl TickManagerBuilder {
+    pub fn new(speed: Speed) -> Self {
+        Self {
+            speed,
+            thread_name: None,
+            join_at_frame_boundary: false,
+            batch_topology_changes: false,
+            balance_offsets: false,
+            group_barriers: false,
+            free_run: false,
+            lockstep: false,
+            pipeline_depth: None,
+            seed: None,
+            ordered_dispatch: None,
+            fixed_point_time: false,
+            max_catch_up: None,
+            drop_policy: DropPolicy::Join,
+            when_all_hidden: HiddenPolicy::Emit,
+            time: Arc::new(MonotonicClock),
+            #[cfg(all(feature = "shm", unix))]
+            shm: None,
+        }
+    }
+
+    /// name of the spawned manager thread, defaults to "tick-manager-{id}"
+    pub fn thread_name(mut self, name: impl Into<String>) -> Self {
+        self.thread_name = Some(name.into());
+        self
+    }
+
+    /// only add new members to the member set when the next main frame starts,
+    /// so the set of members never changes while a frame is in progress
+    pub fn join_at_frame_boundary(mut self, enabled: bool) -> Self {
+        self.join_at_frame_boundary = enabled;
+        self
+    }
+
+    /// hold back registrations, unregistrations and speed factor changes until the next
+    /// main frame starts and apply them together, emitting a single TickEvent::TopologyChanged
+    pub fn batch_topology_changes(mut self, enabled: bool) -> Self {
+        self.batch_topology_changes = enabled;
+        self
+    }
+
+    /// give new members the offset that makes them coincide the least with the others,
+    /// so members with the same speed factor are spread across frames instead of all firing
+    /// together. members registered with MemberConfig::auto_offset off keep their offset
+    pub fn balance_offsets(mut self, enabled: bool) -> Self {
+        self.balance_offsets = enabled;
+        self
+    }
+
+    /// let every group of members wait only for its own due members instead of all of them,
+    /// so a slow "audio" group doesn't hold back the ticks of "physics".
+    /// members without a group form a group of their own
+    pub fn group_barriers(mut self, enabled: bool) -> Self {
+        self.group_barriers = enabled;
+        self
+    }
+
+    /// start every frame strictly on schedule and send it to every due member that is ready,
+    /// without a barrier. a member still running misses the tick instead of holding back the
+    /// others, for soft real-time uses like rendering. overrides catch_up's waiting for members
+    pub fn free_run(mut self, enabled: bool) -> Self {
+        self.free_run = enabled;
+        self
+    }
+
+    /// start no frame before every participant acknowledged its latest tick with TickMember::ack
+    /// or by waiting for the next one, even if the frames fall behind the speed.
+    /// for deterministic lockstep simulations, free_run takes precedence
+    pub fn lockstep(mut self, enabled: bool) -> Self {
+        self.lockstep = enabled;
+        self
+    }
+
+    /// pipeline the frames: a participant still running gets the ticks of the next frames queued
+    /// until it has `depth` of them in flight, only then the frames wait for it. decoupled stages
+    /// keep working without waiting on each other, at most `depth - 1` frames behind.
+    /// StateCell::processing tells which frame a member is on
+    pub fn pipeline_depth(mut self, depth: u64) -> Self {
+        self.pipeline_depth = Some(depth.max(1));
+        self
+    }
+
+    /// deliver TickInfo::seed with every main frame, derived from this seed and the frame number.
+    /// the same seed gives the same per frame seeds on every run
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// deliver the ticks of every frame in the order of DispatchKey instead of an unspecified one
+    pub fn ordered_dispatch(mut self, order: OrderedDispatch) -> Self {
+        self.ordered_dispatch = Some(order);
+        self
+    }
+
+    /// compute frame times in integer nanoseconds from the start instead of adding up a float
+    /// period, see FixedTimeline. TickInfo::delta and sim_time are then the exact nominal
+    /// values, identical on every platform, and only wall_time is measured
+    pub fn fixed_point_time(mut self, enabled: bool) -> Self {
+        self.fixed_point_time = enabled;
+        self
+    }
+
+    /// deliver the frames missed while the process was suspended or a frame ran long back to
+    /// back, up to `max_catch_up` of them, instead of starting the pacing over and losing them.
+    /// implies fixed_point_time, so every frame advances the simulated time by exactly one period.
+    /// frames also wait for the members due in them, a member that is still running delays
+    /// the frame instead of missing it. frames beyond the limit are dropped with a
+    /// TickEvent::FramesDropped
+    pub fn catch_up(mut self, max_catch_up: u64) -> Self {
+        self.max_catch_up = Some(max_catch_up);
+        self
+    }
+
+    /// whether dropping the TickManager waits for its thread, by default it joins it.
+    /// in async code TickManager::shutdown_async doesn't block at all
+    pub fn drop_policy(mut self, policy: DropPolicy) -> Self {
+        self.drop_policy = policy;
+        self
+    }
+
+    /// what happens to frames in which every due member is Hidden, by default they start anyway.
+    /// HiddenPolicy::Pause lets the loop go idle while e.g. a menu hides everything
+    pub fn when_all_hidden(mut self, policy: HiddenPolicy) -> Self {
+        self.when_all_hidden = policy;
+        self
+    }
+
+    /// take the time from the clock instead of the real monotonic time, e.g. a MockClock
+    /// to test the pacing without waiting for it
+    pub fn clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.time = Arc::new(clock);
+        self
+    }
+
+    /// publish every main frame into the shared file, so other processes can follow the frames
+    /// with a ShmReader
+    #[cfg(all(feature = "shm", unix))]
+    pub fn publish_to_shm(mut self, publisher: ShmPublisher) -> Self {
+        self.shm = Some(Arc::new(publisher));
+        self
+    }
+
+    /// creates the TickManager and starts its thread
+    pub fn build(self) -> (TickManager, TickManagerHandle) {
+        TickManager::from_builder(self)
+    }
+}
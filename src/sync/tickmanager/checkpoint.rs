@@ -0,0 +1,62 @@
+use std::time::Instant;
+
+use crate::{TickEvent, TickInfo};
+
+/// called on the manager thread at every checkpoint with the frame the checkpoint follows,
+/// while no member is in the middle of a tick. e.g. saves state, computes a checksum
+/// or flushes a trace
+pub type CheckpointHook = Box<dyn FnMut(&TickInfo) + Send>;
+
+/// runs the checkpoint hooks every n main frames, owned by the manager thread
+#[derive(Default)]
+pub(crate) struct Checkpoints {
+    every: Option<u64>,
+    hooks: Vec<CheckpointHook>,
+    /// the frame a checkpoint is waiting to run after, no frames start until it ran
+    pending: Option<TickInfo>,
+}
+
+impl Checkpoints {
+    /// None turns checkpoints off, a pending checkpoint still runs
+    pub(crate) fn set_every(&mut self, every: Option<u64>) {
+        self.every = every.map(|every| every.max(1));
+    }
+
+    pub(crate) fn add_hook(&mut self, hook: CheckpointHook) {
+        self.hooks.push(hook);
+    }
+
+    /// marks a checkpoint as pending if the frame is on the cadence
+    pub(crate) fn frame_started(&mut self, info: &TickInfo) {
+        if self
+            .every
+            .is_some_and(|every| info.tick_number.is_multiple_of(every))
+        {
+            self.pending = Some(*info);
+        }
+    }
+
+    pub(crate) fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// runs the pending checkpoint, the members have to be quiet
+    pub(crate) fn run(&mut self) -> Option<TickEvent> {
+        let info = self.pending.take()?;
+        let start = Instant::now();
+        for hook in &mut self.hooks {
+            hook(&info);
+        }
+        Some(TickEvent::CheckpointCompleted {
+            tick_number: info.tick_number,
+            took: start.elapsed(),
+        })
+    }
+
+    /// takes over the cadence and hooks of a handed over manager
+    pub(crate) fn adopt(&mut self, other: Checkpoints) {
+        self.every = self.every.or(other.every);
+        self.hooks.extend(other.hooks);
+        self.pending = self.pending.or(other.pending);
+    }
+}
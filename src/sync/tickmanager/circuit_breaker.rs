@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+use crate::{MemberID, SpeedFactor, TickEvent};
+
+/// demotes members that keep missing their deadline, so they can't hold back everyone else.
+/// a member misses its deadline when it is due but still Running
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircuitBreakerPolicy {
+    /// misses within the window that trigger a demotion
+    pub max_misses: u32,
+    /// amount of due frames looked at, at most 64
+    pub window: u32,
+    /// the speed factor of a demoted member is multiplied by this
+    pub demote_factor: usize,
+    /// consecutive due frames without a miss needed to restore a member, at most 64
+    pub restore_after: u32,
+}
+
+impl Default for CircuitBreakerPolicy {
+    fn default() -> Self {
+        Self {
+            max_misses: 3,
+            window: 10,
+            demote_factor: 2,
+            restore_after: 30,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct BreakerState {
+    /// one bit per due frame, set if the deadline was missed, newest in the lowest bit
+    history: u64,
+    recorded: u32,
+    /// the original speed factor while demoted
+    demoted_from: Option<SpeedFactor>,
+}
+
+/// the circuit breaker state of all members, owned by the manager thread
+#[derive(Debug, Default)]
+pub(crate) struct CircuitBreaker {
+    policy: Option<CircuitBreakerPolicy>,
+    members: HashMap<MemberID, BreakerState>,
+}
+
+fn mask(bits: u32) -> u64 {
+    match bits {
+        0 => 0,
+        64.. => u64::MAX,
+        bits => (1 << bits) - 1,
+    }
+}
+
+impl CircuitBreaker {
+    /// replaces the policy, restoring all demoted members when it is turned off
+    pub(crate) fn set_policy(
+        &mut self,
+        policy: Option<CircuitBreakerPolicy>,
+        mut set_factor: impl FnMut(MemberID, SpeedFactor),
+    ) -> Vec<TickEvent> {
+        let mut events = Vec::new();
+        if policy.is_none() {
+            for (&id, state) in self.members.iter_mut() {
+                if let Some(factor) = state.demoted_from.take() {
+                    set_factor(id, factor);
+                    events.push(TickEvent::MemberRestored { id, factor });
+                }
+            }
+            self.members.clear();
+        }
+        self.policy = policy;
+        events
+    }
+
+    pub(crate) fn remove(&mut self, id: MemberID) {
+        self.members.remove(&id);
+    }
+
+    /// records whether a due member missed its deadline.
+    /// returns the new speed factor and event if the member got demoted or restored
+    pub(crate) fn record(
+        &mut self,
+        id: MemberID,
+        factor: SpeedFactor,
+        missed: bool,
+    ) -> Option<(SpeedFactor, TickEvent)> {
+        let policy = self.policy.as_ref()?;
+        let state = self.members.entry(id).or_default();
+        state.history = (state.history << 1) | missed as u64;
+        state.recorded = state.recorded.saturating_add(1);
+
+        match state.demoted_from {
+            None => {
+                let misses = (state.history & mask(policy.window)).count_ones();
+                if misses < policy.max_misses.max(1) {
+                    return None;
+                }
+                let demoted = factor.saturating_mul(policy.demote_factor.max(1));
+                *state = BreakerState {
+                    demoted_from: Some(factor),
+                    ..Default::default()
+                };
+                Some((
+                    demoted,
+                    TickEvent::MemberDemoted {
+                        id,
+                        factor: demoted,
+                    },
+                ))
+            }
+            Some(original) => {
+                let clean = state.history & mask(policy.restore_after) == 0;
+                if !clean || state.recorded < policy.restore_after {
+                    return None;
+                }
+                *state = BreakerState::default();
+                Some((
+                    original,
+                    TickEvent::MemberRestored {
+                        id,
+                        factor: original,
+                    },
+                ))
+            }
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use std::fmt;
+
+use crate::{MemberID, MemberInfo, SpeedFactor};
+
+/// identifies a member in log lines, events and errors, see TickMember::descriptor.
+/// displays as `member 3 "physics" (group world, factor 2)`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemberDescriptor {
+    pub id: MemberID,
+    pub name: Option<String>,
+    pub factor: SpeedFactor,
+    pub group: Option<String>,
+}
+
+impl MemberDescriptor {
+    pub(crate) fn of(id: MemberID, factor: SpeedFactor, member_info: &MemberInfo) -> Self {
+        Self {
+            id,
+            name: member_info.name.clone(),
+            factor,
+            group: member_info.group.clone(),
+        }
+    }
+}
+
+impl fmt::Display for MemberDescriptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "member {}", self.id)?;
+        if let Some(name) = &self.name {
+            write!(f, " {:?}", name)?;
+        }
+        match &self.group {
+            Some(group) => write!(f, " (group {}, factor {})", group, self.factor),
+            None => write!(f, " (factor {})", self.factor),
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use std::time::Duration;
+
+use crate::{ManagerID, MemberDescriptor, MemberID, Speed, SpeedFactor};
+
+/// notable things that happened inside the Tick Manager
+#[derive(Debug, Clone, PartialEq)]
+pub enum TickEvent {
+    /// the member kept missing its deadline and now runs with a higher speed factor
+    MemberDemoted {
+        id: MemberID,
+        factor: SpeedFactor,
+    },
+    /// the member behaved again and got its original speed factor back
+    MemberRestored {
+        id: MemberID,
+        factor: SpeedFactor,
+    },
+    /// the member received too few of its ticks and is now woken first
+    MemberBoosted {
+        id: MemberID,
+    },
+    MemberBoostEnded {
+        id: MemberID,
+    },
+    /// the tick handler of the member panicked, see TickMember::catch_ticks
+    MemberPanicked {
+        id: MemberID,
+        message: String,
+    },
+    /// a fallible tick handler of the member returned an error, e.g. a script error,
+    /// see TickManagerHandle::register_fallible
+    MemberFailed {
+        id: MemberID,
+        message: String,
+    },
+    /// the worker of a supervised member was respawned after a panic
+    MemberRestarted {
+        id: MemberID,
+        restarts: u32,
+    },
+    /// a member was still working on its lease when its next due frame started,
+    /// the frame is skipped like for any other member that isn't finished
+    LeaseOverrun {
+        id: MemberID,
+    },
+    /// the batched topology changes applied at the start of `frame`
+    TopologyChanged {
+        frame: u64,
+        added: Vec<MemberID>,
+        removed: Vec<MemberID>,
+        speed_factors: Vec<(MemberID, SpeedFactor)>,
+    },
+    /// the members kept needing longer than the frame period,
+    /// utilization is the average busy time divided by the frame period
+    Overloaded {
+        utilization: f64,
+        /// the degraded speed the manager switched to, if configured
+        switched_to: Option<Speed>,
+    },
+    /// the checkpoint hooks ran after the frame, `took` is the time they needed
+    CheckpointCompleted {
+        tick_number: u64,
+        took: Duration,
+    },
+    /// all members of the group were removed between two frames
+    GroupUnregistered {
+        group: String,
+        members: Vec<MemberID>,
+    },
+    /// a collection of the Tick Manager kept growing, see LeakPolicy.
+    /// `collection` names the field of MemoryStats
+    UnboundedGrowth {
+        collection: &'static str,
+        size: usize,
+    },
+    /// the Tick Manager fell further behind than TickManagerBuilder::catch_up allows,
+    /// `dropped` frames before `frame` are never started
+    FramesDropped {
+        frame: u64,
+        dropped: u64,
+    },
+    /// no member was active for IdlePolicy::idle_after frames up to `frame`
+    Idle {
+        frame: u64,
+    },
+    /// members were active again for IdlePolicy::active_after frames up to `frame`
+    Active {
+        frame: u64,
+    },
+}
+
+impl TickEvent {
+    /// the member the event is about, None for events about the whole Tick Manager or a group
+    pub fn member_id(&self) -> Option<MemberID> {
+        match self {
+            TickEvent::MemberDemoted { id, .. }
+            | TickEvent::MemberRestored { id, .. }
+            | TickEvent::MemberBoosted { id }
+            | TickEvent::MemberBoostEnded { id }
+            | TickEvent::MemberPanicked { id, .. }
+            | TickEvent::MemberFailed { id, .. }
+            | TickEvent::MemberRestarted { id, .. }
+            | TickEvent::LeaseOverrun { id } => Some(*id),
+            TickEvent::TopologyChanged { .. }
+            | TickEvent::Overloaded { .. }
+            | TickEvent::CheckpointCompleted { .. }
+            | TickEvent::GroupUnregistered { .. }
+            | TickEvent::UnboundedGrowth { .. }
+            | TickEvent::FramesDropped { .. }
+            | TickEvent::Idle { .. }
+            | TickEvent::Active { .. } => None,
+        }
+    }
+}
+
+/// a TickEvent together with the Tick Manager it came from
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManagerEvent {
+    pub manager_id: ManagerID,
+    /// the member the event is about as it was registered when the event was emitted
+    pub member: Option<MemberDescriptor>,
+    pub event: TickEvent,
+}
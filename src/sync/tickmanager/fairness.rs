@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+
+use crate::{MemberID, TickEvent};
+
+/// boosts members that receive too few of the ticks they are due for.
+/// boosted members are woken first and can't be demoted by the circuit breaker
+#[derive(Debug, Clone, PartialEq)]
+pub struct StarvationPolicy {
+    /// delivered / expected ratio below which a member counts as starved
+    pub threshold: f64,
+    /// amount of due frames the ratio is measured over
+    pub window: u32,
+    /// amount of due frames a boost lasts
+    pub boost_for: u32,
+}
+
+impl Default for StarvationPolicy {
+    fn default() -> Self {
+        Self {
+            threshold: 0.5,
+            window: 20,
+            boost_for: 60,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct FairnessState {
+    expected: u32,
+    delivered: u32,
+    /// due frames left in the current boost
+    boost_left: u32,
+}
+
+/// the starvation state of all members, owned by the manager thread
+#[derive(Debug, Default)]
+pub(crate) struct Fairness {
+    policy: Option<StarvationPolicy>,
+    members: HashMap<MemberID, FairnessState>,
+}
+
+impl Fairness {
+    pub(crate) fn set_policy(&mut self, policy: Option<StarvationPolicy>) {
+        if policy.is_none() {
+            self.members.clear();
+        }
+        self.policy = policy;
+    }
+
+    pub(crate) fn remove(&mut self, id: MemberID) {
+        self.members.remove(&id);
+    }
+
+    pub(crate) fn is_boosted(&self, id: MemberID) -> bool {
+        self.members.get(&id).is_some_and(|s| s.boost_left > 0)
+    }
+
+    /// records whether a due member got its tick, returns an event when its boost starts or ends
+    pub(crate) fn record(&mut self, id: MemberID, delivered: bool) -> Option<TickEvent> {
+        let policy = self.policy.as_ref()?;
+        let state = self.members.entry(id).or_default();
+
+        if state.boost_left > 0 {
+            state.boost_left -= 1;
+            return (state.boost_left == 0).then_some(TickEvent::MemberBoostEnded { id });
+        }
+
+        state.expected += 1;
+        state.delivered += delivered as u32;
+        if state.expected < policy.window.max(1) {
+            return None;
+        }
+        let ratio = state.delivered as f64 / state.expected as f64;
+        state.expected = 0;
+        state.delivered = 0;
+        if ratio < policy.threshold && policy.boost_for > 0 {
+            state.boost_left = policy.boost_for;
+            return Some(TickEvent::MemberBoosted { id });
+        }
+        None
+    }
+}
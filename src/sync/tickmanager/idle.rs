@@ -0,0 +1,66 @@
+use crate::TickEvent;
+
+/// reports the Tick Manager as dormant once no member was active for a while, so the host can
+/// release resources, see TickEvent::Idle and TickEvent::Active.
+/// a frame is active if one of its due members besides observers wasn't Hidden,
+/// frames held back by HiddenPolicy::Skip or Pause count as inactive ones
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdlePolicy {
+    /// consecutive frames without an active member before TickEvent::Idle
+    pub idle_after: u64,
+    /// consecutive active frames before TickEvent::Active, so a single busy frame
+    /// doesn't wake the host
+    pub active_after: u64,
+}
+
+impl Default for IdlePolicy {
+    fn default() -> Self {
+        Self {
+            idle_after: 60,
+            active_after: 3,
+        }
+    }
+}
+
+/// whether the Tick Manager is idle, owned by the manager thread
+#[derive(Debug, Default)]
+pub(crate) struct IdleTracker {
+    policy: Option<IdlePolicy>,
+    idle: bool,
+    /// consecutive frames that disagreed with the current state
+    streak: u64,
+}
+
+impl IdleTracker {
+    /// a new policy starts out active
+    pub(crate) fn set_policy(&mut self, policy: Option<IdlePolicy>) {
+        self.policy = policy;
+        self.idle = false;
+        self.streak = 0;
+    }
+
+    /// records whether the frame was active, returns an event when the state changes
+    pub(crate) fn record(&mut self, frame: u64, active: bool) -> Option<TickEvent> {
+        let policy = self.policy?;
+        if active != self.idle {
+            self.streak = 0;
+            return None;
+        }
+        self.streak += 1;
+        let needed = if self.idle {
+            policy.active_after
+        } else {
+            policy.idle_after
+        };
+        if self.streak < needed.max(1) {
+            return None;
+        }
+        self.idle = !self.idle;
+        self.streak = 0;
+        Some(if self.idle {
+            TickEvent::Idle { frame }
+        } else {
+            TickEvent::Active { frame }
+        })
+    }
+}
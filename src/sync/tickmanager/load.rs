@@ -0,0 +1,99 @@
+use std::{collections::VecDeque, time::Duration};
+
+use crate::{Speed, TickEvent};
+
+/// called on the manager thread with the rolling utilization after every dispatched frame
+pub type UtilizationHook = Box<dyn FnMut(f64) + Send>;
+
+/// reacts to frames that keep taking longer than the frame period
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverloadPolicy {
+    /// utilization (busy time / frame period) above which a frame counts as overloaded
+    pub threshold: f64,
+    /// amount of dispatched frames the utilization is averaged over
+    pub window: usize,
+    /// speed to switch to once overloaded, None only reports
+    pub degraded_speed: Option<Speed>,
+}
+
+impl Default for OverloadPolicy {
+    fn default() -> Self {
+        Self {
+            threshold: 1.0,
+            window: 30,
+            degraded_speed: None,
+        }
+    }
+}
+
+/// measures how much of each frame period the members spend working, owned by the manager thread.
+/// the busy time of a frame is the time from dispatch until the slowest member reported Finished
+#[derive(Debug, Default)]
+pub(crate) struct LoadMonitor {
+    policy: Option<OverloadPolicy>,
+    /// longest execution time of a member in the current frame
+    frame_busy: Duration,
+    samples: VecDeque<f64>,
+    /// a degraded speed was already switched to
+    degraded: bool,
+}
+
+impl LoadMonitor {
+    pub(crate) fn set_policy(&mut self, policy: Option<OverloadPolicy>) {
+        self.policy = policy;
+        self.samples.clear();
+        self.degraded = false;
+    }
+
+    /// a member finished the tick it was dispatched
+    pub(crate) fn record_member(&mut self, execution: Duration) {
+        self.frame_busy = self.frame_busy.max(execution);
+    }
+
+    /// utilization averaged over the last window, None before the first dispatched frame
+    pub(crate) fn utilization(&self) -> Option<f64> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        Some(self.samples.iter().sum::<f64>() / self.samples.len() as f64)
+    }
+
+    /// closes the previous frame when a new one is dispatched.
+    /// returns the speed to switch to and the event if the manager is overloaded
+    pub(crate) fn end_frame(&mut self, period: Duration) -> Option<(Option<Speed>, TickEvent)> {
+        let busy = std::mem::take(&mut self.frame_busy);
+        if period.is_zero() {
+            return None;
+        }
+        let window = self.policy.as_ref().map_or(30, |p| p.window.max(1));
+        self.samples
+            .push_back(busy.as_secs_f64() / period.as_secs_f64());
+        while self.samples.len() > window {
+            self.samples.pop_front();
+        }
+
+        let policy = self.policy.as_ref()?;
+        if self.samples.len() < window {
+            return None;
+        }
+        let utilization = self.utilization()?;
+        if utilization <= policy.threshold {
+            return None;
+        }
+        // a full new window is needed before reporting again
+        self.samples.clear();
+        let switched_to = if self.degraded {
+            None
+        } else {
+            policy.degraded_speed.clone()
+        };
+        self.degraded |= switched_to.is_some();
+        Some((
+            switched_to.clone(),
+            TickEvent::Overloaded {
+                utilization,
+                switched_to,
+            },
+        ))
+    }
+}
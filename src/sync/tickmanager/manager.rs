@@ -0,0 +1,2374 @@
+use core::fmt;
+use std::{
+    any::Any,
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
+    ops::ControlFlow,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+    },
+    thread::{self, Thread},
+    time::{Duration, Instant},
+};
+
+use flume::{Receiver, Selector, Sender, TrySendError};
+
+#[cfg(all(feature = "shm", unix))]
+use crate::ShmPublisher;
+use crate::{
+    ActiveRetry, ActiveTween, Checkpoints, CircuitBreaker, Clock, ClockDomain, Due, FUTEX_WAKE,
+    Fairness, FixedTimeline, FrameBarrier, FramePacer, FramePredicate, FrameTags, Handover, HookID,
+    IdleTracker, LeakDetector, LoadMonitor, ManagerEvent, ManagerID, MemberDescriptor, MemberID,
+    MemberKind, MemberState, MemberWatch, MemoryStats, Message, OrderedDispatch, Period,
+    ScheduleEntry, ScheduleState, ScheduledMember, ScheduledTimer, Speed, SpeedFactor, SpeedLog,
+    SpeedPlan, StateCell, TenantQuota, TickClock, TickCommand, TickError, TickEvent, TickInfo,
+    TickManagerBuilder, TickManagerHandle, TimerWheel, TopicMessage, UtilizationHook, WakeSlot,
+    balanced_offset, frame_seed, is_due, negotiate_speed, next_due, registry, sorted, summarize,
+};
+
+/// the state that will be sent to the Tick Hooks
+#[derive(Debug)]
+pub enum TickStateReply {
+    /// the id of a new member, the first main frame it takes part in and its state
+    SelfID(HookID, u64, Arc<StateCell>),
+    MemberID(MemberID),
+    /// the latest frame at registration time, None if no frame started yet
+    Bootstrap(Option<TickInfo>),
+    Tick(TickInfo),
+    /// a tick for a background member, it may run for the given time
+    Slice(TickInfo, Duration),
+    /// the member was removed by the Tick Manager and won't receive ticks anymore
+    Unregistered,
+    /// the Tick Manager shut down while the member was still in the middle of a tick,
+    /// see TickManagerHandle::shutdown_with_timeout
+    Cancelled,
+    /// the registration was refused
+    Rejected(TickError),
+    /// the member was handed over to another Tick Manager with the same id
+    SwitchedTo(TickManagerHandle),
+    /// a message from another member, sent right before the tick it arrives with
+    Mail(Message),
+    /// a message published on a topic the member subscribed to, sent like Mail
+    Topic(TopicMessage),
+}
+
+static NEXT_MANAGER_ID: AtomicU64 = AtomicU64::new(0);
+
+/// how often member states are checked while waiting for quiescence, a checkpoint or the
+/// members a frame waits for, without FUTEX_WAKE
+const STATE_POLL_INTERVAL: Duration = Duration::from_micros(100);
+
+/// with FUTEX_WAKE the barrier wakes on every state set and command sent through a handle.
+/// still checked this often for commands sent otherwise and members handed over from another
+/// Tick Manager, whose states notify the barrier they were registered with
+const BARRIER_POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MemberIdentifier {
+    pub hook_id: HookID,
+    pub member_id: MemberID,
+}
+
+impl fmt::Display for MemberIdentifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "MemberIdentifier {{ hook_id: {}, member_id: {} }}",
+            self.hook_id, self.member_id
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MemberInfo {
+    /// the sender to send TickStateReply to the Tick Hook
+    pub sender: Sender<TickStateReply>,
+    pub state: MemberState,
+
+    /// last time this member was ticked
+    pub last_tick: Instant,
+    pub name: Option<String>,
+    pub group: Option<String>,
+    pub tenant: Option<String>,
+    /// phase offset within the speed factor, see is_due
+    pub offset: u64,
+    /// see MemberConfig::priority
+    pub priority: i32,
+    /// see MemberConfig::after
+    pub after: Vec<MemberID>,
+    /// see MemberConfig::phase
+    pub phase: u32,
+    pub kind: MemberKind,
+    /// the work of the member may span the frames until its next due frame
+    pub spread_work: bool,
+    /// the current tick is a lease, it only has to end before the next due frame
+    pub leased: bool,
+    /// the most time the background member may run per tick
+    pub budget: Option<Duration>,
+    /// the state as set by the member, `state` follows it before every barrier check
+    pub state_cell: Arc<StateCell>,
+    /// the thread a parking member waits on and the slot its ticks are put in,
+    /// see WakeStrategy::Park
+    pub parked: Option<(Thread, Arc<WakeSlot>)>,
+}
+
+/// how a member waits for its ticks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WakeStrategy {
+    /// ticks arrive through the member's channel
+    #[default]
+    Channel,
+    /// the member parks its thread and the Tick Manager unparks it directly, which skips the
+    /// channel for plain ticks. slices, mail and topic messages still use the channel.
+    /// with the `futex` feature on Linux, Android, Windows and macOS the member waits on a futex
+    /// instead, see FUTEX_WAKE
+    Park,
+}
+
+/// state attached to a member at registration, see MemberConfig::context
+pub type MemberContext = Arc<dyn Any + Send + Sync>;
+
+/// settings a member is registered with
+#[derive(Clone, Debug)]
+pub struct MemberConfig {
+    pub speed_factor: SpeedFactor,
+    /// a name to tell the member apart in logs and introspection
+    pub name: Option<String>,
+    /// shifts the frames the member is due on, with factor 4 and offset 1 it ticks on frames 1, 5, 9..
+    pub offset: u64,
+    /// let the Tick Manager pick the offset if it balances offsets, see
+    /// TickManagerBuilder::balance_offsets. the offset above is used otherwise
+    pub auto_offset: bool,
+    /// members of a group can be managed together, e.g. unregistered at once
+    pub group: Option<String>,
+    /// the tenant whose TenantQuota the member counts towards
+    pub tenant: Option<String>,
+    /// set from the role of the TickMember
+    pub kind: MemberKind,
+    /// for heavy members with a speed factor above 1: every tick is a lease that only has to end
+    /// before the next due frame, instead of counting as work done within the frame.
+    /// it isn't counted towards the frame utilization, TickEvent::LeaseOverrun reports late ones
+    pub spread_work: bool,
+    /// makes this a background member that receives a time slice of at most this length instead
+    /// of a plain tick, and yields once it is used up. the slice shrinks while the frames are busy
+    pub budget: Option<Duration>,
+    /// receive the latest frame on registration, so late joiners know the current tick and delta
+    pub bootstrap: bool,
+    pub wake: WakeStrategy,
+    /// members with a higher priority are woken first within a frame, e.g. input and audio.
+    /// members of the same priority are woken by id
+    pub priority: i32,
+    /// members that have to finish their tick of a frame before this member gets its own,
+    /// if they are due in the frame as well, e.g. physics before rendering
+    pub after: Vec<MemberID>,
+    /// the phase of the frame the member is ticked in, every member of a phase finishes its tick
+    /// before the members of the next higher phase get theirs, e.g. 0 for input, 1 for the
+    /// update and 2 for rendering
+    pub phase: u32,
+    /// handed to the callback of the member with every tick, so callbacks reach their state
+    /// without globals, see TickManagerHandle::register_with_context. other members read it
+    /// with TickMember::context
+    pub context: Option<MemberContext>,
+}
+
+impl Default for MemberConfig {
+    fn default() -> Self {
+        Self {
+            speed_factor: 1,
+            name: None,
+            offset: 0,
+            auto_offset: true,
+            group: None,
+            tenant: None,
+            kind: MemberKind::Participant,
+            spread_work: false,
+            budget: None,
+            bootstrap: false,
+            wake: WakeStrategy::Channel,
+            priority: 0,
+            after: Vec::new(),
+            phase: 0,
+            context: None,
+        }
+    }
+}
+
+pub(crate) type InternalMap = HashMap<MemberID, (SpeedFactor, MemberInfo)>;
+
+#[derive(Debug)]
+pub struct TickManager {
+    id: ManagerID,
+    thread_name: String,
+    /// name in the process-wide registry, if built with build_unique
+    pub(crate) registered_name: Option<String>,
+    join_at_frame_boundary: bool,
+    batch_topology_changes: bool,
+    balance_offsets: bool,
+    group_barriers: bool,
+    free_run: bool,
+    lockstep: bool,
+    pipeline_depth: Option<u64>,
+    seed: Option<u64>,
+    ordered_dispatch: Option<OrderedDispatch>,
+    fixed_point_time: bool,
+    max_catch_up: Option<u64>,
+    when_all_hidden: HiddenPolicy,
+    /// whether the thread waits for a Handover before starting frames
+    standby: bool,
+    internal_receiver: Receiver<TickCommand>,
+    /// control commands, handled ahead of the ones waiting in internal_receiver
+    control_receiver: Receiver<TickCommand>,
+    /// map of all registered Tick members
+    member_map: Arc<Mutex<InternalMap>>,
+    amount_of_members: Arc<AtomicUsize>,
+    /// time of last main tick
+    instant: Arc<Mutex<Instant>>,
+    /// the speed of the global tick
+    speed: Arc<Speed>,
+    clock: Arc<TickClock>,
+
+    handle: Option<thread::JoinHandle<()>>,
+    /// disconnects once the thread returned
+    exited: Option<Receiver<()>>,
+    drop_policy: DropPolicy,
+    time: Arc<dyn Clock>,
+    #[cfg(all(feature = "shm", unix))]
+    shm: Option<Arc<ShmPublisher>>,
+    /// required to send the Shutdown command on drop
+    control_sender: Sender<TickCommand>,
+    /// shared with the handles and the state cells, see FrameBarrier
+    barrier: Arc<FrameBarrier>,
+}
+
+/// what dropping a TickManager does after asking its thread to shut down
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DropPolicy {
+    /// block until the thread returned
+    #[default]
+    Join,
+    /// block until the thread returned or the timeout passed, the thread is detached after
+    JoinWithTimeout(Duration),
+    /// return right away, the thread shuts down in the background
+    Detach,
+}
+
+/// what the Tick Manager does with a frame in which every due member is Hidden.
+/// observers don't count, frames without due members are always started
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HiddenPolicy {
+    /// start the frame anyway
+    #[default]
+    Emit,
+    /// let the frame pass without counting it, the next one is due a period later
+    Skip,
+    /// stop starting frames, as if paused, until a due member set another state.
+    /// the member states are checked once per frame period
+    Pause,
+}
+
+impl TickManager {
+    pub fn new(speed: Speed) -> (Self, TickManagerHandle) {
+        Self::builder(speed).build()
+    }
+
+    /// a Tick Manager taking the time from the clock, e.g. a MockClock in tests
+    pub fn new_with_clock(speed: Speed, clock: impl Clock + 'static) -> (Self, TickManagerHandle) {
+        Self::builder(speed).clock(clock).build()
+    }
+
+    /// starts a Tick Manager at the slowest speed that can serve every requested member rate,
+    /// the plan holds the speed factor to register each of those members with.
+    /// panics without requirements or if one of them is Fps(0) or Manual, see try_new_auto
+    pub fn new_auto(requirements: &[Speed]) -> (Self, TickManagerHandle, SpeedPlan) {
+        Self::try_new_auto(requirements)
+            .expect("new_auto needs at least one requirement and no Fps(0) or Manual")
+    }
+
+    /// like new_auto, None instead of a panic if no speed serves the requirements
+    pub fn try_new_auto(requirements: &[Speed]) -> Option<(Self, TickManagerHandle, SpeedPlan)> {
+        let plan = negotiate_speed(requirements)?;
+        let (manager, handle) = Self::new(plan.speed.clone());
+        Some((manager, handle, plan))
+    }
+
+    pub fn builder(speed: Speed) -> TickManagerBuilder {
+        TickManagerBuilder::new(speed)
+    }
+
+    pub(crate) fn from_builder(builder: TickManagerBuilder) -> (Self, TickManagerHandle) {
+        Self::spawn(builder, Arc::new(TickClock::default()), false)
+    }
+
+    /// a manager that doesn't start frames before another one handed its members over
+    pub(crate) fn standby(
+        builder: TickManagerBuilder,
+        clock: Arc<TickClock>,
+    ) -> (Self, TickManagerHandle) {
+        Self::spawn(builder, clock, true)
+    }
+
+    fn spawn(
+        builder: TickManagerBuilder,
+        clock: Arc<TickClock>,
+        standby: bool,
+    ) -> (Self, TickManagerHandle) {
+        let id = NEXT_MANAGER_ID.fetch_add(1, Ordering::Relaxed);
+        let (global_sender, internal_receiver) = flume::bounded(10);
+        // unbounded, so control commands never wait behind a full command channel
+        let (control_sender, control_receiver) = flume::unbounded();
+
+        let member_map = Arc::new(Mutex::new(InternalMap::new()));
+
+        let mut manager = TickManager {
+            id,
+            thread_name: builder
+                .thread_name
+                .unwrap_or_else(|| format!("tick-manager-{}", id)),
+            registered_name: None,
+            join_at_frame_boundary: builder.join_at_frame_boundary,
+            batch_topology_changes: builder.batch_topology_changes,
+            balance_offsets: builder.balance_offsets,
+            group_barriers: builder.group_barriers,
+            free_run: builder.free_run,
+            lockstep: builder.lockstep,
+            pipeline_depth: builder.pipeline_depth,
+            seed: builder.seed,
+            ordered_dispatch: builder.ordered_dispatch,
+            fixed_point_time: builder.fixed_point_time,
+            max_catch_up: builder.max_catch_up,
+            when_all_hidden: builder.when_all_hidden,
+            standby,
+            internal_receiver,
+            control_receiver,
+            member_map: member_map.clone(),
+            handle: None,
+            exited: None,
+            drop_policy: builder.drop_policy,
+            time: builder.time.clone(),
+            #[cfg(all(feature = "shm", unix))]
+            shm: builder.shm,
+            amount_of_members: Arc::new(AtomicUsize::new(0)),
+            instant: Arc::new(Mutex::new(builder.time.now())),
+            speed: Arc::new(builder.speed),
+            clock: clock.clone(),
+            control_sender: control_sender.clone(),
+            barrier: Arc::default(),
+        };
+
+        manager.start();
+        let exited = manager.exited.clone().expect("the thread was just started");
+        let handle = TickManagerHandle::new(global_sender, control_sender, clock, id, exited)
+            .watched_by(manager.barrier.clone());
+        (manager, handle)
+    }
+
+    /// the id included in every event of this manager
+    pub fn id(&self) -> ManagerID {
+        self.id
+    }
+
+    pub fn start(&mut self) {
+        let anchor = *self.instant.lock().unwrap();
+        let state = ManagerState {
+            manager_id: self.id,
+            internal_receiver: self.internal_receiver.clone(),
+            control_receiver: self.control_receiver.clone(),
+            member_map: self.member_map.clone(),
+            amount_of_members: self.amount_of_members.clone(),
+            speed: (*self.speed).clone(),
+            instant: self.instant.clone(),
+            clock: self.clock.clone(),
+            barrier: self.barrier.clone(),
+            main_tick_counter: 0,
+            sim_time: Duration::ZERO,
+            started_at: self.time.now(),
+            time: self.time.clone(),
+            paused_since: None,
+            last_frame: None,
+            join_at_frame_boundary: self.join_at_frame_boundary,
+            batch_topology_changes: self.batch_topology_changes,
+            balance_offsets: self.balance_offsets,
+            group_barriers: self.group_barriers,
+            free_run: self.free_run,
+            lockstep: self.lockstep,
+            pipeline_depth: self.pipeline_depth,
+            seed: self.seed,
+            ordered_dispatch: self.ordered_dispatch.clone(),
+            pacer: FramePacer::new(&self.speed, anchor),
+            timeline: (self.fixed_point_time
+                || self.max_catch_up.is_some()
+                || self.speed.is_manual())
+            .then(|| FixedTimeline::new(anchor, 0, Duration::ZERO)),
+            max_catch_up: self.max_catch_up,
+            when_all_hidden: self.when_all_hidden,
+            hidden_idle: false,
+            fence: None,
+            staged: Vec::new(),
+            staged_tick: None,
+            staged_frame: HashSet::new(),
+            speed_log: SpeedLog::new((*self.speed).clone()),
+            manual_ticks: VecDeque::new(),
+            standby: self.standby,
+            forward_to: None,
+            pending_topology: Vec::new(),
+            observers: Vec::new(),
+            tags: FrameTags::default(),
+            tweens: Vec::new(),
+            timers: TimerWheel::new(0),
+            wall_timers: BTreeSet::new(),
+            scheduled: HashMap::new(),
+            event_subscribers: Vec::new(),
+            member_watch: MemberWatch::default(),
+            circuit_breaker: CircuitBreaker::default(),
+            fairness: Fairness::default(),
+            idle: IdleTracker::default(),
+            leaks: LeakDetector::default(),
+            quotas: HashMap::new(),
+            reservations: BTreeMap::new(),
+            sleeping: HashMap::new(),
+            load: LoadMonitor::default(),
+            utilization_hooks: Vec::new(),
+            checkpoints: Checkpoints::default(),
+            deferred: Vec::new(),
+            mailboxes: HashMap::new(),
+            buffered: Vec::new(),
+            frame_conditions: Vec::new(),
+            topics: HashMap::new(),
+            quiesce_count: 0,
+            paused: false,
+            quiesce_waiters: Vec::new(),
+            shutdown: None,
+            #[cfg(all(feature = "shm", unix))]
+            shm: self.shm.clone(),
+        };
+
+        let (exited_sender, exited) = flume::bounded(0);
+        self.exited = Some(exited);
+        self.handle = Some(
+            thread::Builder::new()
+                .name(self.thread_name.clone())
+                .spawn(move || {
+                    let _exited = exited_sender;
+                    state.run()
+                })
+                .expect("failed to spawn the Tick Manager thread"),
+        );
+    }
+
+    /// asks the thread to shut down without waiting for it, returns the thread and the
+    /// receiver that disconnects once it returned. None if it was already asked to
+    pub(crate) fn request_shutdown(&mut self) -> Option<(thread::JoinHandle<()>, Receiver<()>)> {
+        let handle = self.handle.take()?;
+        let _ = self.control_sender.send(TickCommand::Shutdown);
+        Some((handle, self.exited.take()?))
+    }
+}
+
+/// a change to the member set, held back until the next frame starts
+pub(crate) enum TopologyChange {
+    Register(MemberID, (SpeedFactor, MemberInfo)),
+    Unregister(MemberID),
+    SetSpeedFactor(MemberID, SpeedFactor),
+}
+
+/// everything owned by the manager thread
+struct ManagerState {
+    /// the time every frame, timer and deadline is measured in
+    time: Arc<dyn Clock>,
+    manager_id: ManagerID,
+    internal_receiver: Receiver<TickCommand>,
+    control_receiver: Receiver<TickCommand>,
+    member_map: Arc<Mutex<InternalMap>>,
+    amount_of_members: Arc<AtomicUsize>,
+    speed: Speed,
+    instant: Arc<Mutex<Instant>>,
+    clock: Arc<TickClock>,
+    barrier: Arc<FrameBarrier>,
+    main_tick_counter: u64,
+    /// sum of all frame deltas, without the time spent paused
+    sim_time: Duration,
+    /// wall time is measured from here
+    started_at: Instant,
+    /// set while no frames start because of a quiesce or standby
+    paused_since: Option<Instant>,
+    last_frame: Option<TickInfo>,
+    /// whether new members are held back until the next frame starts
+    join_at_frame_boundary: bool,
+    /// whether all topology changes are held back until the next frame starts
+    batch_topology_changes: bool,
+    /// whether new members get the offset that spreads them the most
+    balance_offsets: bool,
+    /// whether every group waits only for its own members, see
+    /// TickManagerBuilder::group_barriers
+    group_barriers: bool,
+    /// whether every member that is ready gets its tick without a barrier,
+    /// see TickManagerBuilder::free_run
+    free_run: bool,
+    /// whether no frame starts before every participant acknowledged its tick,
+    /// see TickManagerBuilder::lockstep
+    lockstep: bool,
+    /// how many ticks a participant may have in flight, see TickManagerBuilder::pipeline_depth
+    pipeline_depth: Option<u64>,
+    /// per frame seeds are derived from this
+    seed: Option<u64>,
+    /// the order ticks are delivered in, unspecified if None
+    ordered_dispatch: Option<OrderedDispatch>,
+    /// when frames are due while there is no fixed point timeline
+    pacer: FramePacer,
+    /// frame times in fixed point, deltas and simulated time are measured if None
+    timeline: Option<FixedTimeline>,
+    /// every speed the frames ran at
+    speed_log: SpeedLog,
+    /// tick_now calls waiting for their frame, oldest first
+    manual_ticks: VecDeque<Sender<TickInfo>>,
+    /// how many missed frames are delivered back to back, None if the pacing starts over
+    /// instead. frames wait for their due members while set
+    max_catch_up: Option<u64>,
+    when_all_hidden: HiddenPolicy,
+    /// due members of the latest frame that wait for a member they come after to finish
+    staged: Vec<MemberID>,
+    /// the tick and slice headroom the staged members get once they are released
+    staged_tick: Option<(TickInfo, Duration)>,
+    /// the members the frame of staged_tick was delivered to, the staged members only wait
+    /// for these
+    staged_frame: HashSet<MemberID>,
+    /// the depth of the FrameFence and the last frame it was signaled for
+    fence: Option<(u64, u64)>,
+    /// whether HiddenPolicy::Pause holds back the frames right now
+    hidden_idle: bool,
+    /// no frames are started before a Handover arrived
+    standby: bool,
+    /// set once the members were handed over, every later command is forwarded there
+    forward_to: Option<TickManagerHandle>,
+    /// changes to the member set since the last frame started, in the order they were sent
+    pending_topology: Vec<TopologyChange>,
+    /// receivers of every main frame, removed once their receiver is dropped
+    /// an observer with a tag only receives the frames carrying it
+    observers: Vec<(Option<String>, Sender<TickInfo>)>,
+    tags: FrameTags,
+    /// interpolations advanced once per main frame
+    tweens: Vec<ActiveTween>,
+    /// id and generation of the Sim items, keyed by the main frame they fire on
+    timers: TimerWheel<(u64, u64)>,
+    /// when the Wall items fire, with their id and generation
+    wall_timers: BTreeSet<(Instant, u64, u64)>,
+    /// one-shot timers, repeating timers and retry schedules by id
+    scheduled: HashMap<u64, ScheduleEntry>,
+    /// receivers of TickEvents, removed once their receiver is dropped
+    event_subscribers: Vec<Sender<ManagerEvent>>,
+    member_watch: MemberWatch,
+    circuit_breaker: CircuitBreaker,
+    fairness: Fairness,
+    idle: IdleTracker,
+    leaks: LeakDetector,
+    /// by tenant
+    quotas: HashMap<String, TenantQuota>,
+    /// frames that only dispatch a single member
+    reservations: BTreeMap<u64, MemberID>,
+    /// members that aren't due before the given main frame
+    sleeping: HashMap<MemberID, u64>,
+    load: LoadMonitor,
+    utilization_hooks: Vec<UtilizationHook>,
+    checkpoints: Checkpoints,
+    /// run at the next epoch
+    deferred: Vec<Box<dyn FnOnce() + Send>>,
+    /// Mail and Topic replies waiting for the next tick of their receiver
+    mailboxes: HashMap<MemberID, Vec<TickStateReply>>,
+    /// the subscribers of every topic
+    topics: HashMap<Arc<str>, BTreeSet<MemberID>>,
+    /// commands of submitted CommandBuffers, applied right before the next frame starts
+    buffered: Vec<TickCommand>,
+    /// waiters released on the first frame their predicate is true for
+    frame_conditions: Vec<(FramePredicate, Sender<TickInfo>)>,
+    /// amount of QuiesceGuards alive, no frames are started while above 0
+    quiesce_count: usize,
+    /// set by TickManagerHandle::pause
+    paused: bool,
+    /// notified once no member is Running anymore
+    quiesce_waiters: Vec<Sender<()>>,
+    #[cfg(all(feature = "shm", unix))]
+    shm: Option<Arc<ShmPublisher>>,
+    /// set by shutdown_with_timeout, no frames start until the members finished or the
+    /// deadline passed
+    shutdown: Option<(Instant, Sender<Vec<MemberID>>)>,
+}
+
+impl ManagerState {
+    fn run(mut self) {
+        let mut woken_by = None;
+        loop {
+            let mut handled = false;
+            while let Some(command) = woken_by.take().or_else(|| self.next_command()) {
+                if let ControlFlow::Break(()) = self.handle_command(command) {
+                    return self.clock.close();
+                }
+                if let Some(forward_to) = self.forward_to.take() {
+                    return self.forward(forward_to);
+                }
+                handled = true;
+            }
+
+            if !self.buffered.is_empty() && self.frame_due() {
+                for command in std::mem::take(&mut self.buffered) {
+                    if let ControlFlow::Break(()) = self.handle_command(command) {
+                        return self.clock.close();
+                    }
+                    if let Some(forward_to) = self.forward_to.take() {
+                        return self.forward(forward_to);
+                    }
+                }
+                handled = true;
+            }
+
+            self.sync_states();
+            self.release_staged();
+            if self.check_shutdown() {
+                return self.clock.close();
+            }
+            self.check_quiescence();
+            self.check_checkpoint();
+            self.fire_wall_timers();
+            if self.try_frame() || handled {
+                self.member_watch.publish(&self.member_map.lock().unwrap());
+            }
+
+            woken_by = self.wait_for_command(self.next_deadline());
+        }
+    }
+
+    /// whether the member, registered or about to be, belongs to the tenant
+    fn in_tenant(&self, id: MemberID, tenant: &str) -> bool {
+        let map = self.member_map.lock().unwrap();
+        let pending = self
+            .pending_topology
+            .iter()
+            .filter_map(|change| match change {
+                TopologyChange::Register(pending_id, member) if *pending_id == id => Some(member),
+                _ => None,
+            });
+        map.get(&id)
+            .into_iter()
+            .chain(pending)
+            .any(|(_sf, member_info)| member_info.tenant.as_deref() == Some(tenant))
+    }
+
+    /// picks up the states the members set in their StateCell since the last check
+    fn sync_states(&mut self) {
+        let mut map = self.member_map.lock().unwrap();
+        let pending = self
+            .pending_topology
+            .iter_mut()
+            .filter_map(|change| match change {
+                TopologyChange::Register(_id, member) => Some(member),
+                _ => None,
+            });
+        for (_sf, member_info) in map.values_mut().chain(pending) {
+            let state = member_info.state_cell.get();
+            if std::mem::discriminant(&state) != std::mem::discriminant(&member_info.state) {
+                Self::change_state(&mut self.load, member_info, state);
+            }
+        }
+    }
+
+    /// takes over the state from the StateCell, a member that stopped running counts towards
+    /// the frame utilization until the moment it set its state
+    fn change_state(load: &mut LoadMonitor, member_info: &mut MemberInfo, state: MemberState) {
+        if matches!(member_info.state, MemberState::Running)
+            && !matches!(state, MemberState::Running)
+            && !std::mem::take(&mut member_info.leased)
+            && member_info.budget.is_none()
+        {
+            let changed_at = member_info.state_cell.changed_at();
+            load.record_member(changed_at.saturating_duration_since(member_info.last_tick));
+        }
+        member_info.state = state;
+    }
+
+    /// control commands first, so a Shutdown doesn't wait for a backlog of registrations
+    fn next_command(&self) -> Option<TickCommand> {
+        self.control_receiver
+            .try_recv()
+            .or_else(|_| self.internal_receiver.try_recv())
+            .ok()
+    }
+
+    /// passes every command on to the manager the members were handed over to, until shut down
+    fn forward(self, to: TickManagerHandle) {
+        loop {
+            let command = self.next_command().or_else(|| self.wait_for_command(None));
+            match command {
+                Some(TickCommand::Shutdown) | None => return,
+                // the handle waits for this thread to return as well
+                Some(command @ TickCommand::ShutdownWithin(..)) => {
+                    let _ = to.send(command);
+                    return;
+                }
+                Some(command) => {
+                    let _ = to.send(command);
+                }
+            }
+        }
+    }
+
+    fn handle_command(&mut self, command: TickCommand) -> ControlFlow<()> {
+        match command {
+            TickCommand::Register(sender, config) => {
+                let mut map = self.member_map.lock().unwrap();
+                if let Some(tenant) = &config.tenant
+                    && let Some(quota) = self.quotas.get(tenant)
+                    && let Some(exceeded) =
+                        quota.check(tenant, config.speed_factor, &map, &self.pending_topology)
+                {
+                    let _ = sender.send(TickStateReply::Rejected(TickError::QuotaExceeded {
+                        tenant: tenant.clone(),
+                        quota: exceeded,
+                    }));
+                    return ControlFlow::Continue(());
+                }
+                let id = self.amount_of_members.fetch_add(1, Ordering::SeqCst);
+                let active_from = self.main_tick_counter + 1;
+                let offset = if self.balance_offsets && config.auto_offset {
+                    self.balanced_offset(config.speed_factor, &map)
+                } else {
+                    config.offset
+                };
+                // observers never report their state
+                let state = match config.kind {
+                    MemberKind::Observer => MemberState::Hidden,
+                    _ => MemberState::Running,
+                };
+                let state_cell =
+                    Arc::new(StateCell::new(state.clone()).watched_by(self.barrier.clone()));
+                let _ = sender.send(TickStateReply::SelfID(id, active_from, state_cell.clone()));
+                if config.bootstrap {
+                    let next_frame_at = *self.instant.lock().unwrap() + self.speed.get_duration();
+                    let latest = self.last_frame.map(|info| info.delivered(next_frame_at));
+                    let _ = sender.send(TickStateReply::Bootstrap(latest));
+                }
+                let member = (
+                    config.speed_factor.max(1),
+                    MemberInfo {
+                        sender,
+                        state,
+                        last_tick: self.time.now(),
+                        name: config.name,
+                        group: config.group,
+                        tenant: config.tenant,
+                        offset,
+                        priority: config.priority,
+                        after: config.after,
+                        phase: config.phase,
+                        kind: config.kind,
+                        spread_work: config.spread_work,
+                        leased: false,
+                        budget: config.budget,
+                        state_cell,
+                        parked: None,
+                    },
+                );
+                if self.join_at_frame_boundary || self.batch_topology_changes {
+                    self.pending_topology
+                        .push(TopologyChange::Register(id, member));
+                } else {
+                    map.insert(id, member);
+                }
+            }
+
+            TickCommand::ChangeMemberState(member_id, state) => {
+                let mut map = self.member_map.lock().unwrap();
+                let pending = self
+                    .pending_topology
+                    .iter_mut()
+                    .find_map(|change| match change {
+                        TopologyChange::Register(id, member) if *id == member_id => Some(member),
+                        _ => None,
+                    });
+                if let Some((_sf, member_info)) = map.get_mut(&member_id).or(pending) {
+                    member_info.state_cell.set(state.clone());
+                    Self::change_state(&mut self.load, member_info, state);
+                }
+            }
+
+            TickCommand::Park(member_id, thread, slot) => {
+                let mut map = self.member_map.lock().unwrap();
+                let pending = self
+                    .pending_topology
+                    .iter_mut()
+                    .find_map(|change| match change {
+                        TopologyChange::Register(id, member) if *id == member_id => Some(member),
+                        _ => None,
+                    });
+                if let Some((_sf, member_info)) = map.get_mut(&member_id).or(pending) {
+                    member_info.parked = Some((thread, slot));
+                }
+            }
+
+            TickCommand::Unpark(member_id) => {
+                let mut map = self.member_map.lock().unwrap();
+                if let Some((_sf, member_info)) = map.get_mut(&member_id) {
+                    // a tick put in the slot before this command would never be picked up
+                    if let Some((_thread, slot)) = member_info.parked.take()
+                        && let Some(info) = slot.take()
+                    {
+                        let _ = member_info.sender.try_send(TickStateReply::Tick(info));
+                    }
+                }
+            }
+
+            TickCommand::Unregister(id) => {
+                if self.batch_topology_changes {
+                    self.pending_topology.push(TopologyChange::Unregister(id));
+                } else {
+                    self.member_map.lock().unwrap().remove(&id);
+                    self.pending_topology.retain(
+                        |change| !matches!(change, TopologyChange::Register(pending_id, _) if *pending_id == id),
+                    );
+                    self.forget_member(id);
+                }
+            }
+
+            TickCommand::SetSpeedFactor(id, factor) => {
+                let factor = factor.max(1);
+                if self.batch_topology_changes {
+                    self.pending_topology
+                        .push(TopologyChange::SetSpeedFactor(id, factor));
+                } else if let Some((sf, _)) = self.member_map.lock().unwrap().get_mut(&id) {
+                    *sf = factor;
+                }
+            }
+
+            TickCommand::SetOffset(id, offset) => {
+                let mut map = self.member_map.lock().unwrap();
+                // the member may still wait for the frame that registers it
+                let pending = self
+                    .pending_topology
+                    .iter_mut()
+                    .find_map(|change| match change {
+                        TopologyChange::Register(pending_id, member) if *pending_id == id => {
+                            Some(member)
+                        }
+                        _ => None,
+                    });
+                if let Some((_sf, member_info)) = map.get_mut(&id).or(pending) {
+                    member_info.offset = offset;
+                }
+            }
+
+            TickCommand::UnregisterGroup(group) => {
+                let in_group = |info: &MemberInfo| info.group.as_deref() == Some(group.as_str());
+                let mut removed: Vec<(MemberID, MemberInfo)> = {
+                    let mut map = self.member_map.lock().unwrap();
+                    let ids: Vec<MemberID> = map
+                        .iter()
+                        .filter(|(_, (_sf, info))| in_group(info))
+                        .map(|(&id, _)| id)
+                        .collect();
+                    ids.into_iter()
+                        .filter_map(|id| map.remove(&id).map(|(_sf, info)| (id, info)))
+                        .collect()
+                };
+                for change in std::mem::take(&mut self.pending_topology) {
+                    match change {
+                        TopologyChange::Register(id, (_sf, info)) if in_group(&info) => {
+                            removed.push((id, info));
+                        }
+                        change => self.pending_topology.push(change),
+                    }
+                }
+                let mut members = Vec::with_capacity(removed.len());
+                for (id, info) in removed {
+                    let _ = info.sender.send(TickStateReply::Unregistered);
+                    self.forget_member(id);
+                    members.push(id);
+                }
+                members.sort_unstable();
+                self.emit(TickEvent::GroupUnregistered { group, members });
+            }
+
+            TickCommand::Observe(sender) => {
+                self.observers.push((None, sender));
+            }
+
+            TickCommand::ObserveTagged(tag, sender) => {
+                self.observers.push((Some(tag), sender));
+            }
+
+            TickCommand::TagFrames(tag, rule) => {
+                self.tags.set(tag, rule);
+            }
+
+            TickCommand::OnUtilization(hook) => {
+                self.utilization_hooks.push(hook);
+            }
+
+            TickCommand::SetCheckpointEvery(every) => {
+                self.checkpoints.set_every(every);
+            }
+
+            TickCommand::OnCheckpoint(hook) => {
+                self.checkpoints.add_hook(hook);
+            }
+
+            TickCommand::Defer(deferred) => {
+                self.deferred.push(deferred);
+            }
+
+            TickCommand::Run(command) => command(),
+
+            TickCommand::InNamespace(namespace, command) => {
+                let ids = command.member_ids();
+                if ids.iter().all(|&id| self.in_tenant(id, &namespace)) {
+                    return self.handle_command(*command);
+                }
+            }
+
+            TickCommand::WaitUntil(predicate, sender) => {
+                self.frame_conditions.push((predicate, sender));
+            }
+
+            TickCommand::Apply(commands) => {
+                self.buffered.extend(commands);
+            }
+
+            TickCommand::SendTo(from, to, payload) => {
+                let known = self.member_map.lock().unwrap().contains_key(&to)
+                    || self.pending_topology.iter().any(
+                        |change| matches!(change, TopologyChange::Register(id, _) if *id == to),
+                    );
+                // messages to members that are gone are dropped
+                if known {
+                    let message = Message::new(from, self.main_tick_counter, payload);
+                    let mailbox = self.mailboxes.entry(to).or_default();
+                    mailbox.push(TickStateReply::Mail(message));
+                }
+            }
+
+            TickCommand::Subscribe(id, topic) => {
+                self.topics.entry(topic.into()).or_default().insert(id);
+            }
+
+            TickCommand::Unsubscribe(id, topic) => {
+                if let Some(subscribers) = self.topics.get_mut(topic.as_str()) {
+                    subscribers.remove(&id);
+                }
+            }
+
+            TickCommand::Publish(from, topic, payload) => {
+                let Some((topic, subscribers)) = self.topics.get_key_value(topic.as_str()) else {
+                    return ControlFlow::Continue(());
+                };
+                for &id in subscribers {
+                    let message = TopicMessage::new(
+                        topic.clone(),
+                        from,
+                        self.main_tick_counter,
+                        payload.clone(),
+                    );
+                    let mailbox = self.mailboxes.entry(id).or_default();
+                    mailbox.push(TickStateReply::Topic(message));
+                }
+            }
+
+            TickCommand::Tween(state) => {
+                self.tweens.push(ActiveTween::new(state));
+            }
+
+            TickCommand::After(period, sender, state) => {
+                let due = self.due_after(period);
+                self.schedule(state, due, period.domain(), ScheduledTimer::Once(sender));
+            }
+
+            TickCommand::Every(period, sender, state) => {
+                let due = self.due_after(period);
+                let timer = ScheduledTimer::Repeat(period, sender);
+                self.schedule(state, due, period.domain(), timer);
+            }
+
+            TickCommand::Retry(policy, sender, retry_state, state) => {
+                let retry = ActiveRetry::new(policy, sender, retry_state);
+                let due = retry.due(self.main_tick_counter, self.speed.get_duration());
+                self.schedule(state, due, retry.domain(), ScheduledTimer::Retry(retry));
+            }
+
+            TickCommand::Reschedule(id, frames) => {
+                if let Some(entry) = self.scheduled.get_mut(&id) {
+                    // Wall items are moved by the time the frames take at the current speed,
+                    // one too far out for an Instant keeps its old due
+                    let due = match entry.domain {
+                        ClockDomain::Sim => {
+                            Due::Frame(self.main_tick_counter.saturating_add(frames.max(1)))
+                        }
+                        ClockDomain::Wall => {
+                            match self.time.now().checked_add(self.speed.duration_of(frames)) {
+                                Some(at) => Due::At(at),
+                                None => return ControlFlow::Continue(()),
+                            }
+                        }
+                    };
+                    entry.generation += 1;
+                    if let ScheduledTimer::Retry(retry) = &mut entry.timer {
+                        retry.due_now(self.time.now());
+                    }
+                    let generation = entry.generation;
+                    self.insert_due(due, id, generation);
+                }
+            }
+
+            TickCommand::Unschedule(id) => {
+                // the queued entry is skipped once it comes up
+                self.scheduled.remove(&id);
+            }
+
+            TickCommand::WatchMembers(sender) => {
+                let map = self.member_map.lock().unwrap();
+                self.member_watch.subscribe(sender, &map);
+            }
+
+            TickCommand::ListMembers(reply) => {
+                let map = self.member_map.lock().unwrap();
+                let _ = reply.send(sorted(summarize(&map)));
+            }
+
+            TickCommand::SubscribeEvents(sender) => {
+                self.event_subscribers.push(sender);
+            }
+
+            TickCommand::SetCircuitBreaker(policy) => {
+                let events = {
+                    let mut map = self.member_map.lock().unwrap();
+                    self.circuit_breaker.set_policy(policy, |id, factor| {
+                        if let Some((sf, _)) = map.get_mut(&id) {
+                            *sf = factor;
+                        }
+                    })
+                };
+                for event in events {
+                    self.emit(event);
+                }
+            }
+
+            TickCommand::SetOverloadPolicy(policy) => {
+                self.load.set_policy(policy);
+            }
+
+            TickCommand::SetHiddenPolicy(policy) => {
+                self.when_all_hidden = policy;
+            }
+
+            TickCommand::SetFrameFence(depth) => {
+                self.fence = depth.map(|depth| (depth, self.main_tick_counter));
+            }
+
+            TickCommand::SignalFence(frame) => {
+                if let Some((_depth, signaled)) = &mut self.fence {
+                    *signaled = (*signaled).max(frame);
+                }
+            }
+
+            TickCommand::SetSpeed(speed) => {
+                self.set_speed(speed);
+                let latest_frame = *self.instant.lock().unwrap();
+                self.restart_timeline(latest_frame);
+            }
+
+            TickCommand::SetStarvationPolicy(policy) => {
+                self.fairness.set_policy(policy);
+            }
+
+            TickCommand::SetIdlePolicy(policy) => {
+                self.idle.set_policy(policy);
+            }
+
+            TickCommand::SetTenantQuota(tenant, quota) => match quota {
+                Some(quota) => {
+                    self.quotas.insert(tenant, quota);
+                }
+                None => {
+                    self.quotas.remove(&tenant);
+                }
+            },
+
+            TickCommand::SetLeakDetection(policy) => {
+                self.leaks.set_policy(policy);
+            }
+
+            TickCommand::TickNow(waiter) => {
+                self.manual_ticks.push_back(waiter);
+            }
+
+            TickCommand::SpeedLog(reply) => {
+                let _ = reply.send(self.speed_log.clone());
+            }
+
+            TickCommand::MemoryStats(reply) => {
+                let _ = reply.send(self.memory_stats());
+            }
+
+            TickCommand::ReserveExclusive(member_id, frame, reply) => {
+                let free = frame > self.main_tick_counter
+                    && !self.reservations.contains_key(&frame)
+                    && self.member_map.lock().unwrap().contains_key(&member_id);
+                if free {
+                    self.reservations.insert(frame, member_id);
+                }
+                let _ = reply.send(free);
+            }
+
+            TickCommand::SleepUntil(id, frame) => {
+                self.sleeping.insert(id, frame);
+            }
+
+            TickCommand::SkipTicks(id, ticks) => {
+                let (sf, offset) = match self.member_map.lock().unwrap().get(&id) {
+                    Some((sf, member_info)) => (*sf, member_info.offset),
+                    None => (1, 0),
+                };
+                let next = next_due(self.main_tick_counter, sf, offset);
+                self.sleeping.insert(
+                    id,
+                    next.saturating_add(ticks.saturating_mul(sf.max(1) as u64)),
+                );
+            }
+
+            TickCommand::ReportPanic(id, message, unregister) => {
+                // emitted first so the event still describes the member
+                self.emit(TickEvent::MemberPanicked { id, message });
+                {
+                    let mut map = self.member_map.lock().unwrap();
+                    if unregister {
+                        map.remove(&id);
+                    } else if let Some((_sf, member_info)) = map.get_mut(&id) {
+                        member_info.state = MemberState::Finished;
+                        member_info.state_cell.set(MemberState::Finished);
+                    }
+                }
+                if unregister {
+                    self.forget_member(id);
+                }
+            }
+
+            TickCommand::ReportError(id, message) => {
+                self.emit(TickEvent::MemberFailed { id, message });
+            }
+
+            TickCommand::ReportRestart(id, restarts) => {
+                self.emit(TickEvent::MemberRestarted { id, restarts });
+            }
+
+            TickCommand::Quiesce(waiter) => {
+                self.quiesce_count += 1;
+                self.quiesce_waiters.push(waiter);
+            }
+
+            TickCommand::ReleaseQuiesce => {
+                self.quiesce_count = self.quiesce_count.saturating_sub(1);
+                if self.quiesce_count == 0 {
+                    // the time spent quiesced doesn't count towards the next frame
+                    *self.instant.lock().unwrap() = self.time.now();
+                }
+            }
+
+            TickCommand::Pause => self.paused = true,
+
+            TickCommand::Resume => {
+                if std::mem::take(&mut self.paused) {
+                    // the pause neither counts towards the next frame nor the simulated time
+                    let now = self.time.now();
+                    *self.instant.lock().unwrap() = now;
+                    self.paused_since = None;
+                    self.restart_timeline(now);
+                }
+            }
+
+            TickCommand::HandOver(to, done) => {
+                let handover = self.hand_over(&to);
+                let _ = to.send(TickCommand::Adopt(Box::new(handover)));
+                self.forward_to = Some(to);
+                let _ = done.send(());
+            }
+
+            TickCommand::Adopt(handover) => {
+                self.adopt(*handover);
+            }
+
+            TickCommand::Lease(id, reply) => {
+                let mut map = self.member_map.lock().unwrap();
+                let next = map.get_mut(&id).and_then(|(sf, member_info)| {
+                    if !matches!(member_info.state, MemberState::Running) {
+                        return None;
+                    }
+                    member_info.leased = true;
+                    Some(next_due(self.main_tick_counter, *sf, member_info.offset))
+                });
+                let _ = reply.send(next);
+            }
+
+            TickCommand::Schedule(reply) => {
+                let map = self.member_map.lock().unwrap();
+                let members = map
+                    .iter()
+                    .map(|(&id, (sf, member_info))| ScheduledMember {
+                        id,
+                        speed_factor: *sf,
+                        offset: member_info.offset,
+                    })
+                    .collect();
+                let _ = reply.send(members);
+            }
+
+            TickCommand::Shutdown => {
+                return ControlFlow::Break(());
+            }
+
+            TickCommand::ShutdownWithin(timeout, waiter) => {
+                self.shutdown = Some((self.time.now() + timeout, waiter));
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    /// the offset that spreads a new member the most against the registered and pending ones
+    fn balanced_offset(&self, speed_factor: SpeedFactor, map: &InternalMap) -> u64 {
+        let pending = self
+            .pending_topology
+            .iter()
+            .filter_map(|change| match change {
+                TopologyChange::Register(_id, member) => Some(member),
+                _ => None,
+            });
+        let members = map
+            .values()
+            .chain(pending)
+            .map(|(sf, member_info)| (*sf, member_info.offset));
+        balanced_offset(speed_factor, members)
+    }
+
+    /// tells every member about the new manager and moves everything it has to take over
+    fn hand_over(&mut self, to: &TickManagerHandle) -> Handover {
+        let members = std::mem::take(&mut *self.member_map.lock().unwrap());
+        let pending_topology = std::mem::take(&mut self.pending_topology);
+        let pending = pending_topology.iter().filter_map(|change| match change {
+            TopologyChange::Register(_id, (_sf, member_info)) => Some(member_info),
+            _ => None,
+        });
+        for member_info in members.values().map(|(_sf, m)| m).chain(pending) {
+            let _ = member_info
+                .sender
+                .send(TickStateReply::SwitchedTo(to.clone()));
+        }
+        Handover {
+            members,
+            pending_topology,
+            next_member_id: self.amount_of_members.load(Ordering::SeqCst),
+            main_tick_counter: self.main_tick_counter,
+            sim_time: self.sim_time,
+            started_at: self.started_at,
+            speed_log: std::mem::take(&mut self.speed_log),
+            seed: self.seed,
+            last_frame: self.last_frame,
+            observers: std::mem::take(&mut self.observers),
+            tags: std::mem::take(&mut self.tags),
+            event_subscribers: std::mem::take(&mut self.event_subscribers),
+            member_watch: std::mem::take(&mut self.member_watch),
+            tweens: std::mem::take(&mut self.tweens),
+            timers: self.timers.drain(),
+            wall_timers: std::mem::take(&mut self.wall_timers),
+            scheduled: std::mem::take(&mut self.scheduled),
+            reservations: std::mem::take(&mut self.reservations),
+            sleeping: std::mem::take(&mut self.sleeping),
+            checkpoints: std::mem::take(&mut self.checkpoints),
+            deferred: std::mem::take(&mut self.deferred),
+            mailboxes: std::mem::take(&mut self.mailboxes),
+            topics: std::mem::take(&mut self.topics),
+            buffered: std::mem::take(&mut self.buffered),
+            frame_conditions: std::mem::take(&mut self.frame_conditions),
+            quiesce_count: std::mem::take(&mut self.quiesce_count),
+            paused: self.paused,
+            quiesce_waiters: std::mem::take(&mut self.quiesce_waiters),
+            #[cfg(all(feature = "shm", unix))]
+            shm: self.shm.take(),
+        }
+    }
+
+    /// takes over the members of another manager, continuing its frame numbers
+    fn adopt(&mut self, handover: Handover) {
+        self.member_map.lock().unwrap().extend(handover.members);
+        self.pending_topology.extend(handover.pending_topology);
+        self.amount_of_members
+            .fetch_max(handover.next_member_id, Ordering::SeqCst);
+        self.main_tick_counter = handover.main_tick_counter;
+        self.sim_time = handover.sim_time;
+        self.started_at = handover.started_at;
+        // the frames continue at the speed of this manager
+        self.speed_log = handover.speed_log;
+        self.speed_log
+            .record(self.main_tick_counter, self.sim_time, self.speed.clone());
+        self.restart_timeline(self.time.now());
+        // replays stay reproducible across the switch unless the new manager has its own seed
+        self.seed = self.seed.or(handover.seed);
+        #[cfg(all(feature = "shm", unix))]
+        {
+            self.shm = self.shm.take().or(handover.shm);
+        }
+        self.last_frame = handover.last_frame;
+        self.observers.extend(handover.observers);
+        self.tags.adopt(handover.tags);
+        self.event_subscribers.extend(handover.event_subscribers);
+        self.member_watch.adopt(handover.member_watch);
+        self.tweens.extend(handover.tweens);
+        for (due, timer) in handover.timers {
+            self.timers.insert(due, timer);
+        }
+        self.wall_timers.extend(handover.wall_timers);
+        self.scheduled.extend(handover.scheduled);
+        self.reservations.extend(handover.reservations);
+        self.sleeping.extend(handover.sleeping);
+        self.checkpoints.adopt(handover.checkpoints);
+        self.deferred.extend(handover.deferred);
+        self.mailboxes.extend(handover.mailboxes);
+        for (topic, subscribers) in handover.topics {
+            self.topics.entry(topic).or_default().extend(subscribers);
+        }
+        self.buffered.extend(handover.buffered);
+        self.frame_conditions.extend(handover.frame_conditions);
+        self.quiesce_count += handover.quiesce_count;
+        self.paused |= handover.paused;
+        self.quiesce_waiters.extend(handover.quiesce_waiters);
+        self.standby = false;
+        *self.instant.lock().unwrap() = self.time.now();
+    }
+
+    /// applies the held back topology changes at the start of a frame
+    fn apply_topology(&mut self) {
+        if self.pending_topology.is_empty() {
+            return;
+        }
+        let mut added = Vec::new();
+        let mut removed = Vec::new();
+        let mut speed_factors = Vec::new();
+        for change in std::mem::take(&mut self.pending_topology) {
+            match change {
+                TopologyChange::Register(id, member) => {
+                    self.member_map.lock().unwrap().insert(id, member);
+                    added.push(id);
+                }
+                TopologyChange::Unregister(id) => {
+                    if self.member_map.lock().unwrap().remove(&id).is_some() {
+                        // joining and leaving within the same batch isn't visible in the diff
+                        match added.iter().position(|added_id| *added_id == id) {
+                            Some(index) => {
+                                added.remove(index);
+                            }
+                            None => removed.push(id),
+                        }
+                    }
+                    self.forget_member(id);
+                }
+                TopologyChange::SetSpeedFactor(id, factor) => {
+                    if let Some((sf, _)) = self.member_map.lock().unwrap().get_mut(&id) {
+                        *sf = factor;
+                        speed_factors.push((id, factor));
+                    }
+                }
+            }
+        }
+        if self.batch_topology_changes {
+            self.emit(TickEvent::TopologyChanged {
+                frame: self.main_tick_counter,
+                added,
+                removed,
+                speed_factors,
+            });
+        }
+    }
+
+    /// drops everything the manager tracks about a member that was removed from the map
+    fn forget_member(&mut self, id: MemberID) {
+        self.circuit_breaker.remove(id);
+        self.fairness.remove(id);
+        self.reservations.retain(|_, owner| *owner != id);
+        self.sleeping.remove(&id);
+        self.mailboxes.remove(&id);
+        self.staged.retain(|staged| *staged != id);
+        for subscribers in self.topics.values_mut() {
+            subscribers.remove(&id);
+        }
+    }
+
+    /// starts tracking a new scheduled item
+    fn schedule(
+        &mut self,
+        state: Arc<ScheduleState>,
+        due: Due,
+        domain: ClockDomain,
+        timer: ScheduledTimer,
+    ) {
+        let id = state.id();
+        self.insert_due(due, id, 0);
+        self.scheduled.insert(
+            id,
+            ScheduleEntry {
+                state,
+                generation: 0,
+                domain,
+                timer,
+            },
+        );
+    }
+
+    fn insert_due(&mut self, due: Due, id: u64, generation: u64) {
+        match due {
+            Due::Frame(frame) => self.timers.insert(frame, (id, generation)),
+            Due::At(at) => {
+                self.wall_timers.insert((at, id, generation));
+            }
+        }
+    }
+
+    fn due_after(&self, period: Period) -> Due {
+        period.due(
+            self.main_tick_counter,
+            self.speed.get_duration(),
+            self.time.now(),
+        )
+    }
+
+    /// fires the Sim items due on the frame
+    fn fire_timers(&mut self, info: &TickInfo, now: Instant) {
+        for (_due, (id, generation)) in self.timers.advance(info.tick_number) {
+            self.fire(id, generation, info, now);
+        }
+    }
+
+    /// fires the Wall items that are due, also while no frames start.
+    /// they are sent the latest frame
+    fn fire_wall_timers(&mut self) {
+        let now = self.time.now();
+        while let Some(&(at, id, generation)) = self.wall_timers.first() {
+            if at > now {
+                break;
+            }
+            self.wall_timers.pop_first();
+            let info = self.last_frame.unwrap_or(TickInfo {
+                tick_number: self.main_tick_counter,
+                delta: Duration::ZERO,
+                raw_delta: Duration::ZERO,
+                scheduled_at: now,
+                sim_time: self.sim_time,
+                wall_time: now - self.started_at,
+                headroom: Duration::ZERO,
+                seed: None,
+            });
+            self.fire(id, generation, &info, now);
+        }
+    }
+
+    /// fires a scheduled item, items that aren't over yet are put back
+    fn fire(&mut self, id: u64, generation: u64, info: &TickInfo, now: Instant) {
+        let (tick_number, frame_period) = (self.main_tick_counter, self.speed.get_duration());
+        let Some(entry) = self.scheduled.get_mut(&id) else {
+            return;
+        };
+        if entry.generation != generation {
+            // the item was moved, its new entry fires it
+            return;
+        }
+        let next = match &mut entry.timer {
+            _ if entry.state.is_cancelled() => None,
+            ScheduledTimer::Once(sender) => {
+                let _ = sender.try_send(*info);
+                None
+            }
+            ScheduledTimer::Repeat(period, sender) => match sender.try_send(*info) {
+                Err(TrySendError::Disconnected(_)) => None,
+                _ => Some(period.due(tick_number, frame_period, now)),
+            },
+            ScheduledTimer::Retry(retry) => retry
+                .poll(now)
+                .then(|| retry.due(tick_number, frame_period)),
+        };
+        match next {
+            Some(due) => self.insert_due(due, id, generation),
+            None => {
+                entry.state.mark_done();
+                self.scheduled.remove(&id);
+            }
+        }
+    }
+
+    /// releases the quiesce waiters once no member is in the middle of a tick
+    fn check_quiescence(&mut self) {
+        if self.quiesce_waiters.is_empty() {
+            return;
+        }
+        if self.is_quiet() {
+            for waiter in self.quiesce_waiters.drain(..) {
+                let _ = waiter.send(());
+            }
+        }
+    }
+
+    /// whether the thread should end because of shutdown_with_timeout: once no member is in the
+    /// middle of a tick or the deadline passed. members still running get Cancelled, the
+    /// others Unregistered
+    fn check_shutdown(&mut self) -> bool {
+        match &self.shutdown {
+            Some((deadline, _waiter)) if self.is_quiet() || self.time.now() >= *deadline => {}
+            _ => return false,
+        }
+        let Some((_deadline, waiter)) = self.shutdown.take() else {
+            return false;
+        };
+        let mut cancelled = Vec::new();
+        for (&id, (_sf, member_info)) in self.member_map.lock().unwrap().iter() {
+            let reply = match member_info.state {
+                MemberState::Running if member_info.kind != MemberKind::Observer => {
+                    cancelled.push(id);
+                    TickStateReply::Cancelled
+                }
+                _ => TickStateReply::Unregistered,
+            };
+            // a full channel must not hold up the shutdown
+            let _ = member_info.sender.try_send(reply);
+        }
+        cancelled.sort_unstable();
+        let _ = waiter.send(cancelled);
+        true
+    }
+
+    /// runs the pending checkpoint once no member is in the middle of a tick
+    fn check_checkpoint(&mut self) {
+        if !self.checkpoints.is_pending() || !self.is_quiet() {
+            return;
+        }
+        if let Some(event) = self.checkpoints.run() {
+            self.emit(event);
+        }
+    }
+
+    /// moves the epoch to the frame that is starting if no member is in the middle of a tick,
+    /// which makes everything deferred before it safe to run
+    fn advance_epoch(&mut self) {
+        if !self.is_quiet() {
+            return;
+        }
+        self.clock.publish_epoch(self.main_tick_counter);
+        for deferred in self.deferred.drain(..) {
+            deferred();
+        }
+    }
+
+    /// whether no member is in the middle of a tick, observers don't count
+    fn is_quiet(&self) -> bool {
+        let map = self.member_map.lock().unwrap();
+        map.values().all(|(_sf, member_info)| {
+            member_info.kind == MemberKind::Observer
+                || !matches!(member_info.state, MemberState::Running)
+        })
+    }
+
+    /// changes the speed from the next frame on, the timeline has to be restarted after
+    fn set_speed(&mut self, speed: Speed) {
+        self.speed_log
+            .record(self.main_tick_counter, self.sim_time, speed.clone());
+        // manual frames advance the simulated time by exactly one step, which needs the
+        // fixed point timeline. it stays on after switching back
+        if speed.is_manual() && self.timeline.is_none() {
+            self.timeline = Some(FixedTimeline::new(self.time.now(), 0, Duration::ZERO));
+        }
+        self.speed = speed;
+    }
+
+    /// starts the frame pacing over from the latest frame, needed whenever the speed
+    /// or the frame numbers change
+    fn restart_timeline(&mut self, at: Instant) {
+        self.pacer = FramePacer::new(&self.speed, at);
+        if let Some(timeline) = &mut self.timeline {
+            *timeline = FixedTimeline::new(at, self.main_tick_counter, self.sim_time);
+        }
+    }
+
+    /// no frames start while standing by, paused, quiesced or waiting for a checkpoint
+    fn is_paused(&self) -> bool {
+        self.standby
+            || self.paused
+            || self.quiesce_count > 0
+            || self.checkpoints.is_pending()
+            || self.shutdown.is_some()
+    }
+
+    /// whether try_frame would start a frame now
+    fn frame_due(&self) -> bool {
+        !self.is_paused()
+            && self.next_frame_at().is_some_and(|at| at <= self.time.now())
+            && !self.awaits_members()
+    }
+
+    /// whether the next frame waits for the FrameFence to be signaled
+    fn fenced(&self) -> bool {
+        let frame = self.main_tick_counter.wrapping_add(1);
+        self.fence
+            .is_some_and(|(depth, signaled)| frame > signaled.saturating_add(depth))
+    }
+
+    /// whether the next frame waits for a member due in it that is still running,
+    /// only while catching up or ticking manually and never while free running.
+    /// in lockstep it waits for every running participant, due or not.
+    /// no frame starts before the staged members got the tick of the latest one
+    fn awaits_members(&self) -> bool {
+        if !self.staged.is_empty() {
+            return true;
+        }
+        if self.free_run
+            || self.max_catch_up.is_none()
+                && !self.speed.is_manual()
+                && !self.lockstep
+                && self.pipeline_depth.is_none()
+        {
+            return false;
+        }
+        let frame = self.main_tick_counter.wrapping_add(1);
+        let map = self.member_map.lock().unwrap();
+        map.iter().any(|(id, (sf, member_info))| {
+            member_info.kind == MemberKind::Participant
+                && !self.accepts_tick(member_info)
+                && (self.lockstep || is_due(frame, *sf, member_info.offset))
+                && self.sleeping.get(id).is_none_or(|until| *until <= frame)
+        })
+    }
+
+    /// whether the member can take the next tick, pipelining a Running participant may take
+    /// ticks until it has pipeline_depth of them in flight
+    fn accepts_tick(&self, member_info: &MemberInfo) -> bool {
+        is_ready(member_info) || self.pipelines(member_info)
+    }
+
+    /// whether a Running participant may take another tick while it finishes the ones before
+    fn pipelines(&self, member_info: &MemberInfo) -> bool {
+        member_info.kind == MemberKind::Participant
+            && self
+                .pipeline_depth
+                .is_some_and(|depth| member_info.state_cell.in_flight() < depth)
+    }
+
+    /// whether the frame has due members besides observers and all of them are Hidden
+    fn all_due_hidden(&self, frame: u64) -> bool {
+        let map = self.member_map.lock().unwrap();
+        let mut due = map
+            .iter()
+            .filter(|(id, (sf, member_info))| {
+                member_info.kind != MemberKind::Observer
+                    && is_due(frame, *sf, member_info.offset)
+                    && self.sleeping.get(id).is_none_or(|until| *until <= frame)
+            })
+            .peekable();
+        due.peek().is_some()
+            && due.all(|(_id, (_sf, member_info))| matches!(member_info.state, MemberState::Hidden))
+    }
+
+    /// lets the frame pass uncounted, the pacing moves on as if it had started
+    fn skip_frame(&mut self, frame: u64) {
+        let now = self.time.now();
+        match &mut self.timeline {
+            Some(timeline) => {
+                let due_at = timeline.due_at(&self.speed, frame);
+                *timeline = FixedTimeline::new(due_at, self.main_tick_counter, self.sim_time);
+            }
+            None => {
+                self.pacer.advance(&self.speed, now);
+            }
+        }
+    }
+
+    /// when the next main frame is due, ignoring pauses.
+    /// None for manual ticking while no tick_now is waiting
+    fn next_frame_at(&self) -> Option<Instant> {
+        if self.speed.is_manual() {
+            return (!self.manual_ticks.is_empty()).then(|| self.time.now());
+        }
+        Some(match &self.timeline {
+            Some(timeline) => timeline.due_at(&self.speed, self.main_tick_counter + 1),
+            None => self.pacer.next_due(),
+        })
+    }
+
+    /// whether the loop waits for member states, which change without a command
+    fn polls_member_states(&self) -> bool {
+        // quiescence, checkpoints, shutdowns and staged members wait for them too
+        if !self.quiesce_waiters.is_empty()
+            || !self.staged.is_empty()
+            || self.checkpoints.is_pending()
+            || self.shutdown.is_some()
+        {
+            return true;
+        }
+        !self.hidden_idle
+            && !self.is_paused()
+            && self.next_frame_at().is_some_and(|at| at <= self.time.now())
+            && self.awaits_members()
+    }
+
+    /// when the loop has something to do without a command arriving, None if only a
+    /// command can change anything
+    fn next_deadline(&self) -> Option<Instant> {
+        if self.polls_member_states() {
+            let interval = if FUTEX_WAKE {
+                BARRIER_POLL_INTERVAL
+            } else {
+                STATE_POLL_INTERVAL
+            };
+            return Some(self.time.now() + interval);
+        }
+        let next_frame_at = self.next_frame_at();
+        if self.hidden_idle && !self.is_paused() {
+            let recheck = self.time.now() + self.speed.get_duration();
+            let next_timer = self.wall_timers.first().map(|&(at, _id, _generation)| at);
+            return Some(next_timer.map_or(recheck, |at| at.min(recheck)));
+        }
+        // only a signal of the fence releases the frame
+        let next_frame = next_frame_at.filter(|_| !self.is_paused() && !self.fenced());
+        let next_timer = self.wall_timers.first().map(|&(at, _id, _generation)| at);
+        next_frame.into_iter().chain(next_timer).min()
+    }
+
+    /// sleeps until a command arrives or the deadline passed, returns the command.
+    /// the loop only runs again when this returns, so an idle Tick Manager never spins
+    fn wait_for_command(&self, deadline: Option<Instant>) -> Option<TickCommand> {
+        if FUTEX_WAKE
+            && let Some(deadline) = deadline
+            && self.polls_member_states()
+        {
+            return self.wait_on_barrier(deadline);
+        }
+        // control commands are picked first if both are ready
+        let selector = Selector::new()
+            .recv(&self.control_receiver, Result::ok)
+            .recv(&self.internal_receiver, Result::ok);
+        match deadline {
+            Some(deadline) => selector
+                .wait_deadline(self.time.wake_at(deadline))
+                .ok()
+                .flatten(),
+            None => selector.wait(),
+        }
+    }
+
+    /// waits on the FrameBarrier instead of the channels, woken by the member states as well
+    fn wait_on_barrier(&self, deadline: Instant) -> Option<TickCommand> {
+        let seen = self.barrier.sequence();
+        if let Some(command) = self.next_command() {
+            return Some(command);
+        }
+        let timeout = self
+            .time
+            .wake_at(deadline)
+            .saturating_duration_since(Instant::now());
+        self.barrier.wait(seen, timeout);
+        self.next_command()
+    }
+
+    /// starts a new main frame if the speed allows it, returns whether it did
+    fn try_frame(&mut self) -> bool {
+        if self.is_paused() {
+            self.paused_since.get_or_insert(self.time.now());
+            return false;
+        }
+        if self.speed.is_manual() {
+            if self.manual_ticks.is_empty() || self.awaits_members() || self.fenced() {
+                return false;
+            }
+            // the requested frame is due right now
+            let now = self.time.now();
+            self.restart_timeline(now.checked_sub(self.speed.get_duration()).unwrap_or(now));
+        }
+        let next_frame = self.main_tick_counter.wrapping_add(1);
+        let due = match &self.timeline {
+            Some(timeline) => timeline.due_at(&self.speed, next_frame) <= self.time.now(),
+            None => self.pacer.is_due(self.time.now()),
+        };
+        if !due || self.awaits_members() || self.fenced() {
+            return false;
+        }
+        self.hidden_idle = false;
+        if self.when_all_hidden != HiddenPolicy::Emit && self.all_due_hidden(next_frame) {
+            if let Some(event) = self.idle.record(next_frame, false) {
+                self.emit(event);
+            }
+            match self.when_all_hidden {
+                HiddenPolicy::Skip => self.skip_frame(next_frame),
+                _ => {
+                    self.paused_since.get_or_insert(self.time.now());
+                    self.hidden_idle = true;
+                }
+            }
+            return false;
+        }
+        let mut instant_guard = self.instant.lock().unwrap();
+        self.main_tick_counter = next_frame;
+        let now = self.time.now();
+        let paused = self
+            .paused_since
+            .take()
+            .map(|since| now - since)
+            .unwrap_or_default();
+        let mut dropped = 0;
+        let (delta, scheduled_at) = match &mut self.timeline {
+            Some(timeline) => {
+                let scheduled_at = timeline.due_at(&self.speed, next_frame);
+                let delta = timeline.delta(&self.speed, next_frame);
+                self.sim_time = timeline.sim_time(&self.speed, next_frame);
+                // after a pause or once more than max_catch_up frames behind, the timeline
+                // starts over from this frame, leaving only max_catch_up frames to catch up
+                let period = self.speed.get_duration();
+                let max_catch_up = self.max_catch_up.unwrap_or(0);
+                let behind = (now - scheduled_at).as_nanos() / period.as_nanos().max(1);
+                if !paused.is_zero() {
+                    *timeline = FixedTimeline::new(now, next_frame, self.sim_time);
+                } else if behind > max_catch_up as u128 && !self.speed.is_manual() {
+                    let anchor = period
+                        .checked_mul(max_catch_up.min(u32::MAX as u64) as u32)
+                        .and_then(|catch_up| now.checked_sub(catch_up))
+                        .unwrap_or(now);
+                    *timeline = FixedTimeline::new(anchor, next_frame, self.sim_time);
+                    if self.max_catch_up.is_some() {
+                        dropped = (behind - max_catch_up as u128) as u64;
+                    }
+                }
+                (delta, scheduled_at)
+            }
+            None => {
+                let delta = (now - *instant_guard).saturating_sub(paused);
+                self.sim_time += delta;
+                let scheduled_at = self.pacer.advance(&self.speed, now);
+                if !paused.is_zero() {
+                    self.pacer = FramePacer::new(&self.speed, now);
+                }
+                (delta, scheduled_at)
+            }
+        };
+        let info = TickInfo {
+            tick_number: self.main_tick_counter,
+            delta,
+            raw_delta: now - *instant_guard,
+            scheduled_at,
+            sim_time: self.sim_time,
+            wall_time: now - self.started_at,
+            headroom: self.speed.get_duration(),
+            seed: self
+                .seed
+                .map(|seed| frame_seed(seed, self.main_tick_counter)),
+        };
+        *instant_guard = now;
+        drop(instant_guard);
+
+        if dropped > 0 {
+            self.emit(TickEvent::FramesDropped {
+                frame: next_frame,
+                dropped,
+            });
+        }
+        self.apply_topology();
+        self.advance_epoch();
+
+        self.last_frame = Some(info);
+        if let Some(waiter) = self.manual_ticks.pop_front() {
+            let _ = waiter.send(info);
+        }
+        self.checkpoints.frame_started(&info);
+        self.check_growth();
+        self.clock.publish(&info);
+        #[cfg(all(feature = "shm", unix))]
+        if let Some(shm) = &self.shm {
+            shm.publish(&info, &self.speed, now);
+        }
+        self.tweens.retain_mut(|tween| tween.advance(&info));
+        self.fire_timers(&info, now);
+        self.frame_conditions.retain_mut(|(predicate, waiter)| {
+            // waiters that gave up are dropped without evaluating their predicate
+            if waiter.is_disconnected() {
+                return false;
+            }
+            if !predicate(&info) {
+                return true;
+            }
+            let _ = waiter.send(info);
+            false
+        });
+        let next_frame_at = now + self.speed.get_duration();
+        self.notify_observers(info.delivered(next_frame_at));
+
+        // reservations for frames that already passed can never be used
+        self.reservations = self.reservations.split_off(&self.main_tick_counter);
+        let exclusive = self.reservations.remove(&self.main_tick_counter);
+
+        let counter = self.main_tick_counter;
+        self.sleeping.retain(|_, until| *until > counter);
+
+        let mut due_members: Vec<MemberID> = if let Some(owner) = exclusive {
+            vec![owner]
+        } else {
+            let map = self.member_map.lock().unwrap();
+            map.iter()
+                .filter_map(|(&member_id, (sf, member_info))| {
+                    if is_due(self.main_tick_counter, *sf, member_info.offset)
+                        && !self.sleeping.contains_key(&member_id)
+                    {
+                        Some(member_id)
+                    } else {
+                        None
+                    }
+                })
+                .collect()
+        };
+
+        let active = {
+            let map = self.member_map.lock().unwrap();
+            due_members.iter().any(|id| {
+                map.get(id).is_some_and(|(_sf, member_info)| {
+                    member_info.kind != MemberKind::Observer
+                        && !matches!(member_info.state, MemberState::Hidden)
+                })
+            })
+        };
+        if let Some(event) = self.idle.record(self.main_tick_counter, active) {
+            self.emit(event);
+        }
+
+        if due_members.is_empty() {
+            return true;
+        }
+
+        self.check_deadlines(&due_members);
+        self.check_leases(&due_members);
+
+        let all_ready = {
+            let map = self.member_map.lock().unwrap();
+            due_members.iter().all(|&id| {
+                !self.staged.contains(&id)
+                    && map
+                        .get(&id)
+                        .is_none_or(|(_sf, member_info)| self.accepts_tick(member_info))
+            })
+        };
+
+        // observers get every due tick, and with group barriers so do the groups whose due
+        // members are all ready, without waiting for the others. free running, every member
+        // that is ready gets its tick
+        let mut blocked = Vec::new();
+        if !all_ready {
+            let map = self.member_map.lock().unwrap();
+            let waiting: HashSet<Option<&String>> = due_members
+                .iter()
+                .filter_map(|id| map.get(id))
+                .filter(|(_sf, member_info)| !self.accepts_tick(member_info))
+                .map(|(_sf, member_info)| member_info.group.as_ref())
+                .collect();
+            let (ready, waiting): (Vec<MemberID>, Vec<MemberID>) =
+                due_members.iter().partition(|&id| {
+                    map.get(id).is_none_or(|(_sf, member_info)| {
+                        member_info.kind == MemberKind::Observer
+                            || self.free_run && self.accepts_tick(member_info)
+                            || self.group_barriers && !waiting.contains(&member_info.group.as_ref())
+                    })
+                });
+            due_members = ready;
+            blocked = waiting;
+        }
+        let deliver = !due_members.is_empty();
+
+        if all_ready {
+            if let Some((switched_to, event)) = self.load.end_frame(self.speed.get_duration()) {
+                if let Some(speed) = switched_to {
+                    self.set_speed(speed);
+                    self.restart_timeline(now);
+                }
+                self.emit(event);
+            }
+            if let Some(utilization) = self.load.utilization() {
+                for hook in &mut self.utilization_hooks {
+                    hook(utilization);
+                }
+            }
+        }
+
+        if deliver {
+            let headroom = self.headroom();
+            let mut deliveries: Vec<Delivery> = Vec::new();
+            {
+                let mut map = self.member_map.lock().unwrap();
+                match &self.ordered_dispatch {
+                    Some(order) => due_members.sort_by_cached_key(|&id| {
+                        map.get(&id)
+                            .map(|(_sf, member_info)| order.key(id, member_info))
+                    }),
+                    // boosted members are woken first, then by priority
+                    None => due_members.sort_by_key(|&id| {
+                        let priority = map.get(&id).map_or(0, |(_sf, info)| info.priority);
+                        (!self.fairness.is_boosted(id), Reverse(priority), id)
+                    }),
+                }
+                let mut last_key = None;
+                let delivered: HashSet<MemberID> = due_members.iter().copied().collect();
+                // members of later phases are staged until the earlier phases finished
+                let first_phase = due_members
+                    .iter()
+                    .filter_map(|id| map.get(id))
+                    .filter(|(_sf, member_info)| member_info.kind == MemberKind::Participant)
+                    .map(|(_sf, member_info)| member_info.phase)
+                    .min()
+                    .unwrap_or(0);
+                for &id in &due_members {
+                    if let Some((_sf, member_info)) = map.get_mut(&id) {
+                        if let Some(order) = &self.ordered_dispatch {
+                            let key = order.key(id, member_info);
+                            debug_assert!(
+                                last_key.as_ref().is_none_or(|last| *last < key),
+                                "ticks delivered out of the dispatch order"
+                            );
+                            last_key = Some(key);
+                        }
+                        if member_info.kind == MemberKind::Observer {
+                            member_info.last_tick = self.time.now();
+                            let mail = self.mailboxes.remove(&id).unwrap_or_default();
+                            deliveries.push(Delivery {
+                                sender: member_info.sender.clone(),
+                                parked: None,
+                                slice: None,
+                                observer: true,
+                                mail,
+                            });
+                            continue;
+                        }
+                        match member_info.state {
+                            MemberState::Finished | MemberState::Hidden
+                                if member_info.after.iter().any(|dep| delivered.contains(dep))
+                                    || member_info.phase > first_phase =>
+                            {
+                                self.staged.push(id);
+                            }
+                            MemberState::Finished | MemberState::Hidden => {
+                                deliveries.push(start_tick(
+                                    member_info,
+                                    info.tick_number,
+                                    self.mailboxes.remove(&id).unwrap_or_default(),
+                                    self.time.now(),
+                                    headroom,
+                                ));
+                            }
+                            // pipelining, the tick is queued until the member finished the
+                            // ones before it
+                            MemberState::Running if self.pipelines(member_info) => {
+                                deliveries.push(start_tick(
+                                    member_info,
+                                    info.tick_number,
+                                    self.mailboxes.remove(&id).unwrap_or_default(),
+                                    self.time.now(),
+                                    headroom,
+                                ));
+                            }
+                            MemberState::Running => {
+                                // only frame dropping members are still running here
+                            }
+                        }
+                    }
+                }
+                if !self.staged.is_empty() {
+                    self.staged_frame = delivered;
+                }
+            }
+
+            for delivery in deliveries {
+                delivery.send(info.delivered(next_frame_at));
+            }
+            if !self.staged.is_empty() {
+                self.staged_tick = Some((info.delivered(next_frame_at), headroom));
+            }
+        }
+
+        self.record_delivery(&due_members, deliver);
+        self.record_delivery(&blocked, false);
+        true
+    }
+
+    /// sends the staged members their tick once every member they come after finished
+    fn release_staged(&mut self) {
+        let Some((info, headroom)) = self.staged_tick else {
+            return;
+        };
+        let mut deliveries = Vec::new();
+        {
+            let mut map = self.member_map.lock().unwrap();
+            // a member that is still on the tick of an earlier frame doesn't hold this one
+            let running = |id: &MemberID| {
+                self.staged_frame.contains(id)
+                    && (self.staged.contains(id)
+                        || map.get(id).is_some_and(|(_sf, member_info)| {
+                            matches!(member_info.state, MemberState::Running)
+                        }))
+            };
+            // the ones it comes after and every participant of an earlier phase
+            let waits = |id: &MemberID| {
+                let Some((_sf, member_info)) = map.get(id) else {
+                    return false;
+                };
+                member_info.after.iter().any(running)
+                    || self.staged_frame.iter().any(|other| {
+                        map.get(other).is_some_and(|(_sf, other_info)| {
+                            other_info.kind == MemberKind::Participant
+                                && other_info.phase < member_info.phase
+                        }) && running(other)
+                    })
+            };
+            let (waiting, released): (Vec<MemberID>, Vec<MemberID>) =
+                self.staged.iter().partition(|id| waits(id));
+            for id in released {
+                if let Some((_sf, member_info)) = map.get_mut(&id) {
+                    let mail = self.mailboxes.remove(&id).unwrap_or_default();
+                    deliveries.push(start_tick(
+                        member_info,
+                        info.tick_number,
+                        mail,
+                        self.time.now(),
+                        headroom,
+                    ));
+                }
+            }
+            self.staged = waiting;
+        }
+        if self.staged.is_empty() {
+            self.staged_tick = None;
+            self.staged_frame.clear();
+        }
+        for delivery in deliveries {
+            delivery.send(info);
+        }
+    }
+
+    /// the part of the frame period the members didn't use recently, the whole period
+    /// before the first measurement
+    fn headroom(&self) -> Duration {
+        let period = self.speed.get_duration();
+        match self.load.utilization() {
+            Some(utilization) => period.mul_f64((1.0 - utilization).clamp(0.0, 1.0)),
+            None => period,
+        }
+    }
+
+    /// feeds the starvation tracking with whether the due members got their tick
+    fn record_delivery(&mut self, due_members: &[MemberID], delivered: bool) {
+        let events: Vec<TickEvent> = due_members
+            .iter()
+            .filter_map(|&id| self.fairness.record(id, delivered))
+            .collect();
+        for event in events {
+            self.emit(event);
+        }
+    }
+
+    /// feeds the circuit breaker with the members that are still Running while due
+    fn check_deadlines(&mut self, due_members: &[MemberID]) {
+        let mut events = Vec::new();
+        {
+            let mut map = self.member_map.lock().unwrap();
+            for id in due_members {
+                // boosted members are exempt from being demoted
+                if self.fairness.is_boosted(*id) {
+                    continue;
+                }
+                if let Some((sf, member_info)) = map.get_mut(id) {
+                    if member_info.kind != MemberKind::Participant {
+                        continue;
+                    }
+                    let missed = matches!(member_info.state, MemberState::Running);
+                    if let Some((factor, event)) = self.circuit_breaker.record(*id, *sf, missed) {
+                        *sf = factor;
+                        events.push(event);
+                    }
+                }
+            }
+        }
+        for event in events {
+            self.emit(event);
+        }
+    }
+
+    /// reports leased members that are still working on their due frame
+    fn check_leases(&mut self, due_members: &[MemberID]) {
+        let overrun: Vec<MemberID> = {
+            let map = self.member_map.lock().unwrap();
+            due_members
+                .iter()
+                .copied()
+                .filter(|id| {
+                    map.get(id).is_some_and(|(_sf, member_info)| {
+                        member_info.leased && matches!(member_info.state, MemberState::Running)
+                    })
+                })
+                .collect()
+        };
+        for id in overrun {
+            self.emit(TickEvent::LeaseOverrun { id });
+        }
+    }
+
+    fn memory_stats(&self) -> MemoryStats {
+        let map = self.member_map.lock().unwrap();
+        MemoryStats {
+            members: map.len(),
+            pending_topology: self.pending_topology.len(),
+            observers: self.observers.len(),
+            event_subscribers: self.event_subscribers.len(),
+            tweens: self.tweens.len(),
+            frame_timers: self.timers.len(),
+            wall_timers: self.wall_timers.len(),
+            schedules: self.scheduled.len(),
+            reservations: self.reservations.len(),
+            sleeping: self.sleeping.len(),
+            deferred: self.deferred.len(),
+            mailed: self.mailboxes.values().map(Vec::len).sum(),
+            topics: self.topics.len(),
+            buffered_commands: self.buffered.len(),
+            frame_conditions: self.frame_conditions.len(),
+            queued_commands: self.internal_receiver.len() + self.control_receiver.len(),
+            queued_replies: map
+                .values()
+                .map(|(_sf, member_info)| member_info.sender.len())
+                .sum(),
+        }
+    }
+
+    /// feeds the leak detection with the sizes of the collections every few frames
+    fn check_growth(&mut self) {
+        if !self.leaks.is_sampling(self.main_tick_counter) {
+            return;
+        }
+        let stats = self.memory_stats();
+        for event in self.leaks.sample(stats) {
+            self.emit(event);
+        }
+    }
+
+    /// sends the event to every subscriber, dropping the ones that hung up
+    fn emit(&mut self, event: TickEvent) {
+        let member = event.member_id().and_then(|id| {
+            let map = self.member_map.lock().unwrap();
+            let (sf, member_info) = map.get(&id)?;
+            Some(MemberDescriptor::of(id, *sf, member_info))
+        });
+        let event = ManagerEvent {
+            manager_id: self.manager_id,
+            member,
+            event,
+        };
+        self.event_subscribers.retain(|subscriber| {
+            !matches!(
+                subscriber.try_send(event.clone()),
+                Err(TrySendError::Disconnected(_))
+            )
+        });
+    }
+
+    /// sends the frame to every observer, dropping the ones that hung up.
+    /// observers that fall behind simply miss frames instead of blocking the loop
+    fn notify_observers(&mut self, info: TickInfo) {
+        let tags = &self.tags;
+        self.observers.retain(|(tag, observer)| {
+            if tag.as_ref().is_some_and(|tag| !tags.has(tag, &info)) {
+                // still drop observers that hung up
+                return !observer.is_disconnected();
+            }
+            !matches!(observer.try_send(info), Err(TrySendError::Disconnected(_)))
+        });
+    }
+}
+
+impl Drop for TickManager {
+    fn drop(&mut self) {
+        if let Some(name) = self.registered_name.take() {
+            registry::release(&name);
+        }
+        let Some((handler, exited)) = self.request_shutdown() else {
+            return;
+        };
+        match self.drop_policy {
+            DropPolicy::Join => {
+                let _ = handler.join();
+            }
+            DropPolicy::JoinWithTimeout(timeout) => {
+                if exited.recv_timeout(timeout) != Err(flume::RecvTimeoutError::Timeout) {
+                    let _ = handler.join();
+                }
+            }
+            DropPolicy::Detach => {}
+        }
+    }
+}
+
+/// whether a due member lets the frame's ticks go out, only participants are waited for
+fn is_ready(member_info: &MemberInfo) -> bool {
+    member_info.kind != MemberKind::Participant
+        || matches!(
+            member_info.state,
+            MemberState::Finished | MemberState::Hidden
+        )
+}
+
+/// marks the member as working on a new tick, the tick is sent with the returned Delivery
+fn start_tick(
+    member_info: &mut MemberInfo,
+    frame: u64,
+    mail: Vec<TickStateReply>,
+    now: Instant,
+    headroom: Duration,
+) -> Delivery {
+    // set before the tick is sent, the member only finishes it after
+    member_info.state = MemberState::Running;
+    member_info.state_cell.set(MemberState::Running);
+    member_info.state_cell.sent(frame);
+    member_info.last_tick = now;
+    member_info.leased = member_info.spread_work;
+    Delivery {
+        sender: member_info.sender.clone(),
+        parked: member_info.parked.clone(),
+        slice: member_info.budget.map(|b| b.min(headroom)),
+        observer: false,
+        mail,
+    }
+}
+
+/// a tick about to be sent to a member
+struct Delivery {
+    sender: Sender<TickStateReply>,
+    /// plain ticks of a parking member skip the channel
+    parked: Option<(Thread, Arc<WakeSlot>)>,
+    /// the slice of a background member
+    slice: Option<Duration>,
+    observer: bool,
+    /// Mail and Topic replies sent right before the tick
+    mail: Vec<TickStateReply>,
+}
+
+impl Delivery {
+    fn send(self, info: TickInfo) {
+        if let (Some((thread, slot)), None) = (&self.parked, self.slice) {
+            for reply in self.mail {
+                let _ = self.sender.send(reply);
+            }
+            slot.put(info, thread);
+            return;
+        }
+        let reply = match self.slice {
+            Some(budget) => TickStateReply::Slice(info, budget),
+            None => TickStateReply::Tick(info),
+        };
+        for reply in self.mail.into_iter().chain([reply]) {
+            // observers aren't waiting for their ticks, what doesn't fit is dropped
+            if self.observer {
+                let _ = self.sender.try_send(reply);
+            } else {
+                let _ = self.sender.send(reply);
+            }
+        }
+    }
+}
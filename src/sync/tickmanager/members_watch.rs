@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+
+use flume::Sender;
+
+use crate::{InternalMap, MemberDescriptor, MemberID, MemberKind, SpeedFactor};
+
+/// what a member looks like from the outside
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemberSummary {
+    pub id: MemberID,
+    pub name: Option<String>,
+    pub group: Option<String>,
+    pub speed_factor: SpeedFactor,
+    pub offset: u64,
+    pub kind: MemberKind,
+}
+
+impl MemberSummary {
+    pub fn descriptor(&self) -> MemberDescriptor {
+        MemberDescriptor {
+            id: self.id,
+            name: self.name.clone(),
+            factor: self.speed_factor,
+            group: self.group.clone(),
+        }
+    }
+}
+
+/// changes to the member set, see TickManagerHandle::watch_members
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MembersDiff {
+    /// all members at the time of subscribing, always the first message
+    Snapshot(Vec<MemberSummary>),
+    Added(MemberSummary),
+    Removed(MemberID),
+    /// the speed factor or offset of the member changed
+    Changed(MemberSummary),
+}
+
+pub(crate) fn summarize(map: &InternalMap) -> HashMap<MemberID, MemberSummary> {
+    map.iter()
+        .map(|(&id, (sf, member_info))| {
+            let summary = MemberSummary {
+                id,
+                name: member_info.name.clone(),
+                group: member_info.group.clone(),
+                speed_factor: *sf,
+                offset: member_info.offset,
+                kind: member_info.kind,
+            };
+            (id, summary)
+        })
+        .collect()
+}
+
+/// the member set last sent to the watchers, owned by the manager thread
+#[derive(Debug, Default)]
+pub(crate) struct MemberWatch {
+    watchers: Vec<Sender<MembersDiff>>,
+    known: HashMap<MemberID, MemberSummary>,
+}
+
+impl MemberWatch {
+    pub(crate) fn subscribe(&mut self, watcher: Sender<MembersDiff>, map: &InternalMap) {
+        // the existing watchers catch up first, so everyone knows the same member set
+        self.publish(map);
+        self.known = summarize(map);
+        if watcher
+            .send(MembersDiff::Snapshot(sorted(self.known.clone())))
+            .is_ok()
+        {
+            self.watchers.push(watcher);
+        }
+    }
+
+    /// takes over the watchers of another manager together with what they already know
+    pub(crate) fn adopt(&mut self, other: MemberWatch) {
+        self.watchers.extend(other.watchers);
+        self.known.extend(other.known);
+    }
+
+    /// sends the differences to the last published member set
+    pub(crate) fn publish(&mut self, map: &InternalMap) {
+        if self.watchers.is_empty() {
+            return;
+        }
+        let current = summarize(map);
+        let mut diffs = Vec::new();
+        for (id, summary) in &current {
+            match self.known.get(id) {
+                None => diffs.push(MembersDiff::Added(summary.clone())),
+                Some(known) if known != summary => {
+                    diffs.push(MembersDiff::Changed(summary.clone()))
+                }
+                Some(_) => {}
+            }
+        }
+        let mut removed: Vec<MemberID> = self
+            .known
+            .keys()
+            .filter(|id| !current.contains_key(id))
+            .copied()
+            .collect();
+        removed.sort_unstable();
+        diffs.extend(removed.into_iter().map(MembersDiff::Removed));
+        self.known = current;
+        if diffs.is_empty() {
+            return;
+        }
+        self.watchers
+            .retain(|watcher| diffs.iter().all(|diff| watcher.send(diff.clone()).is_ok()));
+    }
+}
+
+pub(crate) fn sorted(members: HashMap<MemberID, MemberSummary>) -> Vec<MemberSummary> {
+    let mut members: Vec<MemberSummary> = members.into_values().collect();
+    members.sort_by_key(|m| m.id);
+    members
+}
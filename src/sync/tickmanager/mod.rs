@@ -0,0 +1,40 @@
+pub mod accounting;
+pub mod builder;
+pub mod checkpoint;
+pub mod circuit_breaker;
+pub mod descriptor;
+pub mod events;
+pub mod fairness;
+pub mod idle;
+pub mod load;
+pub mod manager;
+pub mod members_watch;
+pub mod ordering;
+pub mod quiesce;
+pub mod quota;
+pub mod registry;
+pub mod restriction;
+pub mod standby;
+pub mod state_cell;
+pub mod tags;
+pub mod tickmanager_handle;
+pub use accounting::*;
+pub use builder::*;
+pub use checkpoint::*;
+pub use circuit_breaker::*;
+pub use descriptor::*;
+pub use events::*;
+pub use fairness::*;
+pub use idle::*;
+pub use load::*;
+pub use manager::*;
+pub use members_watch::*;
+pub use ordering::*;
+pub use quiesce::*;
+pub use quota::*;
+pub use registry::*;
+pub use restriction::*;
+pub use standby::*;
+pub use state_cell::*;
+pub use tags::*;
+pub use tickmanager_handle::*;
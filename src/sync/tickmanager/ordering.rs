@@ -0,0 +1,50 @@
+use crate::{MemberID, MemberInfo};
+
+/// the position of a member in the delivery order of a frame, compared field by field
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DispatchKey {
+    /// index of the group in OrderedDispatch::group_order, unlisted groups follow by name
+    /// and ungrouped members come last
+    pub group: (usize, Option<String>),
+    /// the phase offset of the member
+    pub phase: u64,
+    /// MemberConfig::priority negated, so higher priorities come first
+    pub priority: i64,
+    pub id: MemberID,
+}
+
+/// makes the Tick Manager deliver the ticks of a frame in the total order of DispatchKey,
+/// checked with debug assertions. boosted members aren't woken first while this is set
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OrderedDispatch {
+    /// groups that are woken first, in this order
+    pub group_order: Vec<String>,
+}
+
+impl OrderedDispatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// wakes the members of the groups first, in the given order
+    pub fn group_order<S: Into<String>>(mut self, groups: impl IntoIterator<Item = S>) -> Self {
+        self.group_order = groups.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn key(&self, id: MemberID, member_info: &MemberInfo) -> DispatchKey {
+        let group = match &member_info.group {
+            Some(group) => match self.group_order.iter().position(|g| g == group) {
+                Some(index) => (index, None),
+                None => (self.group_order.len(), Some(group.clone())),
+            },
+            None => (self.group_order.len() + 1, None),
+        };
+        DispatchKey {
+            group,
+            phase: member_info.offset,
+            priority: -(member_info.priority as i64),
+            id,
+        }
+    }
+}
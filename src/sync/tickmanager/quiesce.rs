@@ -0,0 +1,19 @@
+use crate::{TickCommand, TickManagerHandle};
+
+/// while alive, no member of the Tick Manager is in the middle of a tick
+#[derive(Debug)]
+pub struct QuiesceGuard {
+    manager_handle: TickManagerHandle,
+}
+
+impl QuiesceGuard {
+    pub(crate) fn new(manager_handle: TickManagerHandle) -> Self {
+        Self { manager_handle }
+    }
+}
+
+impl Drop for QuiesceGuard {
+    fn drop(&mut self) {
+        let _ = self.manager_handle.send(TickCommand::ReleaseQuiesce);
+    }
+}
@@ -0,0 +1,53 @@
+use crate::{InternalMap, SpeedFactor, TopologyChange};
+
+/// limits for the members of a tenant, see MemberConfig::tenant and
+/// TickManagerHandle::set_tenant_quota. only checked when a member registers
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TenantQuota {
+    pub max_members: Option<usize>,
+    /// the most ticks all members of the tenant may get per main frame together,
+    /// a member with speed factor n counts as 1 / n
+    pub max_rate: Option<f64>,
+}
+
+/// the limit of a TenantQuota a registration would have exceeded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuotaKind {
+    Members,
+    Rate,
+}
+
+impl TenantQuota {
+    /// the limit a new member with the speed factor would exceed, given the tenant's
+    /// registered and pending members
+    pub(crate) fn check(
+        &self,
+        tenant: &str,
+        speed_factor: SpeedFactor,
+        map: &InternalMap,
+        pending: &[TopologyChange],
+    ) -> Option<QuotaKind> {
+        let pending = pending.iter().filter_map(|change| match change {
+            TopologyChange::Register(id, member) => Some((id, member)),
+            _ => None,
+        });
+        let (members, rate) = map
+            .iter()
+            .chain(pending)
+            .filter(|(_id, (_sf, member_info))| member_info.tenant.as_deref() == Some(tenant))
+            .fold((0, 0.0), |(members, rate), (_id, (sf, _))| {
+                (members + 1, rate + 1.0 / *sf as f64)
+            });
+        if self.max_members.is_some_and(|max| members + 1 > max) {
+            return Some(QuotaKind::Members);
+        }
+        if self
+            .max_rate
+            // leaves room for the rounding of e.g. three members with factor 3
+            .is_some_and(|max| rate + 1.0 / speed_factor.max(1) as f64 > max + 1e-9)
+        {
+            return Some(QuotaKind::Rate);
+        }
+        None
+    }
+}
@@ -0,0 +1,65 @@
+use std::{
+    collections::HashMap,
+    sync::{LazyLock, Mutex},
+};
+
+use crate::{TickManager, TickManagerBuilder, TickManagerHandle};
+
+/// managers created through build_unique, by name
+static REGISTRY: LazyLock<Mutex<HashMap<String, TickManagerHandle>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// the result of TickManagerBuilder::build_unique
+#[derive(Debug)]
+// built once per manager, boxing the manager would only make matching on it clumsier
+#[allow(clippy::large_enum_variant)]
+pub enum UniqueManager {
+    /// no manager had the name yet, the caller owns the new one
+    Created {
+        manager: TickManager,
+        handle: TickManagerHandle,
+    },
+    /// a manager with the name already exists, the builder was discarded
+    Existing(TickManagerHandle),
+}
+
+impl UniqueManager {
+    pub fn handle(&self) -> &TickManagerHandle {
+        match self {
+            UniqueManager::Created { handle, .. } => handle,
+            UniqueManager::Existing(handle) => handle,
+        }
+    }
+
+    pub fn is_created(&self) -> bool {
+        matches!(self, UniqueManager::Created { .. })
+    }
+}
+
+impl TickManagerBuilder {
+    /// creates the TickManager unless one with the same name is already alive in this process,
+    /// in which case the handle of the existing one is returned instead.
+    /// the name is released once the created TickManager is dropped
+    pub fn build_unique(self, name: &str) -> UniqueManager {
+        let mut registry = REGISTRY.lock().unwrap();
+        if let Some(handle) = registry.get(name) {
+            return UniqueManager::Existing(handle.clone());
+        }
+        let (mut manager, handle) = self.build();
+        manager.registered_name = Some(name.to_string());
+        registry.insert(name.to_string(), handle.clone());
+        UniqueManager::Created { manager, handle }
+    }
+}
+
+impl TickManager {
+    /// the handle of the manager created with build_unique under this name, if it is alive
+    pub fn lookup(name: &str) -> Option<TickManagerHandle> {
+        REGISTRY.lock().unwrap().get(name).cloned()
+    }
+}
+
+/// called when a registered TickManager is dropped
+pub(crate) fn release(name: &str) {
+    REGISTRY.lock().unwrap().remove(name);
+}
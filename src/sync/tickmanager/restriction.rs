@@ -0,0 +1,120 @@
+use std::fmt;
+
+use crate::TickCommand;
+
+/// the namespace of a restricted TickManagerHandle.
+/// every member registered through the handle gets it as its tenant, so a TenantQuota set for
+/// the namespace limits the handle
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Namespace(pub String);
+
+impl From<&str> for Namespace {
+    fn from(name: &str) -> Self {
+        Namespace(name.to_string())
+    }
+}
+
+impl From<String> for Namespace {
+    fn from(name: String) -> Self {
+        Namespace(name)
+    }
+}
+
+impl fmt::Display for Namespace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// what a restricted TickManagerHandle may do besides registering members, driving the members
+/// of its namespace and observing the frames, see TickManagerHandle::restricted.
+/// the default allows nothing, TickCommand::Run is never allowed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Capabilities {
+    /// SetSpeed, Pause, Resume, Quiesce, TickNow and the FrameFence
+    pub control_speed: bool,
+    /// Shutdown, ShutdownWithin and HandOver
+    pub shutdown: bool,
+    /// changing the policies, quotas, tags, hooks and checkpoints of the Tick Manager,
+    /// deferring closures and unregistering whole groups
+    pub configure: bool,
+}
+
+impl Capabilities {
+    /// only what both allow
+    pub fn intersect(self, other: Capabilities) -> Capabilities {
+        Capabilities {
+            control_speed: self.control_speed && other.control_speed,
+            shutdown: self.shutdown && other.shutdown,
+            configure: self.configure && other.configure,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Restriction {
+    pub(crate) namespace: Namespace,
+    pub(crate) capabilities: Capabilities,
+}
+
+impl Restriction {
+    /// whether the handle may send the command, anything not listed is denied.
+    /// moves the members the command registers into the namespace, and leaves commands for
+    /// members to the Tick Manager to drop unless every member they name is in the namespace
+    pub(crate) fn admit(&self, command: &mut TickCommand) -> bool {
+        let caps = &self.capabilities;
+        if !command.member_ids().is_empty() {
+            let scoped = std::mem::replace(command, TickCommand::Apply(Vec::new()));
+            *command = TickCommand::InNamespace(self.namespace.0.clone(), Box::new(scoped));
+            return true;
+        }
+        match command {
+            TickCommand::Register(_, config) => {
+                config.tenant = Some(self.namespace.0.clone());
+                true
+            }
+            TickCommand::Apply(commands) => commands.iter_mut().all(|c| self.admit(c)),
+            TickCommand::Observe(_)
+            | TickCommand::ObserveTagged(..)
+            | TickCommand::WaitUntil(..)
+            | TickCommand::Tween(_)
+            | TickCommand::After(..)
+            | TickCommand::Every(..)
+            | TickCommand::Retry(..)
+            | TickCommand::Reschedule(..)
+            | TickCommand::Unschedule(_)
+            | TickCommand::SubscribeEvents(_)
+            | TickCommand::WatchMembers(_)
+            | TickCommand::ListMembers(_)
+            | TickCommand::SpeedLog(_)
+            | TickCommand::MemoryStats(_)
+            | TickCommand::Schedule(_) => true,
+            TickCommand::SetSpeed(_)
+            | TickCommand::Pause
+            | TickCommand::Resume
+            | TickCommand::Quiesce(_)
+            | TickCommand::ReleaseQuiesce
+            | TickCommand::TickNow(_)
+            | TickCommand::SetFrameFence(_)
+            | TickCommand::SignalFence(_) => caps.control_speed,
+            TickCommand::Shutdown
+            | TickCommand::ShutdownWithin(..)
+            | TickCommand::HandOver(..)
+            | TickCommand::Adopt(_) => caps.shutdown,
+            TickCommand::UnregisterGroup(_)
+            | TickCommand::SetCheckpointEvery(_)
+            | TickCommand::OnCheckpoint(_)
+            | TickCommand::TagFrames(..)
+            | TickCommand::SetCircuitBreaker(_)
+            | TickCommand::SetOverloadPolicy(_)
+            | TickCommand::SetHiddenPolicy(_)
+            | TickCommand::SetStarvationPolicy(_)
+            | TickCommand::SetIdlePolicy(_)
+            | TickCommand::SetTenantQuota(..)
+            | TickCommand::SetLeakDetection(_)
+            | TickCommand::OnUtilization(_)
+            | TickCommand::Defer(_) => caps.configure,
+            _ => false,
+        }
+    }
+}
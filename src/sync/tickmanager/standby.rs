@@ -0,0 +1,95 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use flume::Sender;
+
+#[cfg(all(feature = "shm", unix))]
+use crate::ShmPublisher;
+use crate::{
+    ActiveTween, Checkpoints, FramePredicate, FrameTags, InternalMap, ManagerEvent, MemberID,
+    MemberWatch, ScheduleEntry, SpeedLog, TickCommand, TickInfo, TickManager, TickManagerBuilder,
+    TickManagerHandle, TickStateReply, TopologyChange,
+};
+
+/// everything a Tick Manager passes on when it hands its members over to a standby
+pub struct Handover {
+    pub(crate) members: InternalMap,
+    pub(crate) pending_topology: Vec<TopologyChange>,
+    pub(crate) next_member_id: usize,
+    pub(crate) main_tick_counter: u64,
+    pub(crate) sim_time: Duration,
+    pub(crate) started_at: Instant,
+    pub(crate) speed_log: SpeedLog,
+    pub(crate) seed: Option<u64>,
+    pub(crate) last_frame: Option<TickInfo>,
+    pub(crate) observers: Vec<(Option<String>, Sender<TickInfo>)>,
+    pub(crate) tags: FrameTags,
+    pub(crate) event_subscribers: Vec<Sender<ManagerEvent>>,
+    pub(crate) member_watch: MemberWatch,
+    pub(crate) tweens: Vec<ActiveTween>,
+    pub(crate) timers: Vec<(u64, (u64, u64))>,
+    pub(crate) wall_timers: BTreeSet<(Instant, u64, u64)>,
+    pub(crate) scheduled: HashMap<u64, ScheduleEntry>,
+    pub(crate) reservations: BTreeMap<u64, MemberID>,
+    pub(crate) sleeping: HashMap<MemberID, u64>,
+    pub(crate) checkpoints: Checkpoints,
+    pub(crate) deferred: Vec<Box<dyn FnOnce() + Send>>,
+    pub(crate) mailboxes: HashMap<MemberID, Vec<TickStateReply>>,
+    pub(crate) topics: HashMap<Arc<str>, BTreeSet<MemberID>>,
+    pub(crate) buffered: Vec<TickCommand>,
+    pub(crate) frame_conditions: Vec<(FramePredicate, Sender<TickInfo>)>,
+    pub(crate) quiesce_count: usize,
+    pub(crate) paused: bool,
+    pub(crate) quiesce_waiters: Vec<Sender<()>>,
+    #[cfg(all(feature = "shm", unix))]
+    pub(crate) shm: Option<Arc<ShmPublisher>>,
+}
+
+/// a second, already running Tick Manager that takes over all members of the active one
+/// at a frame boundary, for reconfigurations that can't be done with runtime commands
+#[derive(Debug)]
+pub struct StandbyManager {
+    active: TickManagerHandle,
+    manager: TickManager,
+    handle: TickManagerHandle,
+}
+
+impl StandbyManager {
+    /// the handle of the standby, commands sent to it before switch_over affect only the standby
+    pub fn handle(&self) -> &TickManagerHandle {
+        &self.handle
+    }
+
+    /// waits until no member of the active Tick Manager is mid-tick, then moves all members,
+    /// observers, event subscribers, tweens and retries over to the standby.
+    /// members keep their ids and continue with the next frame number on the new speed.
+    /// the old Tick Manager forwards every command it still receives to the new one, keep it
+    /// alive as long as its handles are in use. returns None if the active manager has shut down
+    pub fn switch_over(self) -> Option<(TickManager, TickManagerHandle)> {
+        let guard = self.active.quiesce()?;
+        let (sender, receiver) = flume::bounded(1);
+        self.active
+            .send(TickCommand::HandOver(self.handle.clone(), sender))
+            .ok()?;
+        receiver.recv().ok()?;
+        // the release is forwarded, the standby starts out quiesced by this guard
+        drop(guard);
+        Some((self.manager, self.handle))
+    }
+}
+
+impl TickManagerHandle {
+    /// starts a standby Tick Manager from `builder` that shares the clock of this one
+    /// but has no members until StandbyManager::switch_over is called
+    pub fn prepare_standby(&self, builder: TickManagerBuilder) -> StandbyManager {
+        let (manager, handle) = TickManager::standby(builder, self.clock());
+        StandbyManager {
+            active: self.clone(),
+            manager,
+            handle,
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicU8, AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use crate::{FrameBarrier, MemberState};
+
+const FINISHED: u8 = 0;
+const RUNNING: u8 = 1;
+const HIDDEN: u8 = 2;
+
+/// the state of a member, shared between the TickMember and the Tick Manager.
+/// members set it without sending a command, the Tick Manager reads it before every barrier
+/// check and sets it to Running when it dispatches a tick
+#[derive(Debug)]
+pub struct StateCell {
+    state: AtomicU8,
+    created_at: Instant,
+    /// when the state was set last, in nanoseconds since created_at
+    changed_at: AtomicU64,
+    /// the latest frame the Tick Manager sent a tick of
+    sent: AtomicU64,
+    /// the frame of the tick the member took last
+    processing: AtomicU64,
+    /// notified with every state set, see watched_by
+    barrier: Option<Arc<FrameBarrier>>,
+}
+
+impl StateCell {
+    pub fn new(state: MemberState) -> Self {
+        Self {
+            state: AtomicU8::new(encode(&state)),
+            created_at: Instant::now(),
+            changed_at: AtomicU64::new(0),
+            sent: AtomicU64::new(0),
+            processing: AtomicU64::new(0),
+            barrier: None,
+        }
+    }
+
+    /// wakes the Tick Manager waiting on `barrier` whenever the state is set
+    pub(crate) fn watched_by(mut self, barrier: Arc<FrameBarrier>) -> Self {
+        self.barrier = Some(barrier);
+        self
+    }
+
+    pub fn get(&self) -> MemberState {
+        match self.state.load(Ordering::Acquire) {
+            FINISHED => MemberState::Finished,
+            RUNNING => MemberState::Running,
+            _ => MemberState::Hidden,
+        }
+    }
+
+    /// when the state was set last, the Tick Manager may only notice it a while later
+    pub fn changed_at(&self) -> Instant {
+        self.created_at + Duration::from_nanos(self.changed_at.load(Ordering::Acquire))
+    }
+
+    /// the frame of the tick the member is processing, or finished last if it isn't Running
+    pub fn processing(&self) -> u64 {
+        self.processing.load(Ordering::Acquire)
+    }
+
+    /// the ticks sent to the member that it hasn't finished yet, including the one it is processing
+    pub fn in_flight(&self) -> u64 {
+        let processing = self.processing();
+        let queued = self.sent.load(Ordering::Acquire).saturating_sub(processing);
+        queued + u64::from(matches!(self.get(), MemberState::Running) && processing != 0)
+    }
+
+    pub(crate) fn sent(&self, frame: u64) {
+        self.sent.store(frame, Ordering::Release);
+    }
+
+    /// set by the member when it takes a tick, queued ticks are taken without the Tick Manager
+    /// setting the state, so a member that waited for one is Running again
+    pub(crate) fn took(&self, frame: u64) {
+        if matches!(self.get(), MemberState::Finished) {
+            self.set(MemberState::Running);
+        }
+        self.processing.store(frame, Ordering::Release);
+    }
+
+    pub(crate) fn set(&self, state: MemberState) {
+        let since_created = self.created_at.elapsed().as_nanos() as u64;
+        self.changed_at.store(since_created, Ordering::Release);
+        self.state.store(encode(&state), Ordering::Release);
+        if let Some(barrier) = &self.barrier {
+            barrier.notify();
+        }
+    }
+}
+
+fn encode(state: &MemberState) -> u8 {
+    match state {
+        MemberState::Finished => FINISHED,
+        MemberState::Running => RUNNING,
+        MemberState::Hidden => HIDDEN,
+    }
+}
@@ -0,0 +1,45 @@
+use std::collections::HashMap;
+
+use crate::TickInfo;
+
+/// decides which main frames carry a tag, see TickManagerHandle::tag_frames
+#[derive(Debug, Clone, Copy)]
+pub enum TagRule {
+    /// every n-th frame, e.g. Every(60) tags frames 60, 120, 180.. at 60 fps
+    Every(u64),
+    Custom(fn(&TickInfo) -> bool),
+}
+
+impl TagRule {
+    pub fn matches(&self, info: &TickInfo) -> bool {
+        match self {
+            TagRule::Every(n) => info.tick_number.is_multiple_of((*n).max(1)),
+            TagRule::Custom(rule) => rule(info),
+        }
+    }
+}
+
+/// the tag rules of a Tick Manager, owned by the manager thread
+#[derive(Debug, Default)]
+pub(crate) struct FrameTags {
+    rules: HashMap<String, TagRule>,
+}
+
+impl FrameTags {
+    /// replaces the rule of the tag, None removes it
+    pub(crate) fn set(&mut self, tag: String, rule: Option<TagRule>) {
+        match rule {
+            Some(rule) => self.rules.insert(tag, rule),
+            None => self.rules.remove(&tag),
+        };
+    }
+
+    pub(crate) fn has(&self, tag: &str, info: &TickInfo) -> bool {
+        self.rules.get(tag).is_some_and(|rule| rule.matches(info))
+    }
+
+    /// takes over the rules of a handed over manager, rules of the same tag are replaced
+    pub(crate) fn adopt(&mut self, other: FrameTags) {
+        self.rules.extend(other.rules);
+    }
+}
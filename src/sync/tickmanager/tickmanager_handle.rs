@@ -0,0 +1,659 @@
+use flume::{Receiver, Sender};
+use std::{any::Any, sync::Arc, thread::Thread, time::Duration};
+
+use crate::{
+    Capabilities, CheckpointHook, CircuitBreakerPolicy, ClockDomain, CommandBuffer, Cycle,
+    FrameBarrier, Handover, HiddenPolicy, HookID, IdlePolicy, LeakPolicy, ManagerEvent, ManagerID,
+    MemberConfig, MemberID, MemberState, MemberSummary, MembersDiff, MemoryStats, Namespace,
+    OverloadPolicy, Period, QuiesceGuard, Restriction, RetryAttempt, RetryHandle, RetryPolicy,
+    RetryState, ScheduleHandle, ScheduleState, ScheduledMember, Speed, SpeedFactor, SpeedLog,
+    StarvationPolicy, TagRule, TenantQuota, TickClock, TickError, TickInfo, TickStateReply,
+    TweenState, UtilizationHook, WakeSlot,
+};
+
+/// evaluated on the manager thread at the start of every main frame, see TickManagerHandle::wait_until
+pub type FramePredicate = Box<dyn FnMut(&TickInfo) -> bool + Send>;
+
+/// commands that can be sent to the TickManager
+pub enum TickCommand {
+    // register a new member to the TickManager
+    Register(Sender<TickStateReply>, Box<MemberConfig>),
+    //remove a member from the TickManager
+    Unregister(HookID),
+    // change how many main frames pass between ticks of a member
+    SetSpeedFactor(MemberID, SpeedFactor),
+    // move a member to another phase within its speed factor, see MemberConfig::offset
+    SetOffset(MemberID, u64),
+    // remove every member of the group between two frames
+    // each of them receives a final Unregistered reply
+    UnregisterGroup(String),
+
+    ChangeMemberState(MemberID, MemberState),
+    // wake the parking member by unparking the thread instead of sending its ticks,
+    // sent again whenever it waits on another thread
+    Park(MemberID, Thread, Arc<WakeSlot>),
+    // send the ticks of the parking member over the channel again, while it is awaited
+    Unpark(MemberID),
+
+    // receive every main frame without taking part in the barrier
+    // the observer is removed once its receiver is dropped
+    Observe(Sender<TickInfo>),
+
+    // call the hook with the rolling utilization after every dispatched frame
+    OnUtilization(UtilizationHook),
+    // run checkpoints every n main frames, None turns them off
+    SetCheckpointEvery(Option<u64>),
+    OnCheckpoint(CheckpointHook),
+    // run once the epoch moved past the current frame
+    Defer(Box<dyn FnOnce() + Send>),
+    // run on the manager thread
+    Run(Box<dyn FnOnce() + Send>),
+    // send the first frame the predicate is true for
+    WaitUntil(FramePredicate, Sender<TickInfo>),
+    // apply the commands together right before the next frame starts
+    Apply(Vec<TickCommand>),
+    // deliver the payload from the first member to the second with its next tick
+    SendTo(MemberID, MemberID, Box<dyn Any + Send>),
+    // deliver the messages published on the topic to the member with its next ticks
+    Subscribe(MemberID, String),
+    Unsubscribe(MemberID, String),
+    // deliver the payload to every subscriber of the topic with its next tick
+    Publish(MemberID, String, Arc<dyn Any + Send + Sync>),
+
+    // like Observe, but only for the frames carrying the tag
+    ObserveTagged(String, Sender<TickInfo>),
+    // set the rule of a tag, None removes it
+    TagFrames(String, Option<TagRule>),
+
+    // interpolate a value once per main frame
+    Tween(Arc<TweenState>),
+
+    // fire once after the period
+    After(Period, Sender<TickInfo>, Arc<ScheduleState>),
+    // fire every period
+    Every(Period, Sender<TickInfo>, Arc<ScheduleState>),
+    // send retry attempts with exponential backoff
+    Retry(
+        RetryPolicy,
+        Sender<RetryAttempt>,
+        Arc<RetryState>,
+        Arc<ScheduleState>,
+    ),
+    // move a scheduled item to the given amount of main frames from now
+    Reschedule(u64, u64),
+    // drop a cancelled item
+    Unschedule(u64),
+
+    // receive TickEvents, the subscriber is removed once its receiver is dropped
+    SubscribeEvents(Sender<ManagerEvent>),
+
+    // send the current members, then every change to them
+    WatchMembers(Sender<MembersDiff>),
+    // reply with the current members
+    ListMembers(Sender<Vec<MemberSummary>>),
+
+    // turn the deadline circuit breaker on or off
+    SetCircuitBreaker(Option<CircuitBreakerPolicy>),
+
+    // turn overload detection on or off
+    SetOverloadPolicy(Option<OverloadPolicy>),
+    // change what happens to frames in which every due member is Hidden
+    SetHiddenPolicy(HiddenPolicy),
+
+    // change the speed of the main frames, the pacing starts over from the latest frame
+    SetSpeed(Speed),
+    // start a frame while the speed is Manual, the sender gets it once it started
+    TickNow(Sender<TickInfo>),
+    // gate the frames on a FrameFence with the given depth, or lift the gate with None
+    SetFrameFence(Option<u64>),
+    // the frame was presented, see FrameFence::signal
+    SignalFence(u64),
+    // reply with every speed the frames ran at
+    SpeedLog(Sender<SpeedLog>),
+
+    // turn priority boosting for starved members on or off
+    SetStarvationPolicy(Option<StarvationPolicy>),
+    // turn the Idle and Active events on or off
+    SetIdlePolicy(Option<IdlePolicy>),
+
+    // limit the members of a tenant, or lift the limits with None
+    SetTenantQuota(String, Option<TenantQuota>),
+
+    // turn reporting of growing collections on or off
+    SetLeakDetection(Option<LeakPolicy>),
+    // reply with the sizes of the internal collections
+    MemoryStats(Sender<MemoryStats>),
+
+    // dispatch only this member during the given main frame
+    // replies whether the frame could be reserved
+    ReserveExclusive(MemberID, u64, Sender<bool>),
+
+    // the member isn't due before the given main frame
+    SleepUntil(MemberID, u64),
+    // the member isn't due for the given amount of its own ticks
+    SkipTicks(MemberID, u64),
+
+    // a tick handler of the member panicked, marks it Finished
+    // or unregisters it if the bool is set
+    ReportPanic(MemberID, String, bool),
+
+    // a tick handler of the member returned an error, the member keeps running
+    ReportError(MemberID, String),
+
+    // the worker of a supervised member was respawned
+    ReportRestart(MemberID, u32),
+
+    // stop starting frames, the sender is notified once no member is Running
+    Quiesce(Sender<()>),
+    // sent when a QuiesceGuard is dropped
+    ReleaseQuiesce,
+
+    // stop starting frames until Resume, members stay registered
+    Pause,
+    // start frames again, the first one is due a period later
+    Resume,
+
+    // move all members to the given standby manager, then forward every command there
+    // the sender is notified once the Handover was sent
+    HandOver(TickManagerHandle, Sender<()>),
+    // take over the members of another manager
+    Adopt(Box<Handover>),
+
+    // the current tick of the member only has to end before its next due frame
+    // replies that frame, or None if the member isn't mid-tick
+    Lease(MemberID, Sender<Option<u64>>),
+
+    // reply with the speed factor and offset of every registered member
+    Schedule(Sender<Vec<ScheduledMember>>),
+
+    // handle the command only if every member it names is a member of the tenant,
+    // sent by restricted handles in place of the commands for members
+    InNamespace(String, Box<TickCommand>),
+
+    // shutdown the Tick Manager
+    Shutdown,
+    // stop starting frames and shut down once no member is running or the timeout passed,
+    // replies the ids of the members that were still running
+    ShutdownWithin(Duration, Sender<Vec<MemberID>>),
+}
+
+impl TickCommand {
+    /// the members the command acts on or for
+    pub fn member_ids(&self) -> Vec<MemberID> {
+        match self {
+            TickCommand::Unregister(id)
+            | TickCommand::SetSpeedFactor(id, _)
+            | TickCommand::SetOffset(id, _)
+            | TickCommand::ChangeMemberState(id, _)
+            | TickCommand::Park(id, ..)
+            | TickCommand::Unpark(id)
+            | TickCommand::Subscribe(id, _)
+            | TickCommand::Unsubscribe(id, _)
+            | TickCommand::Publish(id, ..)
+            | TickCommand::ReserveExclusive(id, ..)
+            | TickCommand::SleepUntil(id, _)
+            | TickCommand::SkipTicks(id, _)
+            | TickCommand::ReportPanic(id, ..)
+            | TickCommand::ReportError(id, _)
+            | TickCommand::ReportRestart(id, _)
+            | TickCommand::Lease(id, _) => vec![*id],
+            TickCommand::SendTo(from, to, _) => vec![*from, *to],
+            TickCommand::InNamespace(_, command) => command.member_ids(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// whether the command takes effect on the next loop iteration of the Tick Manager,
+    /// ahead of every other command that is still waiting
+    pub fn is_control(&self) -> bool {
+        matches!(
+            self,
+            TickCommand::Shutdown
+                | TickCommand::ShutdownWithin(..)
+                | TickCommand::Quiesce(_)
+                | TickCommand::ReleaseQuiesce
+                | TickCommand::SetSpeed(_)
+                | TickCommand::Pause
+                | TickCommand::Resume
+        )
+    }
+}
+
+/// this struct will be given to other threads, so they can create new Tick Hooks
+#[derive(Debug, Clone)]
+pub struct TickManagerHandle {
+    global_sender: Sender<TickCommand>,
+    /// for the control commands, see TickCommand::is_control
+    control_sender: Sender<TickCommand>,
+    clock: Arc<TickClock>,
+    manager_id: ManagerID,
+    /// disconnects once the manager thread returned
+    exited: Receiver<()>,
+    /// set for handles made by restricted
+    restriction: Option<Arc<Restriction>>,
+    /// notified with every command sent, see FrameBarrier
+    barrier: Arc<FrameBarrier>,
+}
+
+impl TickManagerHandle {
+    pub fn new(
+        global_sender: Sender<TickCommand>,
+        control_sender: Sender<TickCommand>,
+        clock: Arc<TickClock>,
+        manager_id: ManagerID,
+        exited: Receiver<()>,
+    ) -> Self {
+        TickManagerHandle {
+            global_sender,
+            control_sender,
+            clock,
+            manager_id,
+            exited,
+            restriction: None,
+            barrier: Arc::default(),
+        }
+    }
+
+    /// wakes the Tick Manager waiting on `barrier` whenever a command is sent
+    pub(crate) fn watched_by(mut self, barrier: Arc<FrameBarrier>) -> Self {
+        self.barrier = barrier;
+        self
+    }
+
+    /// a handle that registers its members in the namespace and can only send the
+    /// commands the capabilities allow, for plugins or scripting layers that shouldn't
+    /// control the whole Tick Manager.
+    /// restricting a restricted handle allows only what both allow, in the same namespace.
+    /// fails with TickError::OutsideNamespace for any other namespace
+    pub fn restricted(
+        &self,
+        namespace: impl Into<Namespace>,
+        capabilities: Capabilities,
+    ) -> Result<Self, TickError> {
+        let namespace = namespace.into();
+        let restriction = match &self.restriction {
+            Some(outer) if outer.namespace != namespace => {
+                return Err(TickError::OutsideNamespace(outer.namespace.0.clone()));
+            }
+            Some(outer) => Restriction {
+                namespace,
+                capabilities: outer.capabilities.intersect(capabilities),
+            },
+            None => Restriction {
+                namespace,
+                capabilities,
+            },
+        };
+        Ok(TickManagerHandle {
+            restriction: Some(Arc::new(restriction)),
+            ..self.clone()
+        })
+    }
+
+    /// the namespace of a restricted handle
+    pub fn namespace(&self) -> Option<&Namespace> {
+        self.restriction.as_ref().map(|r| &r.namespace)
+    }
+
+    /// shuts the Tick Manager down once no member is in the middle of a tick, waiting at most
+    /// `timeout` for that. members still running then are sent Cancelled, every other member
+    /// Unregistered. blocks until the manager thread returned and gives the ids of the
+    /// cancelled members, so a wedged member can't hang the process exit
+    pub fn shutdown_with_timeout(&self, timeout: Duration) -> Vec<MemberID> {
+        let (sender, receiver) = flume::bounded(1);
+        if self
+            .send(TickCommand::ShutdownWithin(timeout, sender))
+            .is_err()
+        {
+            return Vec::new();
+        }
+        let cancelled = receiver.recv().unwrap_or_default();
+        // only ever disconnects
+        let _ = self.exited.recv();
+        cancelled
+    }
+
+    /// whether the thread of the Tick Manager returned
+    pub fn is_shut_down(&self) -> bool {
+        self.exited.is_disconnected()
+    }
+
+    /// the id of the Tick Manager this handle belongs to
+    pub fn manager_id(&self) -> ManagerID {
+        self.manager_id
+    }
+
+    /// the clock of the Tick Manager, readable without sending commands
+    pub fn clock(&self) -> Arc<TickClock> {
+        self.clock.clone()
+    }
+
+    /// sends a message to the Tick Manager
+    /// control commands skip the commands that are still waiting, see TickCommand::is_control.
+    /// a restricted handle gives commands its Capabilities don't allow back as an error
+    pub fn send(&self, mut command: TickCommand) -> Result<(), flume::SendError<TickCommand>> {
+        if let Some(restriction) = &self.restriction
+            && !restriction.admit(&mut command)
+        {
+            return Err(flume::SendError(command));
+        }
+        if command.is_control() {
+            self.control_sender.send(command)?;
+        } else {
+            self.global_sender.send(command)?;
+        }
+        self.barrier.notify();
+        Ok(())
+    }
+
+    /// blocks until the next main frame starts, without registering a member.
+    /// returns None if the Tick Manager has shut down
+    pub fn sleep_until_next_frame(&self) -> Option<TickInfo> {
+        let (sender, receiver) = flume::bounded(1);
+        self.send(TickCommand::Observe(sender)).ok()?;
+        receiver.recv().ok()
+    }
+
+    /// blocks until the predicate is true for a main frame and returns that frame.
+    /// the predicate is evaluated on the manager thread at the start of every frame, so it may
+    /// keep state, e.g. to wait until the measured frame rate is stable.
+    /// returns None if the Tick Manager has shut down
+    pub fn wait_until(
+        &self,
+        predicate: impl FnMut(&TickInfo) -> bool + Send + 'static,
+    ) -> Option<TickInfo> {
+        self.frame_condition(predicate)?.recv().ok()
+    }
+
+    /// registers the predicate, the receiver gets the first frame it is true for
+    pub(crate) fn frame_condition(
+        &self,
+        predicate: impl FnMut(&TickInfo) -> bool + Send + 'static,
+    ) -> Option<flume::Receiver<TickInfo>> {
+        let (sender, receiver) = flume::bounded(1);
+        self.send(TickCommand::WaitUntil(Box::new(predicate), sender))
+            .ok()?;
+        Some(receiver)
+    }
+
+    /// schedules retries on the tick loop, each attempt is sent to `task_sender` once due.
+    /// cancel the returned handle once the task succeeded
+    pub fn retry(&self, policy: RetryPolicy, task_sender: Sender<RetryAttempt>) -> RetryHandle {
+        let state = Arc::new(RetryState::default());
+        let schedule = self
+            .schedule(|schedule| TickCommand::Retry(policy, task_sender, state.clone(), schedule));
+        RetryHandle::new(schedule, state)
+    }
+
+    /// receives every TickEvent from now on, tagged with the id of the Tick Manager.
+    /// events are dropped for this subscriber while its channel is full.
+    /// None if the Tick Manager has shut down
+    pub fn subscribe_events(&self) -> Option<flume::Receiver<ManagerEvent>> {
+        let (sender, receiver) = flume::bounded(256);
+        self.send(TickCommand::SubscribeEvents(sender)).ok()?;
+        Some(receiver)
+    }
+
+    /// turns the deadline circuit breaker on, or off with None
+    pub fn set_circuit_breaker(
+        &self,
+        policy: Option<CircuitBreakerPolicy>,
+    ) -> Result<(), flume::SendError<TickCommand>> {
+        self.send(TickCommand::SetCircuitBreaker(policy))
+    }
+
+    /// changes the speed of the main frames without touching the members, e.g. to switch
+    /// between 30 and 60 fps. the next frame is due one new period after the latest one started
+    pub fn set_speed(&self, speed: Speed) -> Result<(), flume::SendError<TickCommand>> {
+        self.send(TickCommand::SetSpeed(speed))
+    }
+
+    /// turns overload detection on, or off with None
+    pub fn set_overload_policy(
+        &self,
+        policy: Option<OverloadPolicy>,
+    ) -> Result<(), flume::SendError<TickCommand>> {
+        self.send(TickCommand::SetOverloadPolicy(policy))
+    }
+
+    /// changes what happens to frames in which every due member is Hidden,
+    /// see TickManagerBuilder::when_all_hidden
+    pub fn set_hidden_policy(
+        &self,
+        policy: HiddenPolicy,
+    ) -> Result<(), flume::SendError<TickCommand>> {
+        self.send(TickCommand::SetHiddenPolicy(policy))
+    }
+
+    /// calls `hook` on the manager thread with the rolling utilization
+    /// (busy time / frame period) after every dispatched frame, 1.0 means the frame period was fully used.
+    /// the hook should return quickly, it delays the frame it is called in
+    pub fn on_utilization(
+        &self,
+        hook: impl FnMut(f64) + Send + 'static,
+    ) -> Result<(), flume::SendError<TickCommand>> {
+        self.send(TickCommand::OnUtilization(Box::new(hook)))
+    }
+
+    /// takes a checkpoint every `every` main frames, or none with None. before the next frame
+    /// starts, the manager waits until no member is in the middle of a tick, runs the checkpoint
+    /// hooks and emits TickEvent::CheckpointCompleted
+    pub fn set_checkpoint_every(
+        &self,
+        every: Option<u64>,
+    ) -> Result<(), flume::SendError<TickCommand>> {
+        self.send(TickCommand::SetCheckpointEvery(every))
+    }
+
+    /// calls `hook` on the manager thread at every checkpoint, in the order they were added
+    pub fn on_checkpoint(
+        &self,
+        hook: impl FnMut(&TickInfo) + Send + 'static,
+    ) -> Result<(), flume::SendError<TickCommand>> {
+        self.send(TickCommand::OnCheckpoint(Box::new(hook)))
+    }
+
+    /// turns the TickEvent::Idle and TickEvent::Active events on, or off with None
+    pub fn set_idle_policy(
+        &self,
+        policy: Option<IdlePolicy>,
+    ) -> Result<(), flume::SendError<TickCommand>> {
+        self.send(TickCommand::SetIdlePolicy(policy))
+    }
+
+    /// turns priority boosting for starved members on, or off with None
+    pub fn set_starvation_policy(
+        &self,
+        policy: Option<StarvationPolicy>,
+    ) -> Result<(), flume::SendError<TickCommand>> {
+        self.send(TickCommand::SetStarvationPolicy(policy))
+    }
+
+    /// limits the members registered with MemberConfig::tenant, or lifts the limits with None.
+    /// registrations beyond the quota fail with TickError::QuotaExceeded, members that
+    /// are already registered stay
+    pub fn set_tenant_quota(
+        &self,
+        tenant: impl Into<String>,
+        quota: Option<TenantQuota>,
+    ) -> Result<(), flume::SendError<TickCommand>> {
+        self.send(TickCommand::SetTenantQuota(tenant.into(), quota))
+    }
+
+    /// samples the MemoryStats every few frames and emits TickEvent::UnboundedGrowth for
+    /// collections that keep growing, or stops with None
+    pub fn set_leak_detection(
+        &self,
+        policy: Option<LeakPolicy>,
+    ) -> Result<(), flume::SendError<TickCommand>> {
+        self.send(TickCommand::SetLeakDetection(policy))
+    }
+
+    /// the sizes of the internal collections and queues right now.
+    /// returns None if the Tick Manager has shut down
+    pub fn memory_stats(&self) -> Option<MemoryStats> {
+        let (sender, receiver) = flume::bounded(1);
+        self.send(TickCommand::MemoryStats(sender)).ok()?;
+        receiver.recv().ok()
+    }
+
+    /// starts exactly one main frame while the speed is Speed::Manual, after the members due
+    /// in it finished their previous tick. blocks until the frame started and returns it,
+    /// with other speeds that is simply the next frame. returns None if the Tick Manager
+    /// has shut down
+    pub fn tick_now(&self) -> Option<TickInfo> {
+        let (sender, receiver) = flume::bounded(1);
+        self.send(TickCommand::TickNow(sender)).ok()?;
+        receiver.recv().ok()
+    }
+
+    /// every speed the main frames ran at with the frame it took effect from, including
+    /// set_speed calls and switches by the OverloadPolicy.
+    /// returns None if the Tick Manager has shut down
+    pub fn speed_log(&self) -> Option<SpeedLog> {
+        let (sender, receiver) = flume::bounded(1);
+        self.send(TickCommand::SpeedLog(sender)).ok()?;
+        receiver.recv().ok()
+    }
+
+    /// stops starting main frames, e.g. for a pause menu, without unregistering anyone.
+    /// ticks that were already delivered still finish. the simulated time stands still
+    pub fn pause(&self) -> Result<(), flume::SendError<TickCommand>> {
+        self.send(TickCommand::Pause)
+    }
+
+    /// starts main frames again after pause. the first one is due a frame period later,
+    /// the frames missed while paused are not caught up
+    pub fn resume(&self) -> Result<(), flume::SendError<TickCommand>> {
+        self.send(TickCommand::Resume)
+    }
+
+    /// stops the Tick Manager from starting frames and blocks until no member is mid-tick.
+    /// frames resume once the guard is dropped. returns None if the Tick Manager has shut down
+    pub fn quiesce(&self) -> Option<QuiesceGuard> {
+        let (sender, receiver) = flume::bounded(1);
+        self.send(TickCommand::Quiesce(sender)).ok()?;
+        let guard = QuiesceGuard::new(self.clone());
+        receiver.recv().ok()?;
+        Some(guard)
+    }
+
+    /// removes all members of the group at once, between two frames
+    pub fn unregister_group(&self, group: &str) -> Result<(), flume::SendError<TickCommand>> {
+        self.send(TickCommand::UnregisterGroup(group.to_string()))
+    }
+
+    /// one full cycle of the current member schedule, to find frames where many members
+    /// coincide. returns None if the Tick Manager has shut down
+    pub fn cycle(&self) -> Option<Cycle> {
+        let (sender, receiver) = flume::bounded(1);
+        self.send(TickCommand::Schedule(sender)).ok()?;
+        let mut members = receiver.recv().ok()?;
+        members.sort_by_key(|m| m.id);
+        Some(Cycle::new(members))
+    }
+
+    /// receives the current members right away as MembersDiff::Snapshot,
+    /// followed by every change to them. the watcher is removed once its receiver is dropped.
+    /// None if the Tick Manager has shut down
+    pub fn watch_members(&self) -> Option<flume::Receiver<MembersDiff>> {
+        let (sender, receiver) = flume::unbounded();
+        self.send(TickCommand::WatchMembers(sender)).ok()?;
+        Some(receiver)
+    }
+
+    /// the current members ordered by id, None if the Tick Manager has shut down
+    pub fn list_members(&self) -> Option<Vec<MemberSummary>> {
+        let (sender, receiver) = flume::bounded(1);
+        self.send(TickCommand::ListMembers(sender)).ok()?;
+        receiver.recv().ok()
+    }
+
+    /// tags every main frame the rule matches, replacing the previous rule of the tag.
+    /// None removes the tag
+    pub fn tag_frames(
+        &self,
+        tag: &str,
+        rule: Option<TagRule>,
+    ) -> Result<(), flume::SendError<TickCommand>> {
+        self.send(TickCommand::TagFrames(tag.to_string(), rule))
+    }
+
+    /// receives only the main frames carrying the tag, without registering a member.
+    /// frames are dropped while the channel is full, the observer is removed once the
+    /// receiver is dropped. None if the Tick Manager has shut down
+    pub fn observe_tagged(&self, tag: &str) -> Option<flume::Receiver<TickInfo>> {
+        let (sender, receiver) = flume::bounded(16);
+        self.send(TickCommand::ObserveTagged(tag.to_string(), sender))
+            .ok()?;
+        Some(receiver)
+    }
+
+    /// receives the main frame that starts the given amount of frames from now, once.
+    /// at least one frame is waited
+    pub fn after_frames(&self, frames: u64) -> (ScheduleHandle, flume::Receiver<TickInfo>) {
+        let (sender, receiver) = flume::bounded(1);
+        let schedule =
+            self.schedule(|state| TickCommand::After(Period::Frames(frames), sender, state));
+        (schedule, receiver)
+    }
+
+    /// receives every given amount of main frames, until cancelled or the receiver is dropped.
+    /// frames are dropped while the channel is full
+    pub fn every_frames(&self, frames: u64) -> (ScheduleHandle, flume::Receiver<TickInfo>) {
+        let (sender, receiver) = flume::bounded(16);
+        let schedule =
+            self.schedule(|state| TickCommand::Every(Period::Frames(frames), sender, state));
+        (schedule, receiver)
+    }
+
+    /// receives the latest main frame once the delay passed in the clock domain, once
+    pub fn after(
+        &self,
+        delay: Duration,
+        domain: ClockDomain,
+    ) -> (ScheduleHandle, flume::Receiver<TickInfo>) {
+        let (sender, receiver) = flume::bounded(1);
+        let period = Period::new(delay, domain);
+        let schedule = self.schedule(|state| TickCommand::After(period, sender, state));
+        (schedule, receiver)
+    }
+
+    /// receives the latest main frame every period of the clock domain, until cancelled
+    /// or the receiver is dropped. frames are dropped while the channel is full
+    pub fn every(
+        &self,
+        period: Duration,
+        domain: ClockDomain,
+    ) -> (ScheduleHandle, flume::Receiver<TickInfo>) {
+        let (sender, receiver) = flume::bounded(16);
+        let period = Period::new(period, domain);
+        let schedule = self.schedule(|state| TickCommand::Every(period, sender, state));
+        (schedule, receiver)
+    }
+
+    /// sends the command scheduling an item, the handle is done right away if that fails
+    fn schedule(&self, command: impl FnOnce(Arc<ScheduleState>) -> TickCommand) -> ScheduleHandle {
+        let state = Arc::new(ScheduleState::default());
+        if self.send(command(state.clone())).is_err() {
+            state.mark_done();
+        }
+        ScheduleHandle::new(state, self.clone())
+    }
+
+    /// runs `deferred` on the manager thread at the start of the next main frame that begins
+    /// while no member is in the middle of a tick, see TickClock::epoch. meant to free memory
+    /// that members unlinked from shared lock-free structures during their tick.
+    /// observers aren't waited for
+    pub fn defer_until_next_frame(
+        &self,
+        deferred: impl FnOnce() + Send + 'static,
+    ) -> Result<(), flume::SendError<TickCommand>> {
+        self.send(TickCommand::Defer(Box::new(deferred)))
+    }
+
+    /// a CommandBuffer for this Tick Manager
+    pub fn command_buffer(&self) -> CommandBuffer {
+        CommandBuffer::new(self.clone())
+    }
+}
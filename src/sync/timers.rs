@@ -0,0 +1,281 @@
+use flume::{Receiver, Sender};
+use std::{
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use crate::{ActiveRetry, ClockDomain, TickClock, TickCommand, TickInfo, TickManagerHandle};
+
+/// measures simulated time of the Tick Manager instead of wall time
+#[derive(Debug)]
+pub struct TickStopwatch {
+    clock: Arc<TickClock>,
+    /// used to block until the next frame
+    frames: Receiver<TickInfo>,
+    state: Mutex<StopwatchState>,
+}
+
+#[derive(Debug)]
+struct StopwatchState {
+    /// time collected before the last stop
+    banked: Duration,
+    /// sim_time the current run started at, None while stopped
+    started_at: Option<Duration>,
+}
+
+impl TickStopwatch {
+    /// creates a running stopwatch
+    pub fn new(manager_handle: &TickManagerHandle) -> Self {
+        let (sender, frames) = flume::bounded(1);
+        manager_handle.send(TickCommand::Observe(sender)).unwrap();
+        let clock = manager_handle.clock();
+        Self {
+            state: Mutex::new(StopwatchState {
+                banked: Duration::ZERO,
+                started_at: Some(clock.sim_time()),
+            }),
+            clock,
+            frames,
+        }
+    }
+
+    /// simulated time measured so far
+    pub fn elapsed(&self) -> Duration {
+        let state = self.state.lock().unwrap();
+        state.banked + self.current_run(&state)
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.state.lock().unwrap().started_at.is_some()
+    }
+
+    /// stops measuring, keeping the time measured so far
+    pub fn stop(&self) {
+        let mut state = self.state.lock().unwrap();
+        let run = self.current_run(&state);
+        state.banked += run;
+        state.started_at = None;
+    }
+
+    /// continues measuring after a stop
+    pub fn start(&self) {
+        let mut state = self.state.lock().unwrap();
+        if state.started_at.is_none() {
+            state.started_at = Some(self.clock.sim_time());
+        }
+    }
+
+    /// sets the measured time back to zero, keeping the running state
+    pub fn reset(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.banked = Duration::ZERO;
+        if state.started_at.is_some() {
+            state.started_at = Some(self.clock.sim_time());
+        }
+    }
+
+    /// blocks until the next main frame, false if the Tick Manager has shut down
+    fn wait_frame(&self) -> bool {
+        self.frames.recv().is_ok()
+    }
+
+    fn current_run(&self, state: &StopwatchState) -> Duration {
+        match state.started_at {
+            Some(start) => self.clock.sim_time().saturating_sub(start),
+            None => Duration::ZERO,
+        }
+    }
+}
+
+/// counts simulated time down from a fixed duration
+#[derive(Debug)]
+pub struct Countdown {
+    stopwatch: TickStopwatch,
+    duration: Duration,
+}
+
+impl Countdown {
+    pub fn new(manager_handle: &TickManagerHandle, duration: Duration) -> Self {
+        Self {
+            stopwatch: TickStopwatch::new(manager_handle),
+            duration,
+        }
+    }
+
+    pub fn remaining(&self) -> Duration {
+        self.duration.saturating_sub(self.stopwatch.elapsed())
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.remaining().is_zero()
+    }
+
+    /// blocks until the countdown has run out.
+    /// returns false if the Tick Manager shut down before that
+    pub fn wait(&self) -> bool {
+        while !self.is_finished() {
+            if !self.stopwatch.wait_frame() {
+                return false;
+            }
+        }
+        true
+    }
+
+    pub fn stop(&self) {
+        self.stopwatch.stop();
+    }
+
+    pub fn start(&self) {
+        self.stopwatch.start();
+    }
+
+    /// starts the countdown over from its full duration
+    pub fn reset(&self) {
+        self.stopwatch.reset();
+    }
+}
+
+static NEXT_SCHEDULE_ID: AtomicU64 = AtomicU64::new(0);
+
+/// shared between a ScheduleHandle and the manager thread
+#[derive(Debug)]
+pub struct ScheduleState {
+    id: u64,
+    cancelled: AtomicBool,
+    done: AtomicBool,
+}
+
+impl Default for ScheduleState {
+    fn default() -> Self {
+        Self {
+            id: NEXT_SCHEDULE_ID.fetch_add(1, Ordering::Relaxed),
+            cancelled: AtomicBool::new(false),
+            done: AtomicBool::new(false),
+        }
+    }
+}
+
+impl ScheduleState {
+    pub(crate) fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+
+    pub(crate) fn mark_done(&self) {
+        self.done.store(true, Ordering::Release);
+    }
+}
+
+/// controls an item scheduled on the Tick Manager, like a one-shot or repeating timer
+#[derive(Debug, Clone)]
+pub struct ScheduleHandle {
+    state: Arc<ScheduleState>,
+    manager_handle: TickManagerHandle,
+}
+
+impl ScheduleHandle {
+    pub(crate) fn new(state: Arc<ScheduleState>, manager_handle: TickManagerHandle) -> Self {
+        Self {
+            state,
+            manager_handle,
+        }
+    }
+
+    /// the item won't fire anymore, e.g. a timeout once the reply arrived
+    pub fn cancel(&self) {
+        if !self.state.cancelled.swap(true, Ordering::AcqRel) {
+            let _ = self
+                .manager_handle
+                .send(TickCommand::Unschedule(self.state.id));
+        }
+    }
+
+    /// whether the item will still fire
+    pub fn is_pending(&self) -> bool {
+        !self.state.cancelled.load(Ordering::Acquire) && !self.state.done.load(Ordering::Acquire)
+    }
+
+    /// moves the next firing to the given amount of main frames from now, at least one.
+    /// a Wall item is kept where it was if the frames take longer than an Instant can hold.
+    /// false if the item won't fire anymore
+    pub fn reschedule(&self, frames: u64) -> bool {
+        self.is_pending()
+            && self
+                .manager_handle
+                .send(TickCommand::Reschedule(self.state.id, frames))
+                .is_ok()
+    }
+}
+
+/// how far out a scheduled item fires
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Period {
+    Frames(u64),
+    Sim(Duration),
+    Wall(Duration),
+}
+
+impl Period {
+    pub fn new(delay: Duration, domain: ClockDomain) -> Self {
+        match domain {
+            ClockDomain::Sim => Period::Sim(delay),
+            ClockDomain::Wall => Period::Wall(delay),
+        }
+    }
+
+    pub fn domain(&self) -> ClockDomain {
+        match self {
+            Period::Frames(_) | Period::Sim(_) => ClockDomain::Sim,
+            Period::Wall(_) => ClockDomain::Wall,
+        }
+    }
+
+    /// when the item fires if scheduled on the given frame, at least one frame later
+    pub(crate) fn due(&self, tick_number: u64, frame_period: Duration, now: Instant) -> Due {
+        match self {
+            Period::Frames(frames) => Due::Frame(tick_number + (*frames).max(1)),
+            Period::Sim(delay) => Due::Frame(frames_until(tick_number, frame_period, now + *delay)),
+            Period::Wall(delay) => Due::At(now + *delay),
+        }
+    }
+}
+
+/// where a scheduled item waits, the timer wheel for the Sim domain and
+/// a wall time queue for the Wall domain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Due {
+    Frame(u64),
+    At(Instant),
+}
+
+/// the first frame after `tick_number` that starts at or after `at`, if every frame starts on time
+pub(crate) fn frames_until(tick_number: u64, frame_period: Duration, at: Instant) -> u64 {
+    let frame_period = frame_period.max(Duration::from_nanos(1));
+    let wait = at.saturating_duration_since(Instant::now());
+    tick_number + (wait.as_nanos().div_ceil(frame_period.as_nanos()) as u64).max(1)
+}
+
+/// an item scheduled on the manager thread
+pub(crate) enum ScheduledTimer {
+    Retry(ActiveRetry),
+    /// fires once with the frame it became due on
+    Once(Sender<TickInfo>),
+    /// fires every period until cancelled or the receiver is dropped
+    Repeat(Period, Sender<TickInfo>),
+}
+
+/// a scheduled item as tracked by the manager thread.
+/// the timer queues refer to it by id and generation, moving it bumps the generation
+/// so the old entry is ignored once it comes up
+pub(crate) struct ScheduleEntry {
+    pub(crate) state: Arc<ScheduleState>,
+    pub(crate) generation: u64,
+    pub(crate) domain: ClockDomain,
+    pub(crate) timer: ScheduledTimer,
+}
@@ -0,0 +1,142 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use crate::{TickCommand, TickInfo, TickManagerHandle};
+
+/// how long a tween runs
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TweenDuration {
+    /// amount of main frames
+    Ticks(u64),
+    /// simulated time of the Tick Manager
+    Time(Duration),
+}
+
+/// maps the linear progress (0.0 to 1.0) of a tween to the eased progress
+#[derive(Debug, Clone, Copy)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    Custom(fn(f64) -> f64),
+}
+
+impl Easing {
+    pub fn apply(&self, t: f64) -> f64 {
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => t * (2.0 - t),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    -1.0 + (4.0 - 2.0 * t) * t
+                }
+            }
+            Easing::Custom(f) => f(t),
+        }
+    }
+}
+
+/// the part of a tween shared between the manager thread and its readers
+#[derive(Debug)]
+pub struct TweenState {
+    from: f64,
+    to: f64,
+    duration: TweenDuration,
+    easing: Easing,
+    /// f64 bits of the current value
+    value: AtomicU64,
+    finished: AtomicBool,
+}
+
+/// a value interpolated by the Tick Manager, readable from any thread without locking
+#[derive(Debug, Clone)]
+pub struct Tween {
+    state: Arc<TweenState>,
+}
+
+impl Tween {
+    /// starts interpolating from `from` to `to`, beginning with the next main frame.
+    /// fails if the Tick Manager has shut down
+    pub fn new(
+        manager_handle: &TickManagerHandle,
+        from: f64,
+        to: f64,
+        duration: TweenDuration,
+        easing: Easing,
+    ) -> Result<Self, flume::SendError<TickCommand>> {
+        let state = Arc::new(TweenState {
+            from,
+            to,
+            duration,
+            easing,
+            value: AtomicU64::new(from.to_bits()),
+            finished: AtomicBool::new(false),
+        });
+        manager_handle.send(TickCommand::Tween(state.clone()))?;
+        Ok(Self { state })
+    }
+
+    /// the value as of the latest main frame
+    pub fn value(&self) -> f64 {
+        f64::from_bits(self.state.value.load(Ordering::Acquire))
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.state.finished.load(Ordering::Acquire)
+    }
+}
+
+/// a tween as tracked by the manager thread
+pub(crate) struct ActiveTween {
+    state: Arc<TweenState>,
+    /// tick number and sim_time of the frame the tween started at
+    started: Option<(u64, Duration)>,
+}
+
+impl ActiveTween {
+    pub(crate) fn new(state: Arc<TweenState>) -> Self {
+        Self {
+            state,
+            started: None,
+        }
+    }
+
+    /// updates the value for this frame, returns false once the tween is done
+    pub(crate) fn advance(&mut self, info: &TickInfo) -> bool {
+        // nobody can read the value anymore
+        if Arc::strong_count(&self.state) == 1 {
+            return false;
+        }
+        let (start_tick, start_time) = *self
+            .started
+            .get_or_insert((info.tick_number, info.sim_time));
+
+        let progress = match self.state.duration {
+            TweenDuration::Ticks(0) => 1.0,
+            TweenDuration::Ticks(ticks) => (info.tick_number - start_tick) as f64 / ticks as f64,
+            TweenDuration::Time(duration) if duration.is_zero() => 1.0,
+            TweenDuration::Time(duration) => {
+                (info.sim_time - start_time).as_secs_f64() / duration.as_secs_f64()
+            }
+        }
+        .min(1.0);
+
+        let state = &self.state;
+        let value = state.from + (state.to - state.from) * state.easing.apply(progress);
+        state.value.store(value.to_bits(), Ordering::Release);
+        if progress >= 1.0 {
+            state.finished.store(true, Ordering::Release);
+            return false;
+        }
+        true
+    }
+}
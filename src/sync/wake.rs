@@ -0,0 +1,294 @@
+use std::{
+    collections::VecDeque,
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, AtomicU32, Ordering},
+    },
+    thread::{self, Thread},
+    time::{Duration, Instant},
+};
+
+use crate::{FrameWaiter, TickClock, TickInfo};
+
+/// whether parking members wait for their ticks, and the Tick Manager for its barrier, on a
+/// futex, see the `futex` feature. futex on Linux and Android, WaitOnAddress on Windows and
+/// __ulock_wait on macOS. on the others members park their thread and the barrier polls
+pub const FUTEX_WAKE: bool = cfg!(all(
+    feature = "futex",
+    any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "macos",
+        windows
+    )
+));
+
+/// where the Tick Manager puts the plain ticks of a parking member before waking it,
+/// see WakeStrategy::Park. pipelining queues up to TickManagerBuilder::pipeline_depth of them
+#[derive(Debug, Default)]
+pub struct WakeSlot {
+    ticks: Mutex<VecDeque<TickInfo>>,
+    /// bumped with every tick put in the slot, the member waits for it to change
+    sequence: AtomicU32,
+}
+
+impl WakeSlot {
+    /// hands the tick to the member and wakes the thread it waits on
+    pub(crate) fn put(&self, info: TickInfo, thread: &Thread) {
+        self.ticks.lock().unwrap().push_back(info);
+        self.sequence.fetch_add(1, Ordering::Release);
+        if FUTEX_WAKE {
+            futex::wake_all(&self.sequence);
+        } else {
+            thread.unpark();
+        }
+    }
+
+    pub(crate) fn take(&self) -> Option<TickInfo> {
+        self.ticks.lock().unwrap().pop_front()
+    }
+
+    /// puts a taken tick back in front without waking anyone
+    pub(crate) fn restore(&self, info: TickInfo) {
+        self.ticks.lock().unwrap().push_front(info);
+    }
+
+    /// read before checking the slot, wait returns right away once it changed since
+    pub(crate) fn sequence(&self) -> u32 {
+        self.sequence.load(Ordering::Acquire)
+    }
+
+    /// blocks the calling thread until a tick was put after `seen` was read, or the timeout passed.
+    /// may return early
+    pub(crate) fn wait(&self, seen: u32, timeout: Duration) {
+        if self.sequence() != seen {
+            return;
+        }
+        if FUTEX_WAKE {
+            futex::wait(&self.sequence, seen, timeout);
+        } else {
+            thread::park_timeout(timeout);
+        }
+    }
+}
+
+/// what the Tick Manager waits on while a frame waits for member states, with FUTEX_WAKE.
+/// bumped by every state a member sets and every command sent through a TickManagerHandle
+#[derive(Debug, Default)]
+pub struct FrameBarrier {
+    sequence: AtomicU32,
+    /// set while the Tick Manager waits, so notifying costs no syscall otherwise
+    sleeping: AtomicBool,
+}
+
+impl FrameBarrier {
+    /// wakes the Tick Manager if it waits on the barrier
+    pub(crate) fn notify(&self) {
+        if !FUTEX_WAKE {
+            return;
+        }
+        // pairs with wait, either it sees the new sequence or this sees it sleeping
+        self.sequence.fetch_add(1, Ordering::SeqCst);
+        if self.sleeping.load(Ordering::SeqCst) {
+            futex::wake_all(&self.sequence);
+        }
+    }
+
+    /// read before checking the member states and channels
+    pub(crate) fn sequence(&self) -> u32 {
+        self.sequence.load(Ordering::SeqCst)
+    }
+
+    /// blocks the calling thread until notified after `seen` was read, or the timeout passed.
+    /// may return early
+    pub(crate) fn wait(&self, seen: u32, timeout: Duration) {
+        self.sleeping.store(true, Ordering::SeqCst);
+        if self.sequence() == seen {
+            futex::wait(&self.sequence, seen, timeout);
+        }
+        self.sleeping.store(false, Ordering::SeqCst);
+    }
+}
+
+impl TickClock {
+    /// blocks until main frame `frame` started and returns the latest frame number, without
+    /// any registration in the Tick Manager. the thread parks, which std does on a futex on Linux.
+    /// returns a lower frame number once the Tick Manager shut down before the frame came,
+    /// see TickClock::close
+    pub fn wait_for_frame(&self, frame: u64) -> u64 {
+        let id = self.waiter_id();
+        if let Some(reached) = self.add_waiter(frame, id, FrameWaiter::Thread(thread::current())) {
+            return reached;
+        }
+        loop {
+            thread::park();
+            let reached = self.tick_number();
+            if reached >= frame || self.is_closed() {
+                return reached;
+            }
+        }
+    }
+
+    /// like wait_for_frame, None if the frame didn't start within the timeout or the Tick
+    /// Manager shut down before it
+    pub fn wait_for_frame_timeout(&self, frame: u64, timeout: Duration) -> Option<u64> {
+        let id = self.waiter_id();
+        let started = |reached: u64| (reached >= frame).then_some(reached);
+        if let Some(reached) = self.add_waiter(frame, id, FrameWaiter::Thread(thread::current())) {
+            return started(reached);
+        }
+        let deadline = Instant::now() + timeout;
+        loop {
+            let left = deadline.saturating_duration_since(Instant::now());
+            if left.is_zero() {
+                self.remove_waiter(id);
+                return None;
+            }
+            thread::park_timeout(left);
+            let reached = self.tick_number();
+            if reached >= frame || self.is_closed() {
+                return started(reached);
+            }
+        }
+    }
+}
+
+#[cfg(all(feature = "futex", any(target_os = "linux", target_os = "android")))]
+mod futex {
+    use std::{sync::atomic::AtomicU32, time::Duration};
+
+    pub(super) fn wait(word: &AtomicU32, expected: u32, timeout: Duration) {
+        let timeout = libc::timespec {
+            tv_sec: timeout.as_secs().min(libc::time_t::MAX as u64) as libc::time_t,
+            tv_nsec: timeout.subsec_nanos() as libc::c_long,
+        };
+        // SAFETY: the word outlives the call and FUTEX_WAIT only reads it. an interrupted or
+        // spurious wake up is fine, the caller checks the slot again
+        unsafe {
+            libc::syscall(
+                libc::SYS_futex,
+                word.as_ptr(),
+                libc::FUTEX_WAIT | libc::FUTEX_PRIVATE_FLAG,
+                expected,
+                &timeout as *const libc::timespec,
+            );
+        }
+    }
+
+    pub(super) fn wake_all(word: &AtomicU32) {
+        // SAFETY: FUTEX_WAKE only uses the address to find the waiters
+        unsafe {
+            libc::syscall(
+                libc::SYS_futex,
+                word.as_ptr(),
+                libc::FUTEX_WAKE | libc::FUTEX_PRIVATE_FLAG,
+                i32::MAX,
+            );
+        }
+    }
+}
+
+#[cfg(all(feature = "futex", windows))]
+mod futex {
+    use std::{ffi::c_void, sync::atomic::AtomicU32, time::Duration};
+
+    #[link(name = "synchronization")]
+    unsafe extern "system" {
+        fn WaitOnAddress(
+            address: *const c_void,
+            compare_address: *const c_void,
+            address_size: usize,
+            milliseconds: u32,
+        ) -> i32;
+        fn WakeByAddressAll(address: *const c_void);
+    }
+
+    pub(super) fn wait(word: &AtomicU32, expected: u32, timeout: Duration) {
+        // rounded up to whole milliseconds, below INFINITE
+        let millis = timeout
+            .as_nanos()
+            .div_ceil(1_000_000)
+            .min(u32::MAX as u128 - 1) as u32;
+        // SAFETY: both addresses point at 4 readable bytes for the duration of the call.
+        // a timeout or spurious wake up is fine, the caller checks again
+        unsafe {
+            WaitOnAddress(
+                word.as_ptr() as *const c_void,
+                &expected as *const u32 as *const c_void,
+                size_of::<u32>(),
+                millis,
+            );
+        }
+    }
+
+    pub(super) fn wake_all(word: &AtomicU32) {
+        // SAFETY: WakeByAddressAll only uses the address to find the waiters
+        unsafe { WakeByAddressAll(word.as_ptr() as *const c_void) }
+    }
+}
+
+#[cfg(all(feature = "futex", target_os = "macos"))]
+mod futex {
+    use std::{
+        ffi::{c_int, c_void},
+        sync::atomic::AtomicU32,
+        time::Duration,
+    };
+
+    const UL_COMPARE_AND_WAIT: u32 = 1;
+    const ULF_WAKE_ALL: u32 = 0x100;
+    const ULF_NO_ERRNO: u32 = 0x0100_0000;
+
+    // libSystem, the same calls std and libc++ wait on addresses with
+    unsafe extern "C" {
+        fn __ulock_wait(operation: u32, address: *mut c_void, value: u64, timeout: u32) -> c_int;
+        fn __ulock_wake(operation: u32, address: *mut c_void, wake_value: u64) -> c_int;
+    }
+
+    pub(super) fn wait(word: &AtomicU32, expected: u32, timeout: Duration) {
+        // in microseconds, rounded up since 0 waits forever
+        let micros = timeout
+            .as_nanos()
+            .div_ceil(1_000)
+            .clamp(1, u32::MAX as u128) as u32;
+        // SAFETY: the word outlives the call and is only read. a timeout, interrupt or
+        // spurious wake up is fine, the caller checks again
+        unsafe {
+            __ulock_wait(
+                UL_COMPARE_AND_WAIT | ULF_NO_ERRNO,
+                word.as_ptr() as *mut c_void,
+                expected as u64,
+                micros,
+            );
+        }
+    }
+
+    pub(super) fn wake_all(word: &AtomicU32) {
+        // SAFETY: __ulock_wake only uses the address to find the waiters
+        unsafe {
+            __ulock_wake(
+                UL_COMPARE_AND_WAIT | ULF_WAKE_ALL | ULF_NO_ERRNO,
+                word.as_ptr() as *mut c_void,
+                0,
+            );
+        }
+    }
+}
+
+/// never called, FUTEX_WAKE is false without futex support
+#[cfg(not(all(
+    feature = "futex",
+    any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "macos",
+        windows
+    )
+)))]
+mod futex {
+    use std::{sync::atomic::AtomicU32, time::Duration};
+
+    pub(super) fn wait(_word: &AtomicU32, _expected: u32, _timeout: Duration) {}
+
+    pub(super) fn wake_all(_word: &AtomicU32) {}
+}
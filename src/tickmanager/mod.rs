@@ -1,4 +0,0 @@
-pub mod manager;
-pub mod tickmanager_handle;
-pub use manager::*;
-pub use tickmanager_handle::*;